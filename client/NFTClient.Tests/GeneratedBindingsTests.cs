@@ -0,0 +1,151 @@
+using NFTClient.Generated;
+using Solnet.Wallet;
+using System;
+using System.Collections;
+using System.Collections.Generic;
+using System.IO;
+using System.Linq;
+using System.Reflection;
+using System.Text.Json;
+using System.Text.RegularExpressions;
+using Xunit;
+
+namespace NFTClient.Tests
+{
+    /// <summary>
+    /// Round-trips the golden Borsh fixtures (generated from the Rust types by `cargo run -p nft-bindgen`)
+    /// through the generated C# bindings
+    /// </summary>
+    public class GeneratedBindingsTests
+    {
+        private static readonly JsonElement Fixtures = JsonDocument
+            .Parse(File.ReadAllText(Path.Combine(AppContext.BaseDirectory, "borsh_fixtures.json")))
+            .RootElement;
+
+        public static IEnumerable<object[]> InstructionFixtures => FixtureData("instructions");
+
+        public static IEnumerable<object[]> AccountFixtures => FixtureData("accounts");
+
+        public static IEnumerable<object[]> PaddedAccountFixtures => FixtureData("padded_accounts");
+
+        [Theory]
+        [MemberData(nameof(InstructionFixtures))]
+        public void RoundTripsInstructionFixture(string name, string hex, string value)
+        {
+            var instruction = NFTInstruction.Deserialize(FromHex(hex));
+            Assert.Equal(PascalCase(name) + "Instruction", instruction.GetType().Name);
+            Assert.Equal(Canonical(FromJson(JsonDocument.Parse(value).RootElement)), Canonical(ToFixtureValue(instruction)));
+            Assert.Equal(hex, ToHex(instruction.Serialize()));
+        }
+
+        [Theory]
+        [MemberData(nameof(AccountFixtures))]
+        public void RoundTripsAccountFixture(string name, string hex, string value)
+        {
+            var type = typeof(NFTInstruction).Assembly.GetType($"NFTClient.Generated.{name}", throwOnError: true)!;
+            var account = type.GetMethod("FromAccountData")!.Invoke(null, new object[] { FromHex(hex) });
+            Assert.Equal(Canonical(FromJson(JsonDocument.Parse(value).RootElement)), Canonical(ToFixtureValue(account)));
+            Assert.Equal(hex, ToHex((byte[])type.GetMethod("ToAccountData")!.Invoke(account, null)!));
+        }
+
+        [Theory]
+        [MemberData(nameof(PaddedAccountFixtures))]
+        public void DecodesPaddedAccountFixture(string name, string hex, string value)
+        {
+            var type = typeof(NFTInstruction).Assembly.GetType($"NFTClient.Generated.{name}", throwOnError: true)!;
+            var account = type.GetMethod("FromAccountData")!.Invoke(null, new object[] { FromHex(hex) });
+            Assert.Equal(Canonical(FromJson(JsonDocument.Parse(value).RootElement)), Canonical(ToFixtureValue(account)));
+            var encoded = ToHex((byte[])type.GetMethod("ToAccountData")!.Invoke(account, null)!);
+            Assert.StartsWith(encoded, hex);
+            Assert.Matches("^0*$", hex.Substring(encoded.Length));
+        }
+
+        [Fact]
+        public void IgnoresTrailingAccountSpaceAndRejectsOtherAccountTypes()
+        {
+            var collection = Fixtures.GetProperty("accounts").EnumerateArray()
+                .First(fixture => fixture.GetProperty("name").GetString() == "CollectionAccount");
+            var padded = FromHex(collection.GetProperty("hex").GetString() + "0000");
+            Assert.Equal("Fixture Collection", CollectionAccount.FromAccountData(padded).Name);
+            var error = Assert.Throws<InvalidDataException>(() => NFTAccount.FromAccountData(padded));
+            Assert.Contains("discriminator mismatch", error.Message);
+        }
+
+        [Fact]
+        public void RejectsTrailingInstructionBytes()
+        {
+            var error = Assert.Throws<InvalidDataException>(() => NFTInstruction.Deserialize(new byte[] { 3, 0 }));
+            Assert.Contains("trailing bytes", error.Message);
+        }
+
+        [Fact]
+        public void MapsEveryErrorCode()
+        {
+            var errors = Fixtures.GetProperty("errors").EnumerateArray().ToList();
+            foreach (var fixture in errors)
+            {
+                var error = NFTErrors.FromCode(fixture.GetProperty("code").GetUInt32());
+                Assert.NotNull(error);
+                Assert.Equal(fixture.GetProperty("name").GetString(), error!.Value.ToString());
+                Assert.Equal(fixture.GetProperty("msg").GetString(), NFTErrors.Message(error.Value));
+            }
+            Assert.Null(NFTErrors.FromCode((uint)errors.Count));
+        }
+
+        private static IEnumerable<object[]> FixtureData(string kind) =>
+            Fixtures.GetProperty(kind).EnumerateArray().Select(fixture => new object[]
+            {
+                fixture.GetProperty("name").GetString()!,
+                fixture.GetProperty("hex").GetString()!,
+                fixture.GetProperty("value").GetRawText(),
+            });
+
+        private static byte[] FromHex(string hex) => Convert.FromHexString(hex);
+
+        private static string ToHex(byte[] bytes) => Convert.ToHexString(bytes).ToLowerInvariant();
+
+        private static string PascalCase(string name) =>
+            string.Concat(name.Split('_').Select(word => char.ToUpperInvariant(word[0]) + word.Substring(1)));
+
+        private static string SnakeCase(string name) => Regex.Replace(name, "(?<!^)([A-Z])", "_$1").ToLowerInvariant();
+
+        // Sorted keys make the comparison independent of property order
+        private static string Canonical(object? value) => JsonSerializer.Serialize(value);
+
+        private static object? FromJson(JsonElement element) => element.ValueKind switch
+        {
+            JsonValueKind.Object => new SortedDictionary<string, object?>(
+                element.EnumerateObject().ToDictionary(property => property.Name, property => FromJson(property.Value)),
+                StringComparer.Ordinal),
+            JsonValueKind.Array => element.EnumerateArray().Select(FromJson).ToList(),
+            JsonValueKind.String => element.GetString(),
+            JsonValueKind.Number => element.GetInt64(),
+            JsonValueKind.True => true,
+            JsonValueKind.False => false,
+            _ => null,
+        };
+
+        /// <summary>
+        /// Convert a decoded value into the fixture JSON shape: snake_case keys, 64-bit integers as strings,
+        /// base58 public keys and enum variant names
+        /// </summary>
+        private static object? ToFixtureValue(object? value) => value switch
+        {
+            null => null,
+            PublicKey key => key.ToString(),
+            ulong number => number.ToString(),
+            long number => number.ToString(),
+            byte or ushort or uint => Convert.ToInt64(value),
+            bool flag => flag,
+            string text => text,
+            Enum variant => variant.ToString(),
+            IEnumerable items => items.Cast<object?>().Select(ToFixtureValue).ToList(),
+            _ => new SortedDictionary<string, object?>(
+                value.GetType()
+                    .GetProperties(BindingFlags.Public | BindingFlags.Instance)
+                    .Where(property => property.Name != nameof(NFTInstruction.Discriminator))
+                    .ToDictionary(property => SnakeCase(property.Name), property => ToFixtureValue(property.GetValue(value))),
+                StringComparer.Ordinal),
+        };
+    }
+}
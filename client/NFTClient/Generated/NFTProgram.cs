@@ -0,0 +1,1320 @@
+// 此文件由 nft-bindgen 根据程序IDL生成, 请勿手动修改 (运行 `cargo run -p nft-bindgen` 重新生成)
+// Generated by nft-bindgen from the program IDL, do not edit by hand (run `cargo run -p nft-bindgen` to regenerate)
+using Solnet.Wallet;
+using System;
+using System.Buffers.Binary;
+using System.Collections.Generic;
+using System.IO;
+using System.Text;
+
+namespace NFTClient.Generated
+{
+    /// <summary>
+    /// Borsh encoder
+    /// </summary>
+    public sealed class BorshWriter
+    {
+        private readonly MemoryStream _stream = new MemoryStream();
+        private readonly BinaryWriter _writer;
+
+        public BorshWriter()
+        {
+            _writer = new BinaryWriter(_stream);
+        }
+
+        public void WriteU8(byte value) => _writer.Write(value);
+
+        public void WriteU16(ushort value) => _writer.Write(value);
+
+        public void WriteU32(uint value) => _writer.Write(value);
+
+        public void WriteU64(ulong value) => _writer.Write(value);
+
+        public void WriteI64(long value) => _writer.Write(value);
+
+        public void WriteBool(bool value) => _writer.Write((byte)(value ? 1 : 0));
+
+        public void WriteString(string value)
+        {
+            var bytes = Encoding.UTF8.GetBytes(value);
+            WriteU32((uint)bytes.Length);
+            _writer.Write(bytes);
+        }
+
+        public void WritePubkey(PublicKey value) => _writer.Write(value.KeyBytes);
+
+        public void WriteOptionTag(bool present) => WriteBool(present);
+
+        public void WriteBytes(byte[] value) => _writer.Write(value);
+
+        public byte[] ToArray()
+        {
+            _writer.Flush();
+            return _stream.ToArray();
+        }
+    }
+
+    /// <summary>
+    /// Borsh decoder
+    /// </summary>
+    public sealed class BorshReader
+    {
+        private readonly byte[] _data;
+        private int _offset;
+
+        public BorshReader(byte[] data, int offset = 0)
+        {
+            _data = data;
+            _offset = offset;
+        }
+
+        public byte ReadU8() => Take(1)[0];
+
+        public ushort ReadU16() => BinaryPrimitives.ReadUInt16LittleEndian(Take(2));
+
+        public uint ReadU32() => BinaryPrimitives.ReadUInt32LittleEndian(Take(4));
+
+        public ulong ReadU64() => BinaryPrimitives.ReadUInt64LittleEndian(Take(8));
+
+        public long ReadI64() => BinaryPrimitives.ReadInt64LittleEndian(Take(8));
+
+        public bool ReadBool()
+        {
+            var value = ReadU8();
+            if (value > 1)
+            {
+                throw new InvalidDataException($"Invalid bool {value}");
+            }
+            return value == 1;
+        }
+
+        public string ReadString() => Encoding.UTF8.GetString(Take(checked((int)ReadU32())));
+
+        public PublicKey ReadPubkey() => new PublicKey(Take(32));
+
+        public bool ReadOptionTag()
+        {
+            var tag = ReadU8();
+            if (tag > 1)
+            {
+                throw new InvalidDataException($"Invalid option tag {tag}");
+            }
+            return tag == 1;
+        }
+
+        public List<T> ReadVec<T>(Func<T> read)
+        {
+            var length = ReadU32();
+            var items = new List<T>();
+            for (uint i = 0; i < length; i++)
+            {
+                items.Add(read());
+            }
+            return items;
+        }
+
+        public T[] ReadArray<T>(int length, Func<T> read)
+        {
+            var items = new T[length];
+            for (var i = 0; i < length; i++)
+            {
+                items[i] = read();
+            }
+            return items;
+        }
+
+        public byte[] Take(int length)
+        {
+            if (length < 0 || _offset + length > _data.Length)
+            {
+                throw new InvalidDataException("Unexpected end of data");
+            }
+            var bytes = new byte[length];
+            Array.Copy(_data, _offset, bytes, 0, length);
+            _offset += length;
+            return bytes;
+        }
+
+        public void ExpectEnd()
+        {
+            if (_offset != _data.Length)
+            {
+                throw new InvalidDataException($"{_data.Length - _offset} trailing bytes");
+            }
+        }
+    }
+
+    /// <summary>
+    /// NFT collection account
+    /// </summary>
+    public class CollectionAccount
+    {
+        /// <summary>
+        /// Collection authority
+        /// </summary>
+        public PublicKey Authority { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Collection name
+        /// </summary>
+        public string Name { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Collection symbol
+        /// </summary>
+        public string Symbol { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Collection metadata URI
+        /// </summary>
+        public string Uri { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Royalty percentage (0-100)
+        /// </summary>
+        public byte RoyaltyPercentage { get; set; }
+
+        /// <summary>
+        /// Whether metadata can be updated
+        /// </summary>
+        public bool IsMutable { get; set; }
+
+        /// <summary>
+        /// Number of minted NFTs
+        /// </summary>
+        public ulong TotalMinted { get; set; }
+
+        /// <summary>
+        /// Pending authority awaiting acceptance
+        /// </summary>
+        public PublicKey? PendingAuthority { get; set; }
+
+        /// <summary>
+        /// Freeze authority
+        /// </summary>
+        public PublicKey? FreezeAuthority { get; set; }
+
+        /// <summary>
+        /// Transfer mode
+        /// </summary>
+        public TransferMode TransferMode { get; set; }
+
+        /// <summary>
+        /// Royalty creators (royalties go to the collection authority when empty)
+        /// </summary>
+        public List<Creator> Creators { get; set; } = new();
+
+        /// <summary>
+        /// Maximum supply (unlimited when empty)
+        /// </summary>
+        public ulong? MaxSupply { get; set; }
+
+        /// <summary>
+        /// Serial number assignment mode
+        /// </summary>
+        public SerialMode SerialMode { get; set; }
+
+        /// <summary>
+        /// Number of burned NFTs
+        /// </summary>
+        public ulong TotalBurned { get; set; }
+
+        /// <summary>
+        /// PDA bump seed
+        /// </summary>
+        public byte Bump { get; set; }
+
+        /// <summary>
+        /// Authority at creation time, the collection PDA is derived from it and it stays unchanged after authority
+        /// </summary>
+        public PublicKey OriginalAuthority { get; set; } = new PublicKey(new byte[32]);
+
+        public static readonly byte[] Discriminator = { 1, 3 };
+
+        public byte[] ToAccountData()
+        {
+            var w = new BorshWriter();
+            w.WriteBytes(Discriminator);
+            Serialize(w);
+            return w.ToArray();
+        }
+
+        public static CollectionAccount FromAccountData(byte[] data)
+        {
+            if (data.Length < Discriminator.Length || !data.AsSpan(0, Discriminator.Length).SequenceEqual(Discriminator))
+            {
+                throw new InvalidDataException("CollectionAccount discriminator mismatch");
+            }
+            return Deserialize(new BorshReader(data, Discriminator.Length));
+        }
+
+        public void Serialize(BorshWriter w)
+        {
+            w.WritePubkey(Authority);
+            w.WriteString(Name);
+            w.WriteString(Symbol);
+            w.WriteString(Uri);
+            w.WriteU8(RoyaltyPercentage);
+            w.WriteBool(IsMutable);
+            w.WriteU64(TotalMinted);
+            w.WriteOptionTag(PendingAuthority is not null);
+            if (PendingAuthority is not null)
+            {
+                w.WritePubkey(PendingAuthority);
+            }
+            w.WriteOptionTag(FreezeAuthority is not null);
+            if (FreezeAuthority is not null)
+            {
+                w.WritePubkey(FreezeAuthority);
+            }
+            w.WriteU8((byte)TransferMode);
+            w.WriteU32((uint)Creators.Count);
+            foreach (var item0 in Creators)
+            {
+                item0.Serialize(w);
+            }
+            w.WriteOptionTag(MaxSupply is not null);
+            if (MaxSupply is not null)
+            {
+                w.WriteU64(MaxSupply.Value);
+            }
+            w.WriteU8((byte)SerialMode);
+            w.WriteU64(TotalBurned);
+            w.WriteU8(Bump);
+            w.WritePubkey(OriginalAuthority);
+        }
+
+        public static CollectionAccount Deserialize(BorshReader r)
+        {
+            return new CollectionAccount
+            {
+                Authority = r.ReadPubkey(),
+                Name = r.ReadString(),
+                Symbol = r.ReadString(),
+                Uri = r.ReadString(),
+                RoyaltyPercentage = r.ReadU8(),
+                IsMutable = r.ReadBool(),
+                TotalMinted = r.ReadU64(),
+                PendingAuthority = r.ReadOptionTag() ? (PublicKey?)r.ReadPubkey() : null,
+                FreezeAuthority = r.ReadOptionTag() ? (PublicKey?)r.ReadPubkey() : null,
+                TransferMode = (TransferMode)r.ReadU8(),
+                Creators = r.ReadVec(() => Creator.Deserialize(r)),
+                MaxSupply = r.ReadOptionTag() ? (ulong?)r.ReadU64() : null,
+                SerialMode = (SerialMode)r.ReadU8(),
+                TotalBurned = r.ReadU64(),
+                Bump = r.ReadU8(),
+                OriginalAuthority = r.ReadPubkey(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// NFT account
+    /// </summary>
+    public class NFTAccount
+    {
+        /// <summary>
+        /// NFT owner
+        /// </summary>
+        public PublicKey Owner { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Collection this NFT belongs to
+        /// </summary>
+        public PublicKey Collection { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Metadata URI
+        /// </summary>
+        public string MetadataUri { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Whether the NFT is minted
+        /// </summary>
+        public bool IsMinted { get; set; }
+
+        /// <summary>
+        /// Serial number within collection
+        /// </summary>
+        public ulong SerialNumber { get; set; }
+
+        /// <summary>
+        /// Creation timestamp
+        /// </summary>
+        public long CreatedAt { get; set; }
+
+        /// <summary>
+        /// Last update timestamp
+        /// </summary>
+        public long UpdatedAt { get; set; }
+
+        /// <summary>
+        /// Delegate approved to transfer this NFT
+        /// </summary>
+        public PublicKey? Delegate { get; set; }
+
+        /// <summary>
+        /// Whether the NFT is frozen
+        /// </summary>
+        public bool IsFrozen { get; set; }
+
+        /// <summary>
+        /// PDA bump seed
+        /// </summary>
+        public byte Bump { get; set; }
+
+        /// <summary>
+        /// Original minter (empty for NFTs minted before minters were recorded)
+        /// </summary>
+        public PublicKey? Minter { get; set; }
+
+        public static readonly byte[] Discriminator = { 2, 3 };
+
+        public byte[] ToAccountData()
+        {
+            var w = new BorshWriter();
+            w.WriteBytes(Discriminator);
+            Serialize(w);
+            return w.ToArray();
+        }
+
+        public static NFTAccount FromAccountData(byte[] data)
+        {
+            if (data.Length < Discriminator.Length || !data.AsSpan(0, Discriminator.Length).SequenceEqual(Discriminator))
+            {
+                throw new InvalidDataException("NFTAccount discriminator mismatch");
+            }
+            return Deserialize(new BorshReader(data, Discriminator.Length));
+        }
+
+        public void Serialize(BorshWriter w)
+        {
+            w.WritePubkey(Owner);
+            w.WritePubkey(Collection);
+            w.WriteString(MetadataUri);
+            w.WriteBool(IsMinted);
+            w.WriteU64(SerialNumber);
+            w.WriteI64(CreatedAt);
+            w.WriteI64(UpdatedAt);
+            w.WriteOptionTag(Delegate is not null);
+            if (Delegate is not null)
+            {
+                w.WritePubkey(Delegate);
+            }
+            w.WriteBool(IsFrozen);
+            w.WriteU8(Bump);
+            w.WriteOptionTag(Minter is not null);
+            if (Minter is not null)
+            {
+                w.WritePubkey(Minter);
+            }
+        }
+
+        public static NFTAccount Deserialize(BorshReader r)
+        {
+            return new NFTAccount
+            {
+                Owner = r.ReadPubkey(),
+                Collection = r.ReadPubkey(),
+                MetadataUri = r.ReadString(),
+                IsMinted = r.ReadBool(),
+                SerialNumber = r.ReadU64(),
+                CreatedAt = r.ReadI64(),
+                UpdatedAt = r.ReadI64(),
+                Delegate = r.ReadOptionTag() ? (PublicKey?)r.ReadPubkey() : null,
+                IsFrozen = r.ReadBool(),
+                Bump = r.ReadU8(),
+                Minter = r.ReadOptionTag() ? (PublicKey?)r.ReadPubkey() : null,
+            };
+        }
+    }
+
+    /// <summary>
+    /// Operator approval account (PDA: owner, collection, operator)
+    /// </summary>
+    public class OperatorApproval
+    {
+        /// <summary>
+        /// NFT owner
+        /// </summary>
+        public PublicKey Owner { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Collection covered by the approval
+        /// </summary>
+        public PublicKey Collection { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Approved operator
+        /// </summary>
+        public PublicKey Operator { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Expiry timestamp (None means no expiry)
+        /// </summary>
+        public long? ExpiresAt { get; set; }
+
+        /// <summary>
+        /// PDA bump seed
+        /// </summary>
+        public byte Bump { get; set; }
+
+        public static readonly byte[] Discriminator = { 3, 1 };
+
+        public byte[] ToAccountData()
+        {
+            var w = new BorshWriter();
+            w.WriteBytes(Discriminator);
+            Serialize(w);
+            return w.ToArray();
+        }
+
+        public static OperatorApproval FromAccountData(byte[] data)
+        {
+            if (data.Length < Discriminator.Length || !data.AsSpan(0, Discriminator.Length).SequenceEqual(Discriminator))
+            {
+                throw new InvalidDataException("OperatorApproval discriminator mismatch");
+            }
+            return Deserialize(new BorshReader(data, Discriminator.Length));
+        }
+
+        public void Serialize(BorshWriter w)
+        {
+            w.WritePubkey(Owner);
+            w.WritePubkey(Collection);
+            w.WritePubkey(Operator);
+            w.WriteOptionTag(ExpiresAt is not null);
+            if (ExpiresAt is not null)
+            {
+                w.WriteI64(ExpiresAt.Value);
+            }
+            w.WriteU8(Bump);
+        }
+
+        public static OperatorApproval Deserialize(BorshReader r)
+        {
+            return new OperatorApproval
+            {
+                Owner = r.ReadPubkey(),
+                Collection = r.ReadPubkey(),
+                Operator = r.ReadPubkey(),
+                ExpiresAt = r.ReadOptionTag() ? (long?)r.ReadI64() : null,
+                Bump = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Fixed-price listing account (PDA: NFT)
+    /// </summary>
+    public class Listing
+    {
+        /// <summary>
+        /// Listed NFT
+        /// </summary>
+        public PublicKey Nft { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Seller (NFT owner at listing time)
+        /// </summary>
+        public PublicKey Seller { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Price (lamports)
+        /// </summary>
+        public ulong Price { get; set; }
+
+        /// <summary>
+        /// PDA bump seed
+        /// </summary>
+        public byte Bump { get; set; }
+
+        public static readonly byte[] Discriminator = { 4, 1 };
+
+        public byte[] ToAccountData()
+        {
+            var w = new BorshWriter();
+            w.WriteBytes(Discriminator);
+            Serialize(w);
+            return w.ToArray();
+        }
+
+        public static Listing FromAccountData(byte[] data)
+        {
+            if (data.Length < Discriminator.Length || !data.AsSpan(0, Discriminator.Length).SequenceEqual(Discriminator))
+            {
+                throw new InvalidDataException("Listing discriminator mismatch");
+            }
+            return Deserialize(new BorshReader(data, Discriminator.Length));
+        }
+
+        public void Serialize(BorshWriter w)
+        {
+            w.WritePubkey(Nft);
+            w.WritePubkey(Seller);
+            w.WriteU64(Price);
+            w.WriteU8(Bump);
+        }
+
+        public static Listing Deserialize(BorshReader r)
+        {
+            return new Listing
+            {
+                Nft = r.ReadPubkey(),
+                Seller = r.ReadPubkey(),
+                Price = r.ReadU64(),
+                Bump = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Collection transfer mode
+    /// </summary>
+    public enum TransferMode : byte
+    {
+        Transferable = 0,
+        Soulbound = 1,
+        AuthorityRevocable = 2,
+    }
+
+    /// <summary>
+    /// Royalty creator
+    /// </summary>
+    public class Creator
+    {
+        /// <summary>
+        /// Creator address
+        /// </summary>
+        public PublicKey Address { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Whether the creator has signed to confirm
+        /// </summary>
+        public bool Verified { get; set; }
+
+        /// <summary>
+        /// Royalty share (all creators sum to 100)
+        /// </summary>
+        public byte Share { get; set; }
+
+        public void Serialize(BorshWriter w)
+        {
+            w.WritePubkey(Address);
+            w.WriteBool(Verified);
+            w.WriteU8(Share);
+        }
+
+        public static Creator Deserialize(BorshReader r)
+        {
+            return new Creator
+            {
+                Address = r.ReadPubkey(),
+                Verified = r.ReadBool(),
+                Share = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Serial number assignment mode
+    /// </summary>
+    public enum SerialMode : byte
+    {
+        CallerAssigned = 0,
+        Sequential = 1,
+    }
+
+    /// <summary>
+    /// Program instruction, Borsh-encoded with a one-byte variant tag
+    /// </summary>
+    public abstract class NFTInstruction
+    {
+        public abstract byte Discriminator { get; }
+
+        protected abstract void SerializeArgs(BorshWriter w);
+
+        public byte[] Serialize()
+        {
+            var w = new BorshWriter();
+            w.WriteU8(Discriminator);
+            SerializeArgs(w);
+            return w.ToArray();
+        }
+
+        public static NFTInstruction Deserialize(byte[] data)
+        {
+            var r = new BorshReader(data);
+            var discriminator = r.ReadU8();
+            NFTInstruction instruction = discriminator switch
+            {
+                0 => InitializeCollectionInstruction.DeserializeArgs(r),
+                1 => MintNftInstruction.DeserializeArgs(r),
+                2 => TransferNftInstruction.DeserializeArgs(r),
+                3 => BurnNftInstruction.DeserializeArgs(r),
+                4 => UpdateNftMetadataInstruction.DeserializeArgs(r),
+                5 => UpdateCollectionMetadataInstruction.DeserializeArgs(r),
+                6 => ProposeCollectionAuthorityInstruction.DeserializeArgs(r),
+                7 => AcceptCollectionAuthorityInstruction.DeserializeArgs(r),
+                8 => ApproveDelegateInstruction.DeserializeArgs(r),
+                9 => RevokeDelegateInstruction.DeserializeArgs(r),
+                10 => SetOperatorApprovalInstruction.DeserializeArgs(r),
+                11 => SetFreezeAuthorityInstruction.DeserializeArgs(r),
+                12 => FreezeNftInstruction.DeserializeArgs(r),
+                13 => ThawNftInstruction.DeserializeArgs(r),
+                14 => ListNftInstruction.DeserializeArgs(r),
+                15 => DelistNftInstruction.DeserializeArgs(r),
+                16 => BuyNftInstruction.DeserializeArgs(r),
+                17 => SignCreatorInstruction.DeserializeArgs(r),
+                18 => MigrateAccountInstruction.DeserializeArgs(r),
+                _ => throw new InvalidDataException($"Unknown NFTInstruction discriminator {discriminator}"),
+            };
+            r.ExpectEnd();
+            return instruction;
+        }
+    }
+
+    /// <summary>
+    /// Initialize NFT collection
+    /// </summary>
+    public sealed class InitializeCollectionInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// Collection name
+        /// </summary>
+        public string Name { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Collection symbol
+        /// </summary>
+        public string Symbol { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Collection metadata URI
+        /// </summary>
+        public string Uri { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Royalty percentage (0-100)
+        /// </summary>
+        public byte RoyaltyPercentage { get; set; }
+
+        /// <summary>
+        /// Whether metadata can be updated
+        /// </summary>
+        public bool IsMutable { get; set; }
+
+        /// <summary>
+        /// Transfer mode
+        /// </summary>
+        public TransferMode TransferMode { get; set; }
+
+        /// <summary>
+        /// Royalty creators (shares sum to 100, the authority is verified automatically)
+        /// </summary>
+        public List<Creator> Creators { get; set; } = new();
+
+        /// <summary>
+        /// Maximum supply (unlimited when empty)
+        /// </summary>
+        public ulong? MaxSupply { get; set; }
+
+        /// <summary>
+        /// Serial number assignment mode
+        /// </summary>
+        public SerialMode SerialMode { get; set; }
+
+        /// <summary>
+        /// Canonical bump of the collection PDA (from find_collection_address)
+        /// </summary>
+        public byte Bump { get; set; }
+
+        public override byte Discriminator => 0;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteString(Name);
+            w.WriteString(Symbol);
+            w.WriteString(Uri);
+            w.WriteU8(RoyaltyPercentage);
+            w.WriteBool(IsMutable);
+            w.WriteU8((byte)TransferMode);
+            w.WriteU32((uint)Creators.Count);
+            foreach (var item0 in Creators)
+            {
+                item0.Serialize(w);
+            }
+            w.WriteOptionTag(MaxSupply is not null);
+            if (MaxSupply is not null)
+            {
+                w.WriteU64(MaxSupply.Value);
+            }
+            w.WriteU8((byte)SerialMode);
+            w.WriteU8(Bump);
+        }
+
+        internal static InitializeCollectionInstruction DeserializeArgs(BorshReader r)
+        {
+            return new InitializeCollectionInstruction
+            {
+                Name = r.ReadString(),
+                Symbol = r.ReadString(),
+                Uri = r.ReadString(),
+                RoyaltyPercentage = r.ReadU8(),
+                IsMutable = r.ReadBool(),
+                TransferMode = (TransferMode)r.ReadU8(),
+                Creators = r.ReadVec(() => Creator.Deserialize(r)),
+                MaxSupply = r.ReadOptionTag() ? (ulong?)r.ReadU64() : null,
+                SerialMode = (SerialMode)r.ReadU8(),
+                Bump = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Mint NFT
+    /// </summary>
+    public sealed class MintNftInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// Metadata URI
+        /// </summary>
+        public string MetadataUri { get; set; } = string.Empty;
+
+        /// <summary>
+        /// Serial number (ignored by sequential collections, which use total_minted + 1)
+        /// </summary>
+        public ulong SerialNumber { get; set; }
+
+        /// <summary>
+        /// Canonical bump of the NFT PDA
+        /// </summary>
+        public byte Bump { get; set; }
+
+        /// <summary>
+        /// Recipient (mints to the collection authority when empty)
+        /// </summary>
+        public PublicKey? Recipient { get; set; }
+
+        public override byte Discriminator => 1;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteString(MetadataUri);
+            w.WriteU64(SerialNumber);
+            w.WriteU8(Bump);
+            w.WriteOptionTag(Recipient is not null);
+            if (Recipient is not null)
+            {
+                w.WritePubkey(Recipient);
+            }
+        }
+
+        internal static MintNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new MintNftInstruction
+            {
+                MetadataUri = r.ReadString(),
+                SerialNumber = r.ReadU64(),
+                Bump = r.ReadU8(),
+                Recipient = r.ReadOptionTag() ? (PublicKey?)r.ReadPubkey() : null,
+            };
+        }
+    }
+
+    /// <summary>
+    /// Transfer NFT
+    /// </summary>
+    public sealed class TransferNftInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// New owner
+        /// </summary>
+        public PublicKey NewOwner { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Canonical bump of the listing PDA (from find_listing_address)
+        /// </summary>
+        public byte ListingBump { get; set; }
+
+        public override byte Discriminator => 2;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WritePubkey(NewOwner);
+            w.WriteU8(ListingBump);
+        }
+
+        internal static TransferNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new TransferNftInstruction
+            {
+                NewOwner = r.ReadPubkey(),
+                ListingBump = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Burn NFT
+    /// </summary>
+    public sealed class BurnNftInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// Canonical bump of the listing PDA (from find_listing_address)
+        /// </summary>
+        public byte ListingBump { get; set; }
+
+        public override byte Discriminator => 3;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteU8(ListingBump);
+        }
+
+        internal static BurnNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new BurnNftInstruction
+            {
+                ListingBump = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Update NFT metadata
+    /// </summary>
+    public sealed class UpdateNftMetadataInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// New metadata URI
+        /// </summary>
+        public string NewMetadataUri { get; set; } = string.Empty;
+
+        public override byte Discriminator => 4;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteString(NewMetadataUri);
+        }
+
+        internal static UpdateNftMetadataInstruction DeserializeArgs(BorshReader r)
+        {
+            return new UpdateNftMetadataInstruction
+            {
+                NewMetadataUri = r.ReadString(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Update collection metadata
+    /// </summary>
+    public sealed class UpdateCollectionMetadataInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// New collection metadata URI
+        /// </summary>
+        public string NewUri { get; set; } = string.Empty;
+
+        public override byte Discriminator => 5;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteString(NewUri);
+        }
+
+        internal static UpdateCollectionMetadataInstruction DeserializeArgs(BorshReader r)
+        {
+            return new UpdateCollectionMetadataInstruction
+            {
+                NewUri = r.ReadString(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Propose a new collection authority
+    /// </summary>
+    public sealed class ProposeCollectionAuthorityInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// New authority (None cancels the pending proposal)
+        /// </summary>
+        public PublicKey? NewAuthority { get; set; }
+
+        public override byte Discriminator => 6;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteOptionTag(NewAuthority is not null);
+            if (NewAuthority is not null)
+            {
+                w.WritePubkey(NewAuthority);
+            }
+        }
+
+        internal static ProposeCollectionAuthorityInstruction DeserializeArgs(BorshReader r)
+        {
+            return new ProposeCollectionAuthorityInstruction
+            {
+                NewAuthority = r.ReadOptionTag() ? (PublicKey?)r.ReadPubkey() : null,
+            };
+        }
+    }
+
+    /// <summary>
+    /// Accept collection authority
+    /// </summary>
+    public sealed class AcceptCollectionAuthorityInstruction : NFTInstruction
+    {
+        public override byte Discriminator => 7;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+        }
+
+        internal static AcceptCollectionAuthorityInstruction DeserializeArgs(BorshReader r)
+        {
+            return new AcceptCollectionAuthorityInstruction();
+        }
+    }
+
+    /// <summary>
+    /// Approve a delegate to transfer the NFT
+    /// </summary>
+    public sealed class ApproveDelegateInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// Delegate
+        /// </summary>
+        public PublicKey Delegate { get; set; } = new PublicKey(new byte[32]);
+
+        public override byte Discriminator => 8;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WritePubkey(Delegate);
+        }
+
+        internal static ApproveDelegateInstruction DeserializeArgs(BorshReader r)
+        {
+            return new ApproveDelegateInstruction
+            {
+                Delegate = r.ReadPubkey(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Revoke the delegate
+    /// </summary>
+    public sealed class RevokeDelegateInstruction : NFTInstruction
+    {
+        public override byte Discriminator => 9;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+        }
+
+        internal static RevokeDelegateInstruction DeserializeArgs(BorshReader r)
+        {
+            return new RevokeDelegateInstruction();
+        }
+    }
+
+    /// <summary>
+    /// Set a collection-wide operator approval
+    /// </summary>
+    public sealed class SetOperatorApprovalInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// Operator
+        /// </summary>
+        public PublicKey Operator { get; set; } = new PublicKey(new byte[32]);
+
+        /// <summary>
+        /// Approve or revoke
+        /// </summary>
+        public bool Approved { get; set; }
+
+        /// <summary>
+        /// Expiry timestamp (None means no expiry)
+        /// </summary>
+        public long? ExpiresAt { get; set; }
+
+        /// <summary>
+        /// Canonical bump of the operator approval PDA (from find_operator_approval_address)
+        /// </summary>
+        public byte Bump { get; set; }
+
+        public override byte Discriminator => 10;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WritePubkey(Operator);
+            w.WriteBool(Approved);
+            w.WriteOptionTag(ExpiresAt is not null);
+            if (ExpiresAt is not null)
+            {
+                w.WriteI64(ExpiresAt.Value);
+            }
+            w.WriteU8(Bump);
+        }
+
+        internal static SetOperatorApprovalInstruction DeserializeArgs(BorshReader r)
+        {
+            return new SetOperatorApprovalInstruction
+            {
+                Operator = r.ReadPubkey(),
+                Approved = r.ReadBool(),
+                ExpiresAt = r.ReadOptionTag() ? (long?)r.ReadI64() : null,
+                Bump = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Set collection freeze authority
+    /// </summary>
+    public sealed class SetFreezeAuthorityInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// New freeze authority (None removes it)
+        /// </summary>
+        public PublicKey? FreezeAuthority { get; set; }
+
+        public override byte Discriminator => 11;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteOptionTag(FreezeAuthority is not null);
+            if (FreezeAuthority is not null)
+            {
+                w.WritePubkey(FreezeAuthority);
+            }
+        }
+
+        internal static SetFreezeAuthorityInstruction DeserializeArgs(BorshReader r)
+        {
+            return new SetFreezeAuthorityInstruction
+            {
+                FreezeAuthority = r.ReadOptionTag() ? (PublicKey?)r.ReadPubkey() : null,
+            };
+        }
+    }
+
+    /// <summary>
+    /// Freeze NFT
+    /// </summary>
+    public sealed class FreezeNftInstruction : NFTInstruction
+    {
+        public override byte Discriminator => 12;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+        }
+
+        internal static FreezeNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new FreezeNftInstruction();
+        }
+    }
+
+    /// <summary>
+    /// Thaw NFT
+    /// </summary>
+    public sealed class ThawNftInstruction : NFTInstruction
+    {
+        public override byte Discriminator => 13;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+        }
+
+        internal static ThawNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new ThawNftInstruction();
+        }
+    }
+
+    /// <summary>
+    /// List an NFT at a fixed price (the NFT stays with the seller)
+    /// </summary>
+    public sealed class ListNftInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// Price (lamports)
+        /// </summary>
+        public ulong Price { get; set; }
+
+        /// <summary>
+        /// Canonical bump of the listing PDA (from find_listing_address)
+        /// </summary>
+        public byte Bump { get; set; }
+
+        public override byte Discriminator => 14;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteU64(Price);
+            w.WriteU8(Bump);
+        }
+
+        internal static ListNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new ListNftInstruction
+            {
+                Price = r.ReadU64(),
+                Bump = r.ReadU8(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Delist an NFT
+    /// </summary>
+    public sealed class DelistNftInstruction : NFTInstruction
+    {
+        public override byte Discriminator => 15;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+        }
+
+        internal static DelistNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new DelistNftInstruction();
+        }
+    }
+
+    /// <summary>
+    /// Buy a listed NFT and pay royalties
+    /// Royalty recipient accounts: verified creators in order, or the collection authority if none
+    /// </summary>
+    public sealed class BuyNftInstruction : NFTInstruction
+    {
+        /// <summary>
+        /// Price the buyer agrees to pay, must match the listing price
+        /// </summary>
+        public ulong Price { get; set; }
+
+        public override byte Discriminator => 16;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+            w.WriteU64(Price);
+        }
+
+        internal static BuyNftInstruction DeserializeArgs(BorshReader r)
+        {
+            return new BuyNftInstruction
+            {
+                Price = r.ReadU64(),
+            };
+        }
+    }
+
+    /// <summary>
+    /// Creator signs to verify themselves
+    /// </summary>
+    public sealed class SignCreatorInstruction : NFTInstruction
+    {
+        public override byte Discriminator => 17;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+        }
+
+        internal static SignCreatorInstruction DeserializeArgs(BorshReader r)
+        {
+            return new SignCreatorInstruction();
+        }
+    }
+
+    /// <summary>
+    /// Migrate a collection or NFT account from an old layout to the current version (anyone may call, the payer covers extra rent)
+    /// </summary>
+    public sealed class MigrateAccountInstruction : NFTInstruction
+    {
+        public override byte Discriminator => 18;
+
+        protected override void SerializeArgs(BorshWriter w)
+        {
+        }
+
+        internal static MigrateAccountInstruction DeserializeArgs(BorshReader r)
+        {
+            return new MigrateAccountInstruction();
+        }
+    }
+
+    /// <summary>
+    /// Program error codes (ProgramError::Custom)
+    /// </summary>
+    public enum NFTError : uint
+    {
+        InvalidInstruction = 0,
+        InvalidOwner = 1,
+        NotInitialized = 2,
+        AlreadyInitialized = 3,
+        Overflow = 4,
+        InsufficientAuthority = 5,
+        AlreadyMinted = 6,
+        NotMinted = 7,
+        InvalidMetadataUri = 8,
+        InvalidRoyaltyPercentage = 9,
+        ImmutableMetadata = 10,
+        OperatorApprovalExpired = 11,
+        NFTFrozen = 12,
+        NonTransferable = 13,
+        InvalidListing = 14,
+        InvalidPrice = 15,
+        InvalidCreators = 16,
+        CreatorNotFound = 17,
+        MaxSupplyReached = 18,
+        SerialNumberBurned = 19,
+        AccountTypeMismatch = 20,
+        UnsupportedAccountVersion = 21,
+        NameTooLong = 22,
+        SymbolTooLong = 23,
+        UriTooLong = 24,
+        InvalidRoyaltyRecipient = 25,
+    }
+
+    public static class NFTErrors
+    {
+        public static string Message(NFTError error) => error switch
+        {
+            NFTError.InvalidInstruction => "无效的指令 | Invalid instruction",
+            NFTError.InvalidOwner => "无效的所有者 | Invalid owner",
+            NFTError.NotInitialized => "账户未初始化 | Account not initialized",
+            NFTError.AlreadyInitialized => "账户已初始化 | Account already initialized",
+            NFTError.Overflow => "数据溢出 | Overflow",
+            NFTError.InsufficientAuthority => "权限不足 | Insufficient authority",
+            NFTError.AlreadyMinted => "NFT已铸造 | NFT already minted",
+            NFTError.NotMinted => "NFT未铸造 | NFT not minted",
+            NFTError.InvalidMetadataUri => "无效的元数据URI | Invalid metadata URI",
+            NFTError.InvalidRoyaltyPercentage => "无效的版税百分比 | Invalid royalty percentage",
+            NFTError.ImmutableMetadata => "不可变的元数据 | Immutable metadata",
+            NFTError.OperatorApprovalExpired => "操作员授权已过期 | Operator approval expired",
+            NFTError.NFTFrozen => "NFT已冻结 | NFT is frozen",
+            NFTError.NonTransferable => "NFT不可转移 | NFT is non-transferable",
+            NFTError.InvalidListing => "无效的挂单 | Invalid listing",
+            NFTError.InvalidPrice => "无效的价格 | Invalid price",
+            NFTError.InvalidCreators => "无效的创作者列表 | Invalid creators",
+            NFTError.CreatorNotFound => "找不到创作者 | Creator not found",
+            NFTError.MaxSupplyReached => "已达到最大供应量 | Max supply reached",
+            NFTError.SerialNumberBurned => "序列号已被销毁 | Serial number already burned",
+            NFTError.AccountTypeMismatch => "账户类型不匹配 | Account type mismatch",
+            NFTError.UnsupportedAccountVersion => "不支持的账户版本 | Unsupported account version",
+            NFTError.NameTooLong => "名称过长 | Name too long",
+            NFTError.SymbolTooLong => "符号过长 | Symbol too long",
+            NFTError.UriTooLong => "URI过长 | URI too long",
+            NFTError.InvalidRoyaltyRecipient => "版税接收者与集合的创作者不符 | Royalty recipient does not match the collection's creators",
+            _ => throw new ArgumentOutOfRangeException(nameof(error)),
+        };
+
+        public static NFTError? FromCode(uint code) => Enum.IsDefined(typeof(NFTError), code) ? (NFTError)code : null;
+    }
+}
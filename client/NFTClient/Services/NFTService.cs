@@ -6,6 +6,7 @@ using System;
 using System.Collections.Generic;
 using System.Linq;
 using System.Numerics;
+using System.Security.Cryptography;
 using System.Text;
 using System.Text.Json;
 using System.Threading.Tasks;
@@ -66,8 +67,8 @@ namespace NFTClient.Services
             var collectionSeeds = new List<byte[]>
             {
                 COLLECTION_SEED_PREFIX,
-                Encoding.UTF8.GetBytes(_wallet.Account.PublicKey),
-                Encoding.UTF8.GetBytes(metadata.Name)
+                _wallet.Account.PublicKey.KeyBytes,
+                SHA256.HashData(Encoding.UTF8.GetBytes(metadata.Name))
             };
             
             var collectionAddress = FindProgramAddress(collectionSeeds.ToArray(), _programId);
@@ -148,7 +149,7 @@ namespace NFTClient.Services
             var nftSeeds = new List<byte[]>
             {
                 NFT_SEED_PREFIX,
-                Encoding.UTF8.GetBytes(collectionAddress),
+                new PublicKey(collectionAddress).KeyBytes,
                 serialNumberBytes
             };
             
@@ -0,0 +1,1141 @@
+// 此文件由 nft-bindgen 根据程序IDL生成, 请勿手动修改 (运行 `cargo run -p nft-bindgen` 重新生成)
+// Generated by nft-bindgen from the program IDL, do not edit by hand (run `cargo run -p nft-bindgen` to regenerate)
+import { PublicKey } from '@solana/web3.js';
+
+function utf8Encode(value: string): Uint8Array {
+  const bytes: number[] = [];
+  for (let i = 0; i < value.length; i++) {
+    let code = value.charCodeAt(i);
+    if (code >= 0xd800 && code < 0xdc00 && i + 1 < value.length) {
+      const next = value.charCodeAt(i + 1);
+      if (next >= 0xdc00 && next < 0xe000) {
+        code = 0x10000 + ((code - 0xd800) << 10) + (next - 0xdc00);
+        i++;
+      }
+    }
+    if (code < 0x80) {
+      bytes.push(code);
+    } else if (code < 0x800) {
+      bytes.push(0xc0 | (code >> 6), 0x80 | (code & 0x3f));
+    } else if (code < 0x10000) {
+      bytes.push(0xe0 | (code >> 12), 0x80 | ((code >> 6) & 0x3f), 0x80 | (code & 0x3f));
+    } else {
+      bytes.push(0xf0 | (code >> 18), 0x80 | ((code >> 12) & 0x3f), 0x80 | ((code >> 6) & 0x3f), 0x80 | (code & 0x3f));
+    }
+  }
+  return Uint8Array.from(bytes);
+}
+
+function utf8Decode(bytes: Uint8Array): string {
+  let result = '';
+  let i = 0;
+  while (i < bytes.length) {
+    const byte = bytes[i++];
+    let code: number;
+    if (byte < 0x80) {
+      code = byte;
+    } else if (byte < 0xe0) {
+      code = ((byte & 0x1f) << 6) | (bytes[i++] & 0x3f);
+    } else if (byte < 0xf0) {
+      code = ((byte & 0x0f) << 12) | ((bytes[i++] & 0x3f) << 6) | (bytes[i++] & 0x3f);
+    } else {
+      code = ((byte & 0x07) << 18) | ((bytes[i++] & 0x3f) << 12) | ((bytes[i++] & 0x3f) << 6) | (bytes[i++] & 0x3f);
+    }
+    if (code >= 0x10000) {
+      code -= 0x10000;
+      result += String.fromCharCode(0xd800 + (code >> 10), 0xdc00 + (code & 0x3ff));
+    } else {
+      result += String.fromCharCode(code);
+    }
+  }
+  return result;
+}
+
+/**
+ * Borsh encoder
+ */
+export class BorshWriter {
+  private bytes: number[] = [];
+
+  u8(value: number): void {
+    this.bytes.push(value & 0xff);
+  }
+
+  u16(value: number): void {
+    this.u8(value);
+    this.u8(value >> 8);
+  }
+
+  u32(value: number): void {
+    this.u16(value & 0xffff);
+    this.u16(value >>> 16);
+  }
+
+  u64(value: bigint): void {
+    const view = new DataView(new ArrayBuffer(8));
+    view.setBigUint64(0, value, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  i64(value: bigint): void {
+    const view = new DataView(new ArrayBuffer(8));
+    view.setBigInt64(0, value, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  bool(value: boolean): void {
+    this.u8(value ? 1 : 0);
+  }
+
+  string(value: string): void {
+    const encoded = utf8Encode(value);
+    this.u32(encoded.length);
+    this.raw(encoded);
+  }
+
+  pubkey(value: PublicKey): void {
+    this.raw(value.toBytes());
+  }
+
+  option<T>(value: T | null, write: (value: T) => void): void {
+    if (value === null) {
+      this.u8(0);
+    } else {
+      this.u8(1);
+      write(value);
+    }
+  }
+
+  vec<T>(items: T[], write: (item: T) => void): void {
+    this.u32(items.length);
+    items.forEach((item) => write(item));
+  }
+
+  raw(data: Uint8Array): void {
+    for (let i = 0; i < data.length; i++) {
+      this.bytes.push(data[i]);
+    }
+  }
+
+  toBytes(): Uint8Array {
+    return Uint8Array.from(this.bytes);
+  }
+}
+
+/**
+ * Borsh decoder
+ */
+export class BorshReader {
+  private offset = 0;
+
+  constructor(private readonly data: Uint8Array) {}
+
+  u8(): number {
+    return this.take(1)[0];
+  }
+
+  u16(): number {
+    const bytes = this.take(2);
+    return bytes[0] | (bytes[1] << 8);
+  }
+
+  u32(): number {
+    const bytes = this.take(4);
+    return (bytes[0] | (bytes[1] << 8) | (bytes[2] << 16) | (bytes[3] << 24)) >>> 0;
+  }
+
+  u64(): bigint {
+    return this.view(8).getBigUint64(0, true);
+  }
+
+  i64(): bigint {
+    return this.view(8).getBigInt64(0, true);
+  }
+
+  bool(): boolean {
+    const value = this.u8();
+    if (value > 1) {
+      throw new Error(`Invalid bool ${value}`);
+    }
+    return value === 1;
+  }
+
+  string(): string {
+    return utf8Decode(this.take(this.u32()));
+  }
+
+  pubkey(): PublicKey {
+    return new PublicKey(this.take(32));
+  }
+
+  option<T>(read: () => T): T | null {
+    const tag = this.u8();
+    if (tag > 1) {
+      throw new Error(`Invalid option tag ${tag}`);
+    }
+    return tag === 0 ? null : read();
+  }
+
+  vec<T>(read: () => T): T[] {
+    const length = this.u32();
+    const items: T[] = [];
+    for (let i = 0; i < length; i++) {
+      items.push(read());
+    }
+    return items;
+  }
+
+  take(length: number): Uint8Array {
+    if (this.offset + length > this.data.length) {
+      throw new Error('Unexpected end of data');
+    }
+    const bytes = this.data.slice(this.offset, this.offset + length);
+    this.offset += length;
+    return bytes;
+  }
+
+  expectEnd(): void {
+    if (this.offset !== this.data.length) {
+      throw new Error(`${this.data.length - this.offset} trailing bytes`);
+    }
+  }
+
+  private view(length: number): DataView {
+    const bytes = this.take(length);
+    return new DataView(bytes.buffer, bytes.byteOffset, length);
+  }
+}
+
+/**
+ * NFT collection account
+ */
+export interface CollectionAccount {
+  /**
+   * Collection authority
+   */
+  authority: PublicKey;
+  /**
+   * Collection name
+   */
+  name: string;
+  /**
+   * Collection symbol
+   */
+  symbol: string;
+  /**
+   * Collection metadata URI
+   */
+  uri: string;
+  /**
+   * Royalty percentage (0-100)
+   */
+  royaltyPercentage: number;
+  /**
+   * Whether metadata can be updated
+   */
+  isMutable: boolean;
+  /**
+   * Number of minted NFTs
+   */
+  totalMinted: bigint;
+  /**
+   * Pending authority awaiting acceptance
+   */
+  pendingAuthority: PublicKey | null;
+  /**
+   * Freeze authority
+   */
+  freezeAuthority: PublicKey | null;
+  /**
+   * Transfer mode
+   */
+  transferMode: TransferMode;
+  /**
+   * Royalty creators (royalties go to the collection authority when empty)
+   */
+  creators: Creator[];
+  /**
+   * Maximum supply (unlimited when empty)
+   */
+  maxSupply: bigint | null;
+  /**
+   * Serial number assignment mode
+   */
+  serialMode: SerialMode;
+  /**
+   * Number of burned NFTs
+   */
+  totalBurned: bigint;
+  /**
+   * PDA bump seed
+   */
+  bump: number;
+  /**
+   * Authority at creation time, the collection PDA is derived from it and it stays unchanged after authority
+   */
+  originalAuthority: PublicKey;
+}
+
+export function encodeCollectionAccount(w: BorshWriter, value: CollectionAccount): void {
+  w.pubkey(value.authority);
+  w.string(value.name);
+  w.string(value.symbol);
+  w.string(value.uri);
+  w.u8(value.royaltyPercentage);
+  w.bool(value.isMutable);
+  w.u64(value.totalMinted);
+  w.option(value.pendingAuthority, (value) => w.pubkey(value));
+  w.option(value.freezeAuthority, (value) => w.pubkey(value));
+  w.u8(value.transferMode);
+  w.vec(value.creators, (item) => encodeCreator(w, item));
+  w.option(value.maxSupply, (value) => w.u64(value));
+  w.u8(value.serialMode);
+  w.u64(value.totalBurned);
+  w.u8(value.bump);
+  w.pubkey(value.originalAuthority);
+}
+
+export function decodeCollectionAccount(r: BorshReader): CollectionAccount {
+  return {
+    authority: r.pubkey(),
+    name: r.string(),
+    symbol: r.string(),
+    uri: r.string(),
+    royaltyPercentage: r.u8(),
+    isMutable: r.bool(),
+    totalMinted: r.u64(),
+    pendingAuthority: r.option(() => r.pubkey()),
+    freezeAuthority: r.option(() => r.pubkey()),
+    transferMode: r.u8() as TransferMode,
+    creators: r.vec(() => decodeCreator(r)),
+    maxSupply: r.option(() => r.u64()),
+    serialMode: r.u8() as SerialMode,
+    totalBurned: r.u64(),
+    bump: r.u8(),
+    originalAuthority: r.pubkey(),
+  };
+}
+
+/**
+ * NFT account
+ */
+export interface NFTAccount {
+  /**
+   * NFT owner
+   */
+  owner: PublicKey;
+  /**
+   * Collection this NFT belongs to
+   */
+  collection: PublicKey;
+  /**
+   * Metadata URI
+   */
+  metadataUri: string;
+  /**
+   * Whether the NFT is minted
+   */
+  isMinted: boolean;
+  /**
+   * Serial number within collection
+   */
+  serialNumber: bigint;
+  /**
+   * Creation timestamp
+   */
+  createdAt: bigint;
+  /**
+   * Last update timestamp
+   */
+  updatedAt: bigint;
+  /**
+   * Delegate approved to transfer this NFT
+   */
+  delegate: PublicKey | null;
+  /**
+   * Whether the NFT is frozen
+   */
+  isFrozen: boolean;
+  /**
+   * PDA bump seed
+   */
+  bump: number;
+  /**
+   * Original minter (empty for NFTs minted before minters were recorded)
+   */
+  minter: PublicKey | null;
+}
+
+export function encodeNFTAccount(w: BorshWriter, value: NFTAccount): void {
+  w.pubkey(value.owner);
+  w.pubkey(value.collection);
+  w.string(value.metadataUri);
+  w.bool(value.isMinted);
+  w.u64(value.serialNumber);
+  w.i64(value.createdAt);
+  w.i64(value.updatedAt);
+  w.option(value.delegate, (value) => w.pubkey(value));
+  w.bool(value.isFrozen);
+  w.u8(value.bump);
+  w.option(value.minter, (value) => w.pubkey(value));
+}
+
+export function decodeNFTAccount(r: BorshReader): NFTAccount {
+  return {
+    owner: r.pubkey(),
+    collection: r.pubkey(),
+    metadataUri: r.string(),
+    isMinted: r.bool(),
+    serialNumber: r.u64(),
+    createdAt: r.i64(),
+    updatedAt: r.i64(),
+    delegate: r.option(() => r.pubkey()),
+    isFrozen: r.bool(),
+    bump: r.u8(),
+    minter: r.option(() => r.pubkey()),
+  };
+}
+
+/**
+ * Operator approval account (PDA: owner, collection, operator)
+ */
+export interface OperatorApproval {
+  /**
+   * NFT owner
+   */
+  owner: PublicKey;
+  /**
+   * Collection covered by the approval
+   */
+  collection: PublicKey;
+  /**
+   * Approved operator
+   */
+  operator: PublicKey;
+  /**
+   * Expiry timestamp (None means no expiry)
+   */
+  expiresAt: bigint | null;
+  /**
+   * PDA bump seed
+   */
+  bump: number;
+}
+
+export function encodeOperatorApproval(w: BorshWriter, value: OperatorApproval): void {
+  w.pubkey(value.owner);
+  w.pubkey(value.collection);
+  w.pubkey(value.operator);
+  w.option(value.expiresAt, (value) => w.i64(value));
+  w.u8(value.bump);
+}
+
+export function decodeOperatorApproval(r: BorshReader): OperatorApproval {
+  return {
+    owner: r.pubkey(),
+    collection: r.pubkey(),
+    operator: r.pubkey(),
+    expiresAt: r.option(() => r.i64()),
+    bump: r.u8(),
+  };
+}
+
+/**
+ * Fixed-price listing account (PDA: NFT)
+ */
+export interface Listing {
+  /**
+   * Listed NFT
+   */
+  nft: PublicKey;
+  /**
+   * Seller (NFT owner at listing time)
+   */
+  seller: PublicKey;
+  /**
+   * Price (lamports)
+   */
+  price: bigint;
+  /**
+   * PDA bump seed
+   */
+  bump: number;
+}
+
+export function encodeListing(w: BorshWriter, value: Listing): void {
+  w.pubkey(value.nft);
+  w.pubkey(value.seller);
+  w.u64(value.price);
+  w.u8(value.bump);
+}
+
+export function decodeListing(r: BorshReader): Listing {
+  return {
+    nft: r.pubkey(),
+    seller: r.pubkey(),
+    price: r.u64(),
+    bump: r.u8(),
+  };
+}
+
+/**
+ * Collection transfer mode
+ */
+export enum TransferMode {
+  Transferable = 0,
+  Soulbound = 1,
+  AuthorityRevocable = 2,
+}
+
+/**
+ * Royalty creator
+ */
+export interface Creator {
+  /**
+   * Creator address
+   */
+  address: PublicKey;
+  /**
+   * Whether the creator has signed to confirm
+   */
+  verified: boolean;
+  /**
+   * Royalty share (all creators sum to 100)
+   */
+  share: number;
+}
+
+export function encodeCreator(w: BorshWriter, value: Creator): void {
+  w.pubkey(value.address);
+  w.bool(value.verified);
+  w.u8(value.share);
+}
+
+export function decodeCreator(r: BorshReader): Creator {
+  return {
+    address: r.pubkey(),
+    verified: r.bool(),
+    share: r.u8(),
+  };
+}
+
+/**
+ * Serial number assignment mode
+ */
+export enum SerialMode {
+  CallerAssigned = 0,
+  Sequential = 1,
+}
+
+export const COLLECTION_ACCOUNT_DISCRIMINATOR = [1, 3];
+
+export function encodeCollectionAccountData(value: CollectionAccount): Uint8Array {
+  const w = new BorshWriter();
+  COLLECTION_ACCOUNT_DISCRIMINATOR.forEach((byte) => w.u8(byte));
+  encodeCollectionAccount(w, value);
+  return w.toBytes();
+}
+
+export function decodeCollectionAccountData(data: Uint8Array): CollectionAccount {
+  if (COLLECTION_ACCOUNT_DISCRIMINATOR.some((byte, i) => data[i] !== byte)) {
+    throw new Error('CollectionAccount discriminator mismatch');
+  }
+  return decodeCollectionAccount(new BorshReader(data.subarray(2)));
+}
+
+export const NFT_ACCOUNT_DISCRIMINATOR = [2, 3];
+
+export function encodeNFTAccountData(value: NFTAccount): Uint8Array {
+  const w = new BorshWriter();
+  NFT_ACCOUNT_DISCRIMINATOR.forEach((byte) => w.u8(byte));
+  encodeNFTAccount(w, value);
+  return w.toBytes();
+}
+
+export function decodeNFTAccountData(data: Uint8Array): NFTAccount {
+  if (NFT_ACCOUNT_DISCRIMINATOR.some((byte, i) => data[i] !== byte)) {
+    throw new Error('NFTAccount discriminator mismatch');
+  }
+  return decodeNFTAccount(new BorshReader(data.subarray(2)));
+}
+
+export const OPERATOR_APPROVAL_DISCRIMINATOR = [3, 1];
+
+export function encodeOperatorApprovalData(value: OperatorApproval): Uint8Array {
+  const w = new BorshWriter();
+  OPERATOR_APPROVAL_DISCRIMINATOR.forEach((byte) => w.u8(byte));
+  encodeOperatorApproval(w, value);
+  return w.toBytes();
+}
+
+export function decodeOperatorApprovalData(data: Uint8Array): OperatorApproval {
+  if (OPERATOR_APPROVAL_DISCRIMINATOR.some((byte, i) => data[i] !== byte)) {
+    throw new Error('OperatorApproval discriminator mismatch');
+  }
+  return decodeOperatorApproval(new BorshReader(data.subarray(2)));
+}
+
+export const LISTING_DISCRIMINATOR = [4, 1];
+
+export function encodeListingData(value: Listing): Uint8Array {
+  const w = new BorshWriter();
+  LISTING_DISCRIMINATOR.forEach((byte) => w.u8(byte));
+  encodeListing(w, value);
+  return w.toBytes();
+}
+
+export function decodeListingData(data: Uint8Array): Listing {
+  if (LISTING_DISCRIMINATOR.some((byte, i) => data[i] !== byte)) {
+    throw new Error('Listing discriminator mismatch');
+  }
+  return decodeListing(new BorshReader(data.subarray(2)));
+}
+
+/**
+ * Initialize NFT collection
+ */
+export interface InitializeCollectionInstruction {
+  kind: 'initializeCollection';
+  /**
+   * Collection name
+   */
+  name: string;
+  /**
+   * Collection symbol
+   */
+  symbol: string;
+  /**
+   * Collection metadata URI
+   */
+  uri: string;
+  /**
+   * Royalty percentage (0-100)
+   */
+  royaltyPercentage: number;
+  /**
+   * Whether metadata can be updated
+   */
+  isMutable: boolean;
+  /**
+   * Transfer mode
+   */
+  transferMode: TransferMode;
+  /**
+   * Royalty creators (shares sum to 100, the authority is verified automatically)
+   */
+  creators: Creator[];
+  /**
+   * Maximum supply (unlimited when empty)
+   */
+  maxSupply: bigint | null;
+  /**
+   * Serial number assignment mode
+   */
+  serialMode: SerialMode;
+  /**
+   * Canonical bump of the collection PDA (from find_collection_address)
+   */
+  bump: number;
+}
+
+/**
+ * Mint NFT
+ */
+export interface MintNftInstruction {
+  kind: 'mintNft';
+  /**
+   * Metadata URI
+   */
+  metadataUri: string;
+  /**
+   * Serial number (ignored by sequential collections, which use total_minted + 1)
+   */
+  serialNumber: bigint;
+  /**
+   * Canonical bump of the NFT PDA
+   */
+  bump: number;
+  /**
+   * Recipient (mints to the collection authority when empty)
+   */
+  recipient: PublicKey | null;
+}
+
+/**
+ * Transfer NFT
+ */
+export interface TransferNftInstruction {
+  kind: 'transferNft';
+  /**
+   * New owner
+   */
+  newOwner: PublicKey;
+  /**
+   * Canonical bump of the listing PDA (from find_listing_address)
+   */
+  listingBump: number;
+}
+
+/**
+ * Burn NFT
+ */
+export interface BurnNftInstruction {
+  kind: 'burnNft';
+  /**
+   * Canonical bump of the listing PDA (from find_listing_address)
+   */
+  listingBump: number;
+}
+
+/**
+ * Update NFT metadata
+ */
+export interface UpdateNftMetadataInstruction {
+  kind: 'updateNftMetadata';
+  /**
+   * New metadata URI
+   */
+  newMetadataUri: string;
+}
+
+/**
+ * Update collection metadata
+ */
+export interface UpdateCollectionMetadataInstruction {
+  kind: 'updateCollectionMetadata';
+  /**
+   * New collection metadata URI
+   */
+  newUri: string;
+}
+
+/**
+ * Propose a new collection authority
+ */
+export interface ProposeCollectionAuthorityInstruction {
+  kind: 'proposeCollectionAuthority';
+  /**
+   * New authority (None cancels the pending proposal)
+   */
+  newAuthority: PublicKey | null;
+}
+
+/**
+ * Accept collection authority
+ */
+export interface AcceptCollectionAuthorityInstruction {
+  kind: 'acceptCollectionAuthority';
+}
+
+/**
+ * Approve a delegate to transfer the NFT
+ */
+export interface ApproveDelegateInstruction {
+  kind: 'approveDelegate';
+  /**
+   * Delegate
+   */
+  delegate: PublicKey;
+}
+
+/**
+ * Revoke the delegate
+ */
+export interface RevokeDelegateInstruction {
+  kind: 'revokeDelegate';
+}
+
+/**
+ * Set a collection-wide operator approval
+ */
+export interface SetOperatorApprovalInstruction {
+  kind: 'setOperatorApproval';
+  /**
+   * Operator
+   */
+  operator: PublicKey;
+  /**
+   * Approve or revoke
+   */
+  approved: boolean;
+  /**
+   * Expiry timestamp (None means no expiry)
+   */
+  expiresAt: bigint | null;
+  /**
+   * Canonical bump of the operator approval PDA (from find_operator_approval_address)
+   */
+  bump: number;
+}
+
+/**
+ * Set collection freeze authority
+ */
+export interface SetFreezeAuthorityInstruction {
+  kind: 'setFreezeAuthority';
+  /**
+   * New freeze authority (None removes it)
+   */
+  freezeAuthority: PublicKey | null;
+}
+
+/**
+ * Freeze NFT
+ */
+export interface FreezeNftInstruction {
+  kind: 'freezeNft';
+}
+
+/**
+ * Thaw NFT
+ */
+export interface ThawNftInstruction {
+  kind: 'thawNft';
+}
+
+/**
+ * List an NFT at a fixed price (the NFT stays with the seller)
+ */
+export interface ListNftInstruction {
+  kind: 'listNft';
+  /**
+   * Price (lamports)
+   */
+  price: bigint;
+  /**
+   * Canonical bump of the listing PDA (from find_listing_address)
+   */
+  bump: number;
+}
+
+/**
+ * Delist an NFT
+ */
+export interface DelistNftInstruction {
+  kind: 'delistNft';
+}
+
+/**
+ * Buy a listed NFT and pay royalties
+ * Royalty recipient accounts: verified creators in order, or the collection authority if none
+ */
+export interface BuyNftInstruction {
+  kind: 'buyNft';
+  /**
+   * Price the buyer agrees to pay, must match the listing price
+   */
+  price: bigint;
+}
+
+/**
+ * Creator signs to verify themselves
+ */
+export interface SignCreatorInstruction {
+  kind: 'signCreator';
+}
+
+/**
+ * Migrate a collection or NFT account from an old layout to the current version (anyone may call, the payer covers extra rent)
+ */
+export interface MigrateAccountInstruction {
+  kind: 'migrateAccount';
+}
+
+export type NFTInstruction =
+  | InitializeCollectionInstruction
+  | MintNftInstruction
+  | TransferNftInstruction
+  | BurnNftInstruction
+  | UpdateNftMetadataInstruction
+  | UpdateCollectionMetadataInstruction
+  | ProposeCollectionAuthorityInstruction
+  | AcceptCollectionAuthorityInstruction
+  | ApproveDelegateInstruction
+  | RevokeDelegateInstruction
+  | SetOperatorApprovalInstruction
+  | SetFreezeAuthorityInstruction
+  | FreezeNftInstruction
+  | ThawNftInstruction
+  | ListNftInstruction
+  | DelistNftInstruction
+  | BuyNftInstruction
+  | SignCreatorInstruction
+  | MigrateAccountInstruction;
+
+export function encodeNFTInstruction(instruction: NFTInstruction): Uint8Array {
+  const w = new BorshWriter();
+  switch (instruction.kind) {
+    case 'initializeCollection':
+      w.u8(0);
+      w.string(instruction.name);
+      w.string(instruction.symbol);
+      w.string(instruction.uri);
+      w.u8(instruction.royaltyPercentage);
+      w.bool(instruction.isMutable);
+      w.u8(instruction.transferMode);
+      w.vec(instruction.creators, (item) => encodeCreator(w, item));
+      w.option(instruction.maxSupply, (value) => w.u64(value));
+      w.u8(instruction.serialMode);
+      w.u8(instruction.bump);
+      break;
+    case 'mintNft':
+      w.u8(1);
+      w.string(instruction.metadataUri);
+      w.u64(instruction.serialNumber);
+      w.u8(instruction.bump);
+      w.option(instruction.recipient, (value) => w.pubkey(value));
+      break;
+    case 'transferNft':
+      w.u8(2);
+      w.pubkey(instruction.newOwner);
+      w.u8(instruction.listingBump);
+      break;
+    case 'burnNft':
+      w.u8(3);
+      w.u8(instruction.listingBump);
+      break;
+    case 'updateNftMetadata':
+      w.u8(4);
+      w.string(instruction.newMetadataUri);
+      break;
+    case 'updateCollectionMetadata':
+      w.u8(5);
+      w.string(instruction.newUri);
+      break;
+    case 'proposeCollectionAuthority':
+      w.u8(6);
+      w.option(instruction.newAuthority, (value) => w.pubkey(value));
+      break;
+    case 'acceptCollectionAuthority':
+      w.u8(7);
+      break;
+    case 'approveDelegate':
+      w.u8(8);
+      w.pubkey(instruction.delegate);
+      break;
+    case 'revokeDelegate':
+      w.u8(9);
+      break;
+    case 'setOperatorApproval':
+      w.u8(10);
+      w.pubkey(instruction.operator);
+      w.bool(instruction.approved);
+      w.option(instruction.expiresAt, (value) => w.i64(value));
+      w.u8(instruction.bump);
+      break;
+    case 'setFreezeAuthority':
+      w.u8(11);
+      w.option(instruction.freezeAuthority, (value) => w.pubkey(value));
+      break;
+    case 'freezeNft':
+      w.u8(12);
+      break;
+    case 'thawNft':
+      w.u8(13);
+      break;
+    case 'listNft':
+      w.u8(14);
+      w.u64(instruction.price);
+      w.u8(instruction.bump);
+      break;
+    case 'delistNft':
+      w.u8(15);
+      break;
+    case 'buyNft':
+      w.u8(16);
+      w.u64(instruction.price);
+      break;
+    case 'signCreator':
+      w.u8(17);
+      break;
+    case 'migrateAccount':
+      w.u8(18);
+      break;
+  }
+  return w.toBytes();
+}
+
+export function decodeNFTInstruction(data: Uint8Array): NFTInstruction {
+  const r = new BorshReader(data);
+  let instruction: NFTInstruction;
+  const discriminator = r.u8();
+  switch (discriminator) {
+    case 0:
+      instruction = {
+        kind: 'initializeCollection',
+        name: r.string(),
+        symbol: r.string(),
+        uri: r.string(),
+        royaltyPercentage: r.u8(),
+        isMutable: r.bool(),
+        transferMode: r.u8() as TransferMode,
+        creators: r.vec(() => decodeCreator(r)),
+        maxSupply: r.option(() => r.u64()),
+        serialMode: r.u8() as SerialMode,
+        bump: r.u8(),
+      };
+      break;
+    case 1:
+      instruction = {
+        kind: 'mintNft',
+        metadataUri: r.string(),
+        serialNumber: r.u64(),
+        bump: r.u8(),
+        recipient: r.option(() => r.pubkey()),
+      };
+      break;
+    case 2:
+      instruction = {
+        kind: 'transferNft',
+        newOwner: r.pubkey(),
+        listingBump: r.u8(),
+      };
+      break;
+    case 3:
+      instruction = {
+        kind: 'burnNft',
+        listingBump: r.u8(),
+      };
+      break;
+    case 4:
+      instruction = {
+        kind: 'updateNftMetadata',
+        newMetadataUri: r.string(),
+      };
+      break;
+    case 5:
+      instruction = {
+        kind: 'updateCollectionMetadata',
+        newUri: r.string(),
+      };
+      break;
+    case 6:
+      instruction = {
+        kind: 'proposeCollectionAuthority',
+        newAuthority: r.option(() => r.pubkey()),
+      };
+      break;
+    case 7:
+      instruction = { kind: 'acceptCollectionAuthority' };
+      break;
+    case 8:
+      instruction = {
+        kind: 'approveDelegate',
+        delegate: r.pubkey(),
+      };
+      break;
+    case 9:
+      instruction = { kind: 'revokeDelegate' };
+      break;
+    case 10:
+      instruction = {
+        kind: 'setOperatorApproval',
+        operator: r.pubkey(),
+        approved: r.bool(),
+        expiresAt: r.option(() => r.i64()),
+        bump: r.u8(),
+      };
+      break;
+    case 11:
+      instruction = {
+        kind: 'setFreezeAuthority',
+        freezeAuthority: r.option(() => r.pubkey()),
+      };
+      break;
+    case 12:
+      instruction = { kind: 'freezeNft' };
+      break;
+    case 13:
+      instruction = { kind: 'thawNft' };
+      break;
+    case 14:
+      instruction = {
+        kind: 'listNft',
+        price: r.u64(),
+        bump: r.u8(),
+      };
+      break;
+    case 15:
+      instruction = { kind: 'delistNft' };
+      break;
+    case 16:
+      instruction = {
+        kind: 'buyNft',
+        price: r.u64(),
+      };
+      break;
+    case 17:
+      instruction = { kind: 'signCreator' };
+      break;
+    case 18:
+      instruction = { kind: 'migrateAccount' };
+      break;
+    default:
+      throw new Error(`Unknown NFTInstruction discriminator ${discriminator}`);
+  }
+  r.expectEnd();
+  return instruction;
+}
+
+export enum NFTError {
+  InvalidInstruction = 0,
+  InvalidOwner = 1,
+  NotInitialized = 2,
+  AlreadyInitialized = 3,
+  Overflow = 4,
+  InsufficientAuthority = 5,
+  AlreadyMinted = 6,
+  NotMinted = 7,
+  InvalidMetadataUri = 8,
+  InvalidRoyaltyPercentage = 9,
+  ImmutableMetadata = 10,
+  OperatorApprovalExpired = 11,
+  NFTFrozen = 12,
+  NonTransferable = 13,
+  InvalidListing = 14,
+  InvalidPrice = 15,
+  InvalidCreators = 16,
+  CreatorNotFound = 17,
+  MaxSupplyReached = 18,
+  SerialNumberBurned = 19,
+  AccountTypeMismatch = 20,
+  UnsupportedAccountVersion = 21,
+  NameTooLong = 22,
+  SymbolTooLong = 23,
+  UriTooLong = 24,
+  InvalidRoyaltyRecipient = 25,
+}
+
+export const NFT_ERROR_MESSAGES: Record<number, string> = {
+  0: '无效的指令 | Invalid instruction',
+  1: '无效的所有者 | Invalid owner',
+  2: '账户未初始化 | Account not initialized',
+  3: '账户已初始化 | Account already initialized',
+  4: '数据溢出 | Overflow',
+  5: '权限不足 | Insufficient authority',
+  6: 'NFT已铸造 | NFT already minted',
+  7: 'NFT未铸造 | NFT not minted',
+  8: '无效的元数据URI | Invalid metadata URI',
+  9: '无效的版税百分比 | Invalid royalty percentage',
+  10: '不可变的元数据 | Immutable metadata',
+  11: '操作员授权已过期 | Operator approval expired',
+  12: 'NFT已冻结 | NFT is frozen',
+  13: 'NFT不可转移 | NFT is non-transferable',
+  14: '无效的挂单 | Invalid listing',
+  15: '无效的价格 | Invalid price',
+  16: '无效的创作者列表 | Invalid creators',
+  17: '找不到创作者 | Creator not found',
+  18: '已达到最大供应量 | Max supply reached',
+  19: '序列号已被销毁 | Serial number already burned',
+  20: '账户类型不匹配 | Account type mismatch',
+  21: '不支持的账户版本 | Unsupported account version',
+  22: '名称过长 | Name too long',
+  23: '符号过长 | Symbol too long',
+  24: 'URI过长 | URI too long',
+  25: '版税接收者与集合的创作者不符 | Royalty recipient does not match the collection\'s creators',
+};
+
+export function decodeNFTError(code: number): { code: number; name: string; msg: string } | null {
+  const msg = NFT_ERROR_MESSAGES[code];
+  return msg === undefined ? null : { code, name: NFTError[code], msg };
+}
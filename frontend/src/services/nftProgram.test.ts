@@ -0,0 +1,127 @@
+import fs from 'fs';
+import path from 'path';
+import { PublicKey } from '@solana/web3.js';
+import {
+  CollectionAccount,
+  decodeCollectionAccountData,
+  decodeListingData,
+  decodeNFTAccountData,
+  decodeNFTError,
+  decodeNFTInstruction,
+  decodeOperatorApprovalData,
+  encodeCollectionAccountData,
+  encodeListingData,
+  encodeNFTAccountData,
+  encodeNFTInstruction,
+  encodeOperatorApprovalData,
+  SerialMode,
+  TransferMode,
+} from './generated/nftProgram';
+
+interface Fixture {
+  name: string;
+  hex: string;
+  value: Record<string, unknown>;
+}
+
+interface Fixtures {
+  instructions: Fixture[];
+  accounts: Fixture[];
+  padded_accounts: Fixture[];
+  errors: { code: number; name: string; msg: string }[];
+}
+
+// Golden Borsh fixtures generated from the Rust types by `cargo run -p nft-bindgen`
+const fixtures: Fixtures = JSON.parse(
+  fs.readFileSync(path.join(__dirname, '../../../fixtures/borsh_fixtures.json'), 'utf8')
+);
+
+const fromHex = (hex: string): Uint8Array =>
+  Uint8Array.from(hex.match(/../g) ?? [], (byte) => parseInt(byte, 16));
+
+const toHex = (bytes: Uint8Array): string =>
+  Array.from(bytes, (byte) => byte.toString(16).padStart(2, '0')).join('');
+
+const camelCase = (name: string): string => name.replace(/_([a-z])/g, (_, c: string) => c.toUpperCase());
+
+const snakeCase = (name: string): string => name.replace(/[A-Z]/g, (c) => `_${c.toLowerCase()}`);
+
+/**
+ * Convert a decoded value into the fixture JSON shape: snake_case keys, 64-bit integers as strings,
+ * base58 public keys and enum variant names
+ */
+const toFixtureValue = (value: unknown, key = ''): unknown => {
+  if (key === 'transferMode') return TransferMode[value as number];
+  if (key === 'serialMode') return SerialMode[value as number];
+  if (value instanceof PublicKey) return value.toBase58();
+  if (typeof value === 'bigint') return value.toString();
+  if (Array.isArray(value)) return value.map((item) => toFixtureValue(item));
+  if (value !== null && typeof value === 'object') {
+    const result: Record<string, unknown> = {};
+    Object.entries(value).forEach(([field, fieldValue]) => {
+      if (field !== 'kind') result[snakeCase(field)] = toFixtureValue(fieldValue, field);
+    });
+    return result;
+  }
+  return value;
+};
+
+const accountCodecs: Record<string, [(data: Uint8Array) => unknown, (value: any) => Uint8Array]> = {
+  CollectionAccount: [decodeCollectionAccountData, encodeCollectionAccountData],
+  NFTAccount: [decodeNFTAccountData, encodeNFTAccountData],
+  OperatorApproval: [decodeOperatorApprovalData, encodeOperatorApprovalData],
+  Listing: [decodeListingData, encodeListingData],
+};
+
+describe('generated Borsh bindings', () => {
+  it.each(fixtures.instructions.map((fixture) => [fixture.name, fixture]))(
+    'round-trips the %s instruction fixture',
+    (_, fixture) => {
+      const instruction = decodeNFTInstruction(fromHex(fixture.hex));
+      expect(instruction.kind).toBe(camelCase(fixture.name));
+      expect(toFixtureValue(instruction)).toEqual(fixture.value);
+      expect(toHex(encodeNFTInstruction(instruction))).toBe(fixture.hex);
+    }
+  );
+
+  it.each(fixtures.accounts.map((fixture) => [fixture.name, fixture]))(
+    'round-trips the %s account fixture',
+    (_, fixture) => {
+      const [decode, encode] = accountCodecs[fixture.name];
+      const account = decode(fromHex(fixture.hex));
+      expect(toFixtureValue(account)).toEqual(fixture.value);
+      expect(toHex(encode(account))).toBe(fixture.hex);
+    }
+  );
+
+  it.each(fixtures.padded_accounts.map((fixture) => [fixture.name, fixture]))(
+    'decodes the %s account fixture padded to its on-chain size',
+    (_, fixture) => {
+      const [decode, encode] = accountCodecs[fixture.name];
+      const account = decode(fromHex(fixture.hex));
+      expect(toFixtureValue(account)).toEqual(fixture.value);
+      const encoded = toHex(encode(account));
+      expect(fixture.hex.startsWith(encoded)).toBe(true);
+      expect(fixture.hex.slice(encoded.length)).toMatch(/^0*$/);
+    }
+  );
+
+  it('ignores trailing account space and rejects other account types', () => {
+    const collection = fixtures.accounts.find((fixture) => fixture.name === 'CollectionAccount')!;
+    const padded = fromHex(`${collection.hex}0000`);
+    const account: CollectionAccount = decodeCollectionAccountData(padded);
+    expect(account.name).toBe('Fixture Collection');
+    expect(() => decodeNFTAccountData(padded)).toThrow('discriminator mismatch');
+  });
+
+  it('rejects trailing instruction bytes', () => {
+    expect(() => decodeNFTInstruction(Uint8Array.from([3, 0]))).toThrow('trailing bytes');
+  });
+
+  it('maps every error code', () => {
+    fixtures.errors.forEach((error) => {
+      expect(decodeNFTError(error.code)).toEqual(error);
+    });
+    expect(decodeNFTError(fixtures.errors.length)).toBeNull();
+  });
+});
@@ -0,0 +1,242 @@
+use std::str::FromStr;
+
+use nft_idl::{Idl, IdlField, IdlType, IdlTypeDef, IdlTypeDefTy};
+use serde_json::{Map, Value};
+use solana_program::pubkey::Pubkey;
+
+// 按IDL描述的布局在Borsh字节和JSON之间转换, 用于生成夹具并核对IDL与真实的Borsh布局一致
+// Convert between Borsh bytes and JSON following the layout described by the IDL, used to build the fixtures
+// and to check that the IDL matches the real Borsh layout
+
+/// 解码一条指令, 返回指令名和参数
+/// Decode an instruction, returning its name and arguments
+pub fn decode_instruction(idl: &Idl, data: &[u8]) -> Result<(String, Value), String> {
+    let (&tag, mut rest) = data.split_first().ok_or("指令数据为空 | empty instruction data")?;
+    let instruction = idl
+        .instructions
+        .iter()
+        .find(|instruction| instruction.discriminator == [tag])
+        .ok_or_else(|| format!("未知指令 | unknown instruction {}", tag))?;
+    let args = decode_fields(idl, &instruction.args, &mut rest)?;
+    expect_end(rest)?;
+    Ok((instruction.name.clone(), args))
+}
+
+/// 按指令名编码指令参数
+/// Encode instruction arguments by instruction name
+pub fn encode_instruction(idl: &Idl, name: &str, args: &Value) -> Result<Vec<u8>, String> {
+    let instruction = idl
+        .instructions
+        .iter()
+        .find(|instruction| instruction.name == *name)
+        .ok_or_else(|| format!("未知指令 | unknown instruction {}", name))?;
+    let mut data = instruction.discriminator.clone();
+    encode_fields(idl, &instruction.args, args, &mut data)?;
+    Ok(data)
+}
+
+/// 校验鉴别符并解码账户字段
+/// Check the discriminator and decode the account fields
+pub fn decode_account(idl: &Idl, name: &str, data: &[u8]) -> Result<Value, String> {
+    let account = idl
+        .accounts
+        .iter()
+        .find(|account| account.name == name)
+        .ok_or_else(|| format!("未知账户 | unknown account {}", name))?;
+    let mut rest = data
+        .strip_prefix(account.discriminator.as_slice())
+        .ok_or_else(|| format!("{} 鉴别符不匹配 | {} discriminator mismatch", name, name))?;
+    let value = decode_fields(idl, struct_fields(type_def(idl, name)?)?, &mut rest)?;
+    expect_end(rest)?;
+    Ok(value)
+}
+
+/// 编码账户数据 (包括鉴别符)
+/// Encode account data (including the discriminator)
+pub fn encode_account(idl: &Idl, name: &str, value: &Value) -> Result<Vec<u8>, String> {
+    let account = idl
+        .accounts
+        .iter()
+        .find(|account| account.name == name)
+        .ok_or_else(|| format!("未知账户 | unknown account {}", name))?;
+    let mut data = account.discriminator.clone();
+    encode_fields(idl, struct_fields(type_def(idl, name)?)?, value, &mut data)?;
+    Ok(data)
+}
+
+pub(crate) fn type_def<'a>(idl: &'a Idl, name: &str) -> Result<&'a IdlTypeDef, String> {
+    idl.types
+        .iter()
+        .find(|ty| ty.name == name)
+        .ok_or_else(|| format!("未知类型 | unknown type {}", name))
+}
+
+fn struct_fields(type_def: &IdlTypeDef) -> Result<&[IdlField], String> {
+    match &type_def.ty {
+        IdlTypeDefTy::Struct { fields } => Ok(fields),
+        IdlTypeDefTy::Enum { .. } => Err(format!("{} 不是结构体 | {} is not a struct", type_def.name, type_def.name)),
+    }
+}
+
+fn expect_end(rest: &[u8]) -> Result<(), String> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("剩余 {} 字节未读取 | {} trailing bytes", rest.len(), rest.len()))
+    }
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if data.len() < len {
+        return Err("数据不足 | unexpected end of data".to_string());
+    }
+    let (head, rest) = data.split_at(len);
+    *data = rest;
+    Ok(head)
+}
+
+fn decode_fields(idl: &Idl, fields: &[IdlField], data: &mut &[u8]) -> Result<Value, String> {
+    let mut object = Map::new();
+    for field in fields {
+        object.insert(field.name.clone(), decode_value(idl, &field.ty, data)?);
+    }
+    Ok(Value::Object(object))
+}
+
+fn decode_value(idl: &Idl, ty: &IdlType, data: &mut &[u8]) -> Result<Value, String> {
+    Ok(match ty {
+        // 64位整数以字符串表示, 避免超出JSON数字的安全范围
+        // 64-bit integers are strings so they stay within the safe range of JSON numbers
+        IdlType::Primitive(name) => match name.as_str() {
+            "u8" => Value::from(take(data, 1)?[0]),
+            "u16" => Value::from(u16::from_le_bytes(take(data, 2)?.try_into().unwrap())),
+            "u32" => Value::from(u32::from_le_bytes(take(data, 4)?.try_into().unwrap())),
+            "u64" => Value::from(u64::from_le_bytes(take(data, 8)?.try_into().unwrap()).to_string()),
+            "i64" => Value::from(i64::from_le_bytes(take(data, 8)?.try_into().unwrap()).to_string()),
+            "bool" => match take(data, 1)?[0] {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                other => return Err(format!("无效的布尔值 | invalid bool {}", other)),
+            },
+            "string" => {
+                let len = u32::from_le_bytes(take(data, 4)?.try_into().unwrap()) as usize;
+                let bytes = take(data, len)?.to_vec();
+                Value::from(String::from_utf8(bytes).map_err(|_| "无效的UTF-8 | invalid UTF-8")?)
+            }
+            "pubkey" => Value::from(Pubkey::new_from_array(take(data, 32)?.try_into().unwrap()).to_string()),
+            other => return Err(format!("不支持的类型 | unsupported type {}", other)),
+        },
+        IdlType::Option { option } => match take(data, 1)?[0] {
+            0 => Value::Null,
+            1 => decode_value(idl, option, data)?,
+            other => return Err(format!("无效的Option标签 | invalid option tag {}", other)),
+        },
+        IdlType::Vec { vec } => {
+            let len = u32::from_le_bytes(take(data, 4)?.try_into().unwrap());
+            let items = (0..len).map(|_| decode_value(idl, vec, data)).collect::<Result<_, _>>()?;
+            Value::Array(items)
+        }
+        IdlType::Array { array: (item, len) } => {
+            let items = (0..*len).map(|_| decode_value(idl, item, data)).collect::<Result<_, _>>()?;
+            Value::Array(items)
+        }
+        IdlType::Defined { defined } => match &type_def(idl, &defined.name)?.ty {
+            IdlTypeDefTy::Struct { fields } => decode_fields(idl, fields, data)?,
+            IdlTypeDefTy::Enum { variants } => {
+                let tag = take(data, 1)?[0] as usize;
+                let variant = variants
+                    .get(tag)
+                    .ok_or_else(|| format!("{} 标签无效 | invalid {} tag {}", defined.name, defined.name, tag))?;
+                match &variant.fields {
+                    None => Value::from(variant.name.clone()),
+                    Some(fields) => {
+                        let mut object = Map::new();
+                        object.insert(variant.name.clone(), decode_fields(idl, fields, data)?);
+                        Value::Object(object)
+                    }
+                }
+            }
+        },
+    })
+}
+
+fn encode_fields(idl: &Idl, fields: &[IdlField], value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    for field in fields {
+        let field_value = value
+            .get(&field.name)
+            .ok_or_else(|| format!("缺少字段 | missing field {}", field.name))?;
+        encode_value(idl, &field.ty, field_value, out)?;
+    }
+    Ok(())
+}
+
+fn encode_value(idl: &Idl, ty: &IdlType, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+    let mismatch = || format!("值与类型不匹配 | value {} does not match {:?}", value, ty);
+    match ty {
+        IdlType::Primitive(name) => match name.as_str() {
+            "u8" => out.push(value.as_u64().and_then(|n| u8::try_from(n).ok()).ok_or_else(mismatch)?),
+            "u16" => out.extend(value.as_u64().and_then(|n| u16::try_from(n).ok()).ok_or_else(mismatch)?.to_le_bytes()),
+            "u32" => out.extend(value.as_u64().and_then(|n| u32::try_from(n).ok()).ok_or_else(mismatch)?.to_le_bytes()),
+            // 64位整数接受字符串, 也接受安全范围内的数字
+            // 64-bit integers accept strings as well as numbers within the safe range
+            "u64" => out.extend(value.as_u64().or_else(|| parse_str(value)).ok_or_else(mismatch)?.to_le_bytes()),
+            "i64" => out.extend(value.as_i64().or_else(|| parse_str(value)).ok_or_else(mismatch)?.to_le_bytes()),
+            "bool" => out.push(value.as_bool().ok_or_else(mismatch)? as u8),
+            "string" => {
+                let string = value.as_str().ok_or_else(mismatch)?;
+                out.extend((string.len() as u32).to_le_bytes());
+                out.extend(string.as_bytes());
+            }
+            "pubkey" => out.extend(parse_str::<Pubkey>(value).ok_or_else(mismatch)?.to_bytes()),
+            other => return Err(format!("不支持的类型 | unsupported type {}", other)),
+        },
+        IdlType::Option { option } => {
+            if value.is_null() {
+                out.push(0);
+            } else {
+                out.push(1);
+                encode_value(idl, option, value, out)?;
+            }
+        }
+        IdlType::Vec { vec } => {
+            let items = value.as_array().ok_or_else(mismatch)?;
+            out.extend((items.len() as u32).to_le_bytes());
+            for item in items {
+                encode_value(idl, vec, item, out)?;
+            }
+        }
+        IdlType::Array { array: (item, len) } => {
+            let items = value.as_array().filter(|items| items.len() == *len).ok_or_else(mismatch)?;
+            for element in items {
+                encode_value(idl, item, element, out)?;
+            }
+        }
+        IdlType::Defined { defined } => match &type_def(idl, &defined.name)?.ty {
+            IdlTypeDefTy::Struct { fields } => encode_fields(idl, fields, value, out)?,
+            IdlTypeDefTy::Enum { variants } => {
+                // 单元变体为字符串, 带字段的变体为 `{"<变体名>": {字段}}`
+                // Unit variants are strings, variants with fields are `{"<variant name>": {fields}}`
+                let (name, fields_value) = match value {
+                    Value::String(name) => (name.as_str(), None),
+                    Value::Object(object) if object.len() == 1 => {
+                        let (name, fields) = object.iter().next().unwrap();
+                        (name.as_str(), Some(fields))
+                    }
+                    _ => return Err(mismatch()),
+                };
+                let tag = variants.iter().position(|variant| variant.name == name).ok_or_else(mismatch)?;
+                out.push(tag as u8);
+                match (&variants[tag].fields, fields_value) {
+                    (None, None) => {}
+                    (Some(fields), Some(fields_value)) => encode_fields(idl, fields, fields_value, out)?,
+                    _ => return Err(mismatch()),
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+fn parse_str<T: FromStr>(value: &Value) -> Option<T> {
+    value.as_str()?.parse().ok()
+}
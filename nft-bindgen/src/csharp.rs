@@ -0,0 +1,449 @@
+use std::fmt::Write;
+
+use nft_idl::{Idl, IdlField, IdlType, IdlTypeDefTy};
+
+use crate::{binding_types, is_unit_enum, pascal_case};
+
+// Borsh读写器运行时, 原样写入生成的文件
+// Borsh reader/writer runtime, copied verbatim into the generated file
+const RUNTIME: &str = r#"// 此文件由 nft-bindgen 根据程序IDL生成, 请勿手动修改 (运行 `cargo run -p nft-bindgen` 重新生成)
+// Generated by nft-bindgen from the program IDL, do not edit by hand (run `cargo run -p nft-bindgen` to regenerate)
+using Solnet.Wallet;
+using System;
+using System.Buffers.Binary;
+using System.Collections.Generic;
+using System.IO;
+using System.Text;
+
+namespace NFTClient.Generated
+{
+    /// <summary>
+    /// Borsh encoder
+    /// </summary>
+    public sealed class BorshWriter
+    {
+        private readonly MemoryStream _stream = new MemoryStream();
+        private readonly BinaryWriter _writer;
+
+        public BorshWriter()
+        {
+            _writer = new BinaryWriter(_stream);
+        }
+
+        public void WriteU8(byte value) => _writer.Write(value);
+
+        public void WriteU16(ushort value) => _writer.Write(value);
+
+        public void WriteU32(uint value) => _writer.Write(value);
+
+        public void WriteU64(ulong value) => _writer.Write(value);
+
+        public void WriteI64(long value) => _writer.Write(value);
+
+        public void WriteBool(bool value) => _writer.Write((byte)(value ? 1 : 0));
+
+        public void WriteString(string value)
+        {
+            var bytes = Encoding.UTF8.GetBytes(value);
+            WriteU32((uint)bytes.Length);
+            _writer.Write(bytes);
+        }
+
+        public void WritePubkey(PublicKey value) => _writer.Write(value.KeyBytes);
+
+        public void WriteOptionTag(bool present) => WriteBool(present);
+
+        public void WriteBytes(byte[] value) => _writer.Write(value);
+
+        public byte[] ToArray()
+        {
+            _writer.Flush();
+            return _stream.ToArray();
+        }
+    }
+
+    /// <summary>
+    /// Borsh decoder
+    /// </summary>
+    public sealed class BorshReader
+    {
+        private readonly byte[] _data;
+        private int _offset;
+
+        public BorshReader(byte[] data, int offset = 0)
+        {
+            _data = data;
+            _offset = offset;
+        }
+
+        public byte ReadU8() => Take(1)[0];
+
+        public ushort ReadU16() => BinaryPrimitives.ReadUInt16LittleEndian(Take(2));
+
+        public uint ReadU32() => BinaryPrimitives.ReadUInt32LittleEndian(Take(4));
+
+        public ulong ReadU64() => BinaryPrimitives.ReadUInt64LittleEndian(Take(8));
+
+        public long ReadI64() => BinaryPrimitives.ReadInt64LittleEndian(Take(8));
+
+        public bool ReadBool()
+        {
+            var value = ReadU8();
+            if (value > 1)
+            {
+                throw new InvalidDataException($"Invalid bool {value}");
+            }
+            return value == 1;
+        }
+
+        public string ReadString() => Encoding.UTF8.GetString(Take(checked((int)ReadU32())));
+
+        public PublicKey ReadPubkey() => new PublicKey(Take(32));
+
+        public bool ReadOptionTag()
+        {
+            var tag = ReadU8();
+            if (tag > 1)
+            {
+                throw new InvalidDataException($"Invalid option tag {tag}");
+            }
+            return tag == 1;
+        }
+
+        public List<T> ReadVec<T>(Func<T> read)
+        {
+            var length = ReadU32();
+            var items = new List<T>();
+            for (uint i = 0; i < length; i++)
+            {
+                items.Add(read());
+            }
+            return items;
+        }
+
+        public T[] ReadArray<T>(int length, Func<T> read)
+        {
+            var items = new T[length];
+            for (var i = 0; i < length; i++)
+            {
+                items[i] = read();
+            }
+            return items;
+        }
+
+        public byte[] Take(int length)
+        {
+            if (length < 0 || _offset + length > _data.Length)
+            {
+                throw new InvalidDataException("Unexpected end of data");
+            }
+            var bytes = new byte[length];
+            Array.Copy(_data, _offset, bytes, 0, length);
+            _offset += length;
+            return bytes;
+        }
+
+        public void ExpectEnd()
+        {
+            if (_offset != _data.Length)
+            {
+                throw new InvalidDataException($"{_data.Length - _offset} trailing bytes");
+            }
+        }
+    }
+"#;
+
+/// 生成C#绑定
+/// Generate the C# bindings
+pub fn generate(idl: &Idl) -> String {
+    let mut out = RUNTIME.to_string();
+
+    for type_def in binding_types(idl) {
+        out.push('\n');
+        doc_comment(&mut out, "    ", &type_def.docs);
+        match &type_def.ty {
+            IdlTypeDefTy::Enum { variants } => {
+                assert!(
+                    is_unit_enum(idl, &type_def.name),
+                    "仅支持单元变体枚举 | only unit-variant enums are supported: {}",
+                    type_def.name
+                );
+                writeln!(out, "    public enum {} : byte\n    {{", type_def.name).unwrap();
+                for (index, variant) in variants.iter().enumerate() {
+                    writeln!(out, "        {} = {},", variant.name, index).unwrap();
+                }
+                out.push_str("    }\n");
+            }
+            IdlTypeDefTy::Struct { fields } => {
+                writeln!(out, "    public class {}\n    {{", type_def.name).unwrap();
+                properties(&mut out, idl, fields);
+
+                // 账户类型额外提供带鉴别符的读写; 账户可能有预留空间, 因此不检查剩余字节
+                // Account types also read and write the discriminator; accounts may be over-allocated, so trailing
+                // bytes are not checked
+                if let Some(account) = idl.accounts.iter().find(|account| account.name == type_def.name) {
+                    let discriminator: Vec<String> = account.discriminator.iter().map(u8::to_string).collect();
+                    write!(
+                        out,
+                        r#"        public static readonly byte[] Discriminator = {{ {discriminator} }};
+
+        public byte[] ToAccountData()
+        {{
+            var w = new BorshWriter();
+            w.WriteBytes(Discriminator);
+            Serialize(w);
+            return w.ToArray();
+        }}
+
+        public static {name} FromAccountData(byte[] data)
+        {{
+            if (data.Length < Discriminator.Length || !data.AsSpan(0, Discriminator.Length).SequenceEqual(Discriminator))
+            {{
+                throw new InvalidDataException("{name} discriminator mismatch");
+            }}
+            return Deserialize(new BorshReader(data, Discriminator.Length));
+        }}
+
+"#,
+                        discriminator = discriminator.join(", "),
+                        name = type_def.name,
+                    )
+                    .unwrap();
+                }
+
+                out.push_str("        public void Serialize(BorshWriter w)\n        {\n");
+                for field in fields {
+                    write_stmts(&mut out, idl, &field.ty, &pascal_case(&field.name), 3, 0);
+                }
+                out.push_str("        }\n\n");
+                writeln!(out, "        public static {} Deserialize(BorshReader r)\n        {{", type_def.name).unwrap();
+                object_initializer(&mut out, idl, &type_def.name, fields);
+                out.push_str("        }\n    }\n");
+            }
+        }
+    }
+
+    // 指令: 抽象基类加每个变体一个子类
+    // Instructions: an abstract base class plus one subclass per variant
+    out.push_str(
+        r#"
+    /// <summary>
+    /// Program instruction, Borsh-encoded with a one-byte variant tag
+    /// </summary>
+    public abstract class NFTInstruction
+    {
+        public abstract byte Discriminator { get; }
+
+        protected abstract void SerializeArgs(BorshWriter w);
+
+        public byte[] Serialize()
+        {
+            var w = new BorshWriter();
+            w.WriteU8(Discriminator);
+            SerializeArgs(w);
+            return w.ToArray();
+        }
+
+        public static NFTInstruction Deserialize(byte[] data)
+        {
+            var r = new BorshReader(data);
+            var discriminator = r.ReadU8();
+            NFTInstruction instruction = discriminator switch
+            {
+"#,
+    );
+    for instruction in &idl.instructions {
+        writeln!(
+            out,
+            "                {} => {}Instruction.DeserializeArgs(r),",
+            instruction.discriminator[0],
+            pascal_case(&instruction.name)
+        )
+        .unwrap();
+    }
+    out.push_str(
+        r#"                _ => throw new InvalidDataException($"Unknown NFTInstruction discriminator {discriminator}"),
+            };
+            r.ExpectEnd();
+            return instruction;
+        }
+    }
+"#,
+    );
+    for instruction in &idl.instructions {
+        let class_name = format!("{}Instruction", pascal_case(&instruction.name));
+        out.push('\n');
+        doc_comment(&mut out, "    ", &instruction.docs);
+        writeln!(out, "    public sealed class {} : NFTInstruction\n    {{", class_name).unwrap();
+        properties(&mut out, idl, &instruction.args);
+        writeln!(out, "        public override byte Discriminator => {};\n", instruction.discriminator[0]).unwrap();
+        out.push_str("        protected override void SerializeArgs(BorshWriter w)\n        {\n");
+        for arg in &instruction.args {
+            write_stmts(&mut out, idl, &arg.ty, &pascal_case(&arg.name), 3, 0);
+        }
+        out.push_str("        }\n\n");
+        writeln!(out, "        internal static {} DeserializeArgs(BorshReader r)\n        {{", class_name).unwrap();
+        if instruction.args.is_empty() {
+            writeln!(out, "            return new {}();", class_name).unwrap();
+        } else {
+            object_initializer(&mut out, idl, &class_name, &instruction.args);
+        }
+        out.push_str("        }\n    }\n");
+    }
+
+    // 错误: 自定义错误码 (ProgramError::Custom) 到名称和消息的映射
+    // Errors: map custom error codes (ProgramError::Custom) to names and messages
+    out.push_str("\n    /// <summary>\n    /// Program error codes (ProgramError::Custom)\n    /// </summary>\n");
+    out.push_str("    public enum NFTError : uint\n    {\n");
+    for error in &idl.errors {
+        writeln!(out, "        {} = {},", error.name, error.code).unwrap();
+    }
+    out.push_str("    }\n\n    public static class NFTErrors\n    {\n");
+    out.push_str("        public static string Message(NFTError error) => error switch\n        {\n");
+    for error in &idl.errors {
+        writeln!(out, "            NFTError.{} => {},", error.name, string_literal(&error.msg)).unwrap();
+    }
+    out.push_str(
+        r#"            _ => throw new ArgumentOutOfRangeException(nameof(error)),
+        };
+
+        public static NFTError? FromCode(uint code) => Enum.IsDefined(typeof(NFTError), code) ? (NFTError)code : null;
+    }
+}
+"#,
+    );
+    out
+}
+
+fn doc_comment(out: &mut String, indent: &str, docs: &[String]) {
+    if docs.is_empty() {
+        return;
+    }
+    writeln!(out, "{}/// <summary>", indent).unwrap();
+    for line in docs {
+        let escaped = line.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        writeln!(out, "{}/// {}", indent, escaped).unwrap();
+    }
+    writeln!(out, "{}/// </summary>", indent).unwrap();
+}
+
+fn properties(out: &mut String, idl: &Idl, fields: &[IdlField]) {
+    for field in fields {
+        doc_comment(out, "        ", &field.docs);
+        write!(out, "        public {} {} {{ get; set; }}", type_name(&field.ty), pascal_case(&field.name)).unwrap();
+        match default_value(idl, &field.ty) {
+            Some(default) => writeln!(out, " = {};\n", default).unwrap(),
+            None => out.push_str("\n\n"),
+        }
+    }
+}
+
+fn object_initializer(out: &mut String, idl: &Idl, class_name: &str, fields: &[IdlField]) {
+    writeln!(out, "            return new {}\n            {{", class_name).unwrap();
+    for field in fields {
+        writeln!(out, "                {} = {},", pascal_case(&field.name), read_expr(idl, &field.ty)).unwrap();
+    }
+    out.push_str("            };\n");
+}
+
+fn type_name(ty: &IdlType) -> String {
+    match ty {
+        IdlType::Primitive(name) => match name.as_str() {
+            "u8" => "byte",
+            "u16" => "ushort",
+            "u32" => "uint",
+            "u64" => "ulong",
+            "i64" => "long",
+            "bool" => "bool",
+            "string" => "string",
+            "pubkey" => "PublicKey",
+            other => panic!("不支持的类型 | unsupported type {}", other),
+        }
+        .to_string(),
+        IdlType::Option { option } => format!("{}?", type_name(option)),
+        IdlType::Vec { vec } => format!("List<{}>", type_name(vec)),
+        IdlType::Array { array: (item, _) } => format!("{}[]", type_name(item)),
+        IdlType::Defined { defined } => defined.name.clone(),
+    }
+}
+
+// 非空引用类型属性的默认值
+// Default values for non-nullable reference type properties
+fn default_value(idl: &Idl, ty: &IdlType) -> Option<String> {
+    match ty {
+        IdlType::Primitive(name) if name == "string" => Some("string.Empty".to_string()),
+        IdlType::Primitive(name) if name == "pubkey" => Some("new PublicKey(new byte[32])".to_string()),
+        IdlType::Primitive(_) | IdlType::Option { .. } => None,
+        IdlType::Vec { .. } | IdlType::Array { .. } => Some("new()".to_string()),
+        IdlType::Defined { defined } if is_unit_enum(idl, &defined.name) => None,
+        IdlType::Defined { .. } => Some("new()".to_string()),
+    }
+}
+
+fn is_value_type(idl: &Idl, ty: &IdlType) -> bool {
+    match ty {
+        IdlType::Primitive(name) => name != "string" && name != "pubkey",
+        IdlType::Defined { defined } => is_unit_enum(idl, &defined.name),
+        _ => false,
+    }
+}
+
+fn write_stmts(out: &mut String, idl: &Idl, ty: &IdlType, value: &str, level: usize, depth: usize) {
+    let indent = "    ".repeat(level);
+    match ty {
+        IdlType::Primitive(name) => writeln!(out, "{}w.Write{}({});", indent, primitive_method(name), value).unwrap(),
+        IdlType::Option { option } => {
+            writeln!(out, "{}w.WriteOptionTag({} is not null);", indent, value).unwrap();
+            writeln!(out, "{}if ({} is not null)\n{}{{", indent, value, indent).unwrap();
+            let inner = if is_value_type(idl, option) { format!("{}.Value", value) } else { value.to_string() };
+            write_stmts(out, idl, option, &inner, level + 1, depth);
+            writeln!(out, "{}}}", indent).unwrap();
+        }
+        IdlType::Vec { vec: item } | IdlType::Array { array: (item, _) } => {
+            if matches!(ty, IdlType::Vec { .. }) {
+                writeln!(out, "{}w.WriteU32((uint){}.Count);", indent, value).unwrap();
+            }
+            let item_name = format!("item{}", depth);
+            writeln!(out, "{}foreach (var {} in {})\n{}{{", indent, item_name, value, indent).unwrap();
+            write_stmts(out, idl, item, &item_name, level + 1, depth + 1);
+            writeln!(out, "{}}}", indent).unwrap();
+        }
+        IdlType::Defined { defined } if is_unit_enum(idl, &defined.name) => {
+            writeln!(out, "{}w.WriteU8((byte){});", indent, value).unwrap()
+        }
+        IdlType::Defined { .. } => writeln!(out, "{}{}.Serialize(w);", indent, value).unwrap(),
+    }
+}
+
+fn read_expr(idl: &Idl, ty: &IdlType) -> String {
+    match ty {
+        IdlType::Primitive(name) => format!("r.Read{}()", primitive_method(name)),
+        IdlType::Option { option } => {
+            format!("r.ReadOptionTag() ? ({}?){} : null", type_name(option), read_expr(idl, option))
+        }
+        IdlType::Vec { vec } => format!("r.ReadVec(() => {})", read_expr(idl, vec)),
+        IdlType::Array { array: (item, len) } => format!("r.ReadArray({}, () => {})", len, read_expr(idl, item)),
+        IdlType::Defined { defined } if is_unit_enum(idl, &defined.name) => {
+            format!("({})r.ReadU8()", defined.name)
+        }
+        IdlType::Defined { defined } => format!("{}.Deserialize(r)", defined.name),
+    }
+}
+
+fn primitive_method(name: &str) -> &str {
+    match name {
+        "u8" => "U8",
+        "u16" => "U16",
+        "u32" => "U32",
+        "u64" => "U64",
+        "i64" => "I64",
+        "bool" => "Bool",
+        "string" => "String",
+        "pubkey" => "Pubkey",
+        other => panic!("不支持的类型 | unsupported type {}", other),
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
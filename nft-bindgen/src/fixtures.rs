@@ -0,0 +1,217 @@
+use borsh::BorshSerialize;
+use nft_contract::{
+    instruction::NFTInstruction,
+    state::{
+        AccountKey, CollectionAccount, Creator, Listing, NFTAccount, OperatorApproval, ProgramAccount, SerialMode,
+        TransferMode, COLLECTION_ACCOUNT_SIZE, NFT_ACCOUNT_SIZE,
+    },
+};
+use nft_idl::Idl;
+use serde_json::{json, Value};
+use solana_program::pubkey::Pubkey;
+
+use crate::codec;
+
+/// 生成黄金夹具: 由真实的Rust类型Borsh序列化得到的十六进制字节, 以及按IDL解码出的JSON值
+/// Generate the golden fixtures: hex bytes Borsh-serialized from the real Rust types, and the JSON values decoded through the IDL
+pub fn generate(idl: &Idl) -> Value {
+    let instructions: Vec<Value> = sample_instructions()
+        .iter()
+        .map(|instruction| {
+            let data = instruction.try_to_vec().unwrap();
+            let (name, args) = codec::decode_instruction(idl, &data)
+                .unwrap_or_else(|error| panic!("IDL与指令布局不一致 | IDL does not match instruction layout: {}", error));
+            json!({ "name": name, "hex": hex(&data), "value": args })
+        })
+        .collect();
+
+    let accounts: Vec<Value> = sample_accounts()
+        .into_iter()
+        .map(|(name, data)| {
+            let value = codec::decode_account(idl, name, &data)
+                .unwrap_or_else(|error| panic!("IDL与 {} 布局不一致 | IDL does not match {} layout: {}", name, name, error));
+            json!({ "name": name, "hex": hex(&data), "value": value })
+        })
+        .collect();
+
+    // 链上账户按固定大小分配, 数据后面是零填充; 这些夹具与链上读到的字节一致
+    // On-chain accounts are allocated at a fixed size with zero padding after the data; these fixtures match the bytes
+    // read from chain
+    let padded_accounts: Vec<Value> = sample_accounts()
+        .into_iter()
+        .filter_map(|(name, mut data)| {
+            let size = match name {
+                "CollectionAccount" => COLLECTION_ACCOUNT_SIZE,
+                "NFTAccount" => NFT_ACCOUNT_SIZE,
+                _ => return None,
+            };
+            let value = codec::decode_account(idl, name, &data).expect("上面已校验布局 | layout checked above");
+            assert!(data.len() <= size, "{} 超出账户大小 | {} exceeds the account size", name, name);
+            data.resize(size, 0);
+            Some(json!({ "name": name, "hex": hex(&data), "value": value }))
+        })
+        .collect();
+
+    let errors: Vec<Value> = idl
+        .errors
+        .iter()
+        .map(|error| json!({ "code": error.code, "name": error.name, "msg": error.msg }))
+        .collect();
+
+    json!({ "instructions": instructions, "accounts": accounts, "padded_accounts": padded_accounts, "errors": errors })
+}
+
+/// 小写十六进制编码
+/// Lowercase hex encoding
+pub fn hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// 十六进制解码
+/// Hex decoding
+pub fn unhex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+// 固定的公钥, 让夹具在每次生成时保持不变
+// Fixed public keys so the fixtures stay identical between runs
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+// 每个指令变体至少一例, 可选字段同时覆盖有值和为空
+// At least one sample per instruction variant, covering optional fields both set and empty
+fn sample_instructions() -> Vec<NFTInstruction> {
+    vec![
+        NFTInstruction::InitializeCollection {
+            name: "Fixture Collection".to_string(),
+            symbol: "FIX".to_string(),
+            uri: "https://example.com/collection.json".to_string(),
+            royalty_percentage: 5,
+            is_mutable: true,
+            transfer_mode: TransferMode::Transferable,
+            creators: vec![
+                Creator { address: key(1), verified: true, share: 70 },
+                Creator { address: key(2), verified: false, share: 30 },
+            ],
+            max_supply: Some(10_000),
+            serial_mode: SerialMode::Sequential,
+            bump: 254,
+        },
+        NFTInstruction::InitializeCollection {
+            name: "灵魂绑定".to_string(),
+            symbol: "SBT".to_string(),
+            uri: "ipfs://soulbound".to_string(),
+            royalty_percentage: 0,
+            is_mutable: false,
+            transfer_mode: TransferMode::AuthorityRevocable,
+            creators: vec![],
+            max_supply: None,
+            serial_mode: SerialMode::CallerAssigned,
+            bump: 255,
+        },
+        NFTInstruction::MintNFT {
+            metadata_uri: "https://example.com/nft/1.json".to_string(),
+            serial_number: 1,
+            bump: 253,
+            recipient: Some(key(3)),
+        },
+        NFTInstruction::MintNFT {
+            metadata_uri: "ipfs://nft".to_string(),
+            serial_number: u64::MAX,
+            bump: 0,
+            recipient: None,
+        },
+        NFTInstruction::TransferNFT { new_owner: key(4), listing_bump: 255 },
+        NFTInstruction::BurnNFT { listing_bump: 251 },
+        NFTInstruction::UpdateNFTMetadata { new_metadata_uri: "https://example.com/nft/1-v2.json".to_string() },
+        NFTInstruction::UpdateCollectionMetadata { new_uri: "https://example.com/collection-v2.json".to_string() },
+        NFTInstruction::ProposeCollectionAuthority { new_authority: Some(key(5)) },
+        NFTInstruction::ProposeCollectionAuthority { new_authority: None },
+        NFTInstruction::AcceptCollectionAuthority,
+        NFTInstruction::ApproveDelegate { delegate: key(6) },
+        NFTInstruction::RevokeDelegate,
+        NFTInstruction::SetOperatorApproval {
+            operator: key(7),
+            approved: true,
+            expires_at: Some(1_700_000_000),
+            bump: 254,
+        },
+        NFTInstruction::SetOperatorApproval { operator: key(7), approved: false, expires_at: None, bump: 254 },
+        NFTInstruction::SetFreezeAuthority { freeze_authority: Some(key(8)) },
+        NFTInstruction::FreezeNFT,
+        NFTInstruction::ThawNFT,
+        NFTInstruction::ListNFT { price: 1_500_000_000, bump: 253 },
+        NFTInstruction::DelistNFT,
+        NFTInstruction::BuyNFT { price: 1_500_000_000 },
+        NFTInstruction::SignCreator,
+        NFTInstruction::MigrateAccount,
+    ]
+}
+
+fn sample_accounts() -> Vec<(&'static str, Vec<u8>)> {
+    let collection = CollectionAccount {
+        key: AccountKey::Collection,
+        version: CollectionAccount::VERSION,
+        authority: key(10),
+        name: "Fixture Collection".to_string(),
+        symbol: "FIX".to_string(),
+        uri: "https://example.com/collection.json".to_string(),
+        royalty_percentage: 5,
+        is_mutable: true,
+        total_minted: 42,
+        pending_authority: Some(key(11)),
+        freeze_authority: None,
+        transfer_mode: TransferMode::Soulbound,
+        creators: vec![Creator { address: key(12), verified: true, share: 100 }],
+        max_supply: Some(100),
+        serial_mode: SerialMode::Sequential,
+        total_burned: 2,
+        bump: 254,
+        original_authority: key(19),
+    };
+    let nft = NFTAccount {
+        key: AccountKey::NFT,
+        version: NFTAccount::VERSION,
+        owner: key(13),
+        collection: key(14),
+        metadata_uri: "https://example.com/nft/42.json".to_string(),
+        is_minted: true,
+        serial_number: 42,
+        created_at: 1_700_000_000,
+        updated_at: -1,
+        delegate: Some(key(15)),
+        is_frozen: false,
+        bump: 253,
+        minter: None,
+    };
+    let approval = OperatorApproval {
+        key: AccountKey::OperatorApproval,
+        version: OperatorApproval::VERSION,
+        owner: key(16),
+        collection: key(14),
+        operator: key(17),
+        expires_at: Some(1_800_000_000),
+        bump: 252,
+    };
+    let listing = Listing {
+        key: AccountKey::Listing,
+        version: Listing::VERSION,
+        nft: key(18),
+        seller: key(16),
+        price: 2_000_000_000,
+        bump: 251,
+    };
+    vec![
+        ("CollectionAccount", collection.try_to_vec().unwrap()),
+        ("NFTAccount", nft.try_to_vec().unwrap()),
+        ("OperatorApproval", approval.try_to_vec().unwrap()),
+        ("Listing", listing.try_to_vec().unwrap()),
+    ]
+}
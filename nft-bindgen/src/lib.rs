@@ -0,0 +1,81 @@
+use nft_idl::{Idl, IdlTypeDef, IdlTypeDefTy};
+
+pub mod codec;
+pub mod csharp;
+pub mod fixtures;
+pub mod typescript;
+
+#[cfg(test)]
+mod test;
+
+/// 生成的TypeScript绑定路径 (相对于工作区根目录)
+/// Path of the generated TypeScript bindings (relative to the workspace root)
+pub const TYPESCRIPT_PATH: &str = "frontend/src/services/generated/nftProgram.ts";
+
+/// 生成的C#绑定路径 (相对于工作区根目录)
+/// Path of the generated C# bindings (relative to the workspace root)
+pub const CSHARP_PATH: &str = "client/NFTClient/Generated/NFTProgram.cs";
+
+/// 黄金Borsh夹具路径 (相对于工作区根目录)
+/// Path of the golden Borsh fixtures (relative to the workspace root)
+pub const FIXTURES_PATH: &str = "fixtures/borsh_fixtures.json";
+
+/// 生成全部输出文件: (路径, 内容)
+/// Generate every output file: (path, contents)
+pub fn generate_all() -> Vec<(&'static str, String)> {
+    let idl = nft_idl::generate();
+    let mut fixtures =
+        serde_json::to_string_pretty(&fixtures::generate(&idl)).expect("夹具可以序列化 | fixtures are serializable");
+    fixtures.push('\n');
+    vec![
+        (TYPESCRIPT_PATH, typescript::generate(&idl)),
+        (CSHARP_PATH, csharp::generate(&idl)),
+        (FIXTURES_PATH, fixtures),
+    ]
+}
+
+/// snake_case 转 camelCase
+/// Convert snake_case to camelCase
+pub fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|first| first.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// snake_case 转 PascalCase
+/// Convert snake_case to PascalCase
+pub fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// PascalCase 转 SCREAMING_SNAKE_CASE
+/// Convert PascalCase to SCREAMING_SNAKE_CASE
+pub fn screaming_snake_case(name: &str) -> String {
+    nft_idl::snake_case(name).to_uppercase()
+}
+
+/// 需要生成绑定的类型: 除事件以外的全部类型定义
+/// Types that get bindings: every type definition except events
+fn binding_types(idl: &Idl) -> impl Iterator<Item = &IdlTypeDef> {
+    idl.types
+        .iter()
+        .filter(|ty| !idl.events.iter().any(|event| event.name == ty.name))
+}
+
+/// 是否为只含单元变体的枚举 (编码为单个字节)
+/// Whether the type is an enum of unit variants only (encoded as a single byte)
+fn is_unit_enum(idl: &Idl, name: &str) -> bool {
+    idl.types.iter().any(|ty| {
+        ty.name == name
+            && matches!(&ty.ty, IdlTypeDefTy::Enum { variants } if variants.iter().all(|variant| variant.fields.is_none()))
+    })
+}
@@ -0,0 +1,15 @@
+use std::{fs, path::Path};
+
+// 重新生成TypeScript和C#绑定以及黄金夹具
+// Regenerate the TypeScript and C# bindings and the golden fixtures
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for (path, contents) in nft_bindgen::generate_all() {
+        let path_buf = root.join(path);
+        if let Some(parent) = path_buf.parent() {
+            fs::create_dir_all(parent).expect("创建目录失败 | failed to create the directory");
+        }
+        fs::write(&path_buf, contents).expect("写入文件失败 | failed to write the file");
+        println!("已写入 | written {}", path);
+    }
+}
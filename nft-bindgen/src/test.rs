@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use nft_contract::{
+        instruction::NFTInstruction,
+        state::{CollectionAccount, NFTAccount, ProgramAccount, COLLECTION_ACCOUNT_SIZE, NFT_ACCOUNT_SIZE},
+    };
+    use serde_json::{json, Value};
+    use solana_program::pubkey::Pubkey;
+    use std::{fs, path::Path};
+
+    // 提交的绑定和夹具与程序源码不一致时失败
+    // Fails when the committed bindings or fixtures no longer match the program sources
+    #[test]
+    fn test_generated_files_are_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for (path, contents) in generate_all() {
+            let committed = fs::read_to_string(root.join(path)).unwrap_or_default();
+            assert!(
+                committed == contents,
+                "生成文件已过期, 请运行 `cargo run -p nft-bindgen` | {} is stale, run `cargo run -p nft-bindgen`",
+                path
+            );
+        }
+    }
+
+    // 夹具字节可被程序类型解码, 且按IDL从JSON值重新编码后完全一致
+    // Fixture bytes decode with the program types and re-encode byte-for-byte from the JSON values through the IDL
+    #[test]
+    fn test_fixtures_round_trip() {
+        let idl = nft_idl::generate();
+        let fixtures = fixtures::generate(&idl);
+
+        let instructions = fixtures["instructions"].as_array().unwrap();
+        let covered: Vec<&str> = instructions.iter().map(|fixture| fixture["name"].as_str().unwrap()).collect();
+        assert!(idl.instructions.iter().all(|instruction| covered.contains(&instruction.name.as_str())));
+        for fixture in instructions {
+            let data = fixtures::unhex(fixture["hex"].as_str().unwrap()).unwrap();
+            assert!(NFTInstruction::try_from_slice(&data).is_ok());
+            let name = fixture["name"].as_str().unwrap();
+            assert_eq!(codec::encode_instruction(&idl, name, &fixture["value"]).unwrap(), data);
+        }
+
+        let accounts = fixtures["accounts"].as_array().unwrap();
+        for fixture in accounts {
+            let data = fixtures::unhex(fixture["hex"].as_str().unwrap()).unwrap();
+            let name = fixture["name"].as_str().unwrap();
+            assert_eq!(codec::encode_account(&idl, name, &fixture["value"]).unwrap(), data);
+        }
+
+        // 填充到账户大小的夹具可被程序加载, 去掉零填充后与未填充的夹具一致
+        // Fixtures padded to the account size load in the program and match the unpadded fixture once the zeros go
+        let padded_accounts = fixtures["padded_accounts"].as_array().unwrap();
+        assert_eq!(padded_accounts.len(), 2);
+        for fixture in padded_accounts {
+            let data = fixtures::unhex(fixture["hex"].as_str().unwrap()).unwrap();
+            let name = fixture["name"].as_str().unwrap();
+            let unpadded = accounts.iter().find(|account| account["name"] == fixture["name"]).unwrap();
+            assert_eq!(fixture["value"], unpadded["value"]);
+            let unpadded_len = unpadded["hex"].as_str().unwrap().len() / 2;
+            assert!(data[unpadded_len..].iter().all(|byte| *byte == 0));
+            match name {
+                "CollectionAccount" => {
+                    assert_eq!(data.len(), COLLECTION_ACCOUNT_SIZE);
+                    assert_eq!(CollectionAccount::load(&data).unwrap().try_to_vec().unwrap(), data[..unpadded_len]);
+                }
+                _ => {
+                    assert_eq!(data.len(), NFT_ACCOUNT_SIZE);
+                    assert_eq!(NFTAccount::load(&data).unwrap().try_to_vec().unwrap(), data[..unpadded_len]);
+                }
+            }
+        }
+
+        let collection = &fixtures["accounts"][0];
+        let data = fixtures::unhex(collection["hex"].as_str().unwrap()).unwrap();
+        let account = CollectionAccount::try_from_slice(&data).unwrap();
+        assert_eq!(collection["value"]["total_minted"], Value::from(account.total_minted.to_string()));
+        assert_eq!(collection["value"]["transfer_mode"], Value::from("Soulbound"));
+        assert_eq!(collection["value"]["freeze_authority"], Value::Null);
+
+        let nft = &fixtures["accounts"][1];
+        let data = fixtures::unhex(nft["hex"].as_str().unwrap()).unwrap();
+        let account = NFTAccount::try_from_slice(&data).unwrap();
+        assert_eq!(nft["value"]["delegate"], Value::from(account.delegate.unwrap().to_string()));
+        assert_eq!(nft["value"]["updated_at"], Value::from("-1"));
+
+        // 鉴别符不匹配和多余字节都会被拒绝
+        // Discriminator mismatches and trailing bytes are both rejected
+        assert!(codec::decode_account(&idl, "CollectionAccount", &data).is_err());
+        assert!(codec::decode_instruction(&idl, &[7, 0]).is_err());
+        assert_eq!(fixtures["errors"][6]["name"], Value::from("AlreadyMinted"));
+    }
+
+    // 64位整数可以是字符串或安全范围内的数字, 解码时总是字符串
+    // 64-bit integers may be strings or numbers within the safe range, and always decode to strings
+    #[test]
+    fn test_codec_64_bit_integers() {
+        let idl = nft_idl::generate();
+        let price = 9_007_199_254_740_991u64;
+        let expected = NFTInstruction::ListNFT { price, bump: 255 }.try_to_vec().unwrap();
+        for args in [json!({ "price": price, "bump": 255 }), json!({ "price": price.to_string(), "bump": 255 })] {
+            assert_eq!(codec::encode_instruction(&idl, "list_nft", &args).unwrap(), expected);
+        }
+        let (name, args) = codec::decode_instruction(&idl, &expected).unwrap();
+        assert_eq!((name.as_str(), args), ("list_nft", json!({ "price": price.to_string(), "bump": 255 })));
+
+        let operator = Pubkey::new_unique();
+        let expected = NFTInstruction::SetOperatorApproval { operator, approved: true, expires_at: Some(-5), bump: 1 }
+            .try_to_vec()
+            .unwrap();
+        for expires_at in [json!(-5), json!("-5")] {
+            let args =
+                json!({ "operator": operator.to_string(), "approved": true, "expires_at": expires_at, "bump": 1 });
+            assert_eq!(codec::encode_instruction(&idl, "set_operator_approval", &args).unwrap(), expected);
+        }
+
+        // 负数, 小数和非数字字符串被拒绝
+        // Negative numbers, fractions and non-numeric strings are rejected
+        for price in [json!(-1), json!(1.5), json!("1e3"), json!(null)] {
+            assert!(codec::encode_instruction(&idl, "list_nft", &json!({ "price": price, "bump": 255 })).is_err());
+        }
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(camel_case("mint_nft"), "mintNft");
+        assert_eq!(camel_case("metadata_uri"), "metadataUri");
+        assert_eq!(pascal_case("update_nft_metadata"), "UpdateNftMetadata");
+        assert_eq!(screaming_snake_case("NFTAccount"), "NFT_ACCOUNT");
+    }
+}
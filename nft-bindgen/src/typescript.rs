@@ -0,0 +1,449 @@
+use std::fmt::Write;
+
+use nft_idl::{Idl, IdlField, IdlType, IdlTypeDefTy};
+
+use crate::{binding_types, camel_case, is_unit_enum, screaming_snake_case};
+
+// Borsh读写器运行时, 原样写入生成的文件; 不依赖 TextEncoder 和 bigint 字面量, 以兼容 es5 目标和 jsdom
+// Borsh reader/writer runtime, copied verbatim into the generated file; avoids TextEncoder and bigint literals
+// to stay compatible with the es5 target and jsdom
+const RUNTIME: &str = r#"// 此文件由 nft-bindgen 根据程序IDL生成, 请勿手动修改 (运行 `cargo run -p nft-bindgen` 重新生成)
+// Generated by nft-bindgen from the program IDL, do not edit by hand (run `cargo run -p nft-bindgen` to regenerate)
+import { PublicKey } from '@solana/web3.js';
+
+function utf8Encode(value: string): Uint8Array {
+  const bytes: number[] = [];
+  for (let i = 0; i < value.length; i++) {
+    let code = value.charCodeAt(i);
+    if (code >= 0xd800 && code < 0xdc00 && i + 1 < value.length) {
+      const next = value.charCodeAt(i + 1);
+      if (next >= 0xdc00 && next < 0xe000) {
+        code = 0x10000 + ((code - 0xd800) << 10) + (next - 0xdc00);
+        i++;
+      }
+    }
+    if (code < 0x80) {
+      bytes.push(code);
+    } else if (code < 0x800) {
+      bytes.push(0xc0 | (code >> 6), 0x80 | (code & 0x3f));
+    } else if (code < 0x10000) {
+      bytes.push(0xe0 | (code >> 12), 0x80 | ((code >> 6) & 0x3f), 0x80 | (code & 0x3f));
+    } else {
+      bytes.push(0xf0 | (code >> 18), 0x80 | ((code >> 12) & 0x3f), 0x80 | ((code >> 6) & 0x3f), 0x80 | (code & 0x3f));
+    }
+  }
+  return Uint8Array.from(bytes);
+}
+
+function utf8Decode(bytes: Uint8Array): string {
+  let result = '';
+  let i = 0;
+  while (i < bytes.length) {
+    const byte = bytes[i++];
+    let code: number;
+    if (byte < 0x80) {
+      code = byte;
+    } else if (byte < 0xe0) {
+      code = ((byte & 0x1f) << 6) | (bytes[i++] & 0x3f);
+    } else if (byte < 0xf0) {
+      code = ((byte & 0x0f) << 12) | ((bytes[i++] & 0x3f) << 6) | (bytes[i++] & 0x3f);
+    } else {
+      code = ((byte & 0x07) << 18) | ((bytes[i++] & 0x3f) << 12) | ((bytes[i++] & 0x3f) << 6) | (bytes[i++] & 0x3f);
+    }
+    if (code >= 0x10000) {
+      code -= 0x10000;
+      result += String.fromCharCode(0xd800 + (code >> 10), 0xdc00 + (code & 0x3ff));
+    } else {
+      result += String.fromCharCode(code);
+    }
+  }
+  return result;
+}
+
+/**
+ * Borsh encoder
+ */
+export class BorshWriter {
+  private bytes: number[] = [];
+
+  u8(value: number): void {
+    this.bytes.push(value & 0xff);
+  }
+
+  u16(value: number): void {
+    this.u8(value);
+    this.u8(value >> 8);
+  }
+
+  u32(value: number): void {
+    this.u16(value & 0xffff);
+    this.u16(value >>> 16);
+  }
+
+  u64(value: bigint): void {
+    const view = new DataView(new ArrayBuffer(8));
+    view.setBigUint64(0, value, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  i64(value: bigint): void {
+    const view = new DataView(new ArrayBuffer(8));
+    view.setBigInt64(0, value, true);
+    this.raw(new Uint8Array(view.buffer));
+  }
+
+  bool(value: boolean): void {
+    this.u8(value ? 1 : 0);
+  }
+
+  string(value: string): void {
+    const encoded = utf8Encode(value);
+    this.u32(encoded.length);
+    this.raw(encoded);
+  }
+
+  pubkey(value: PublicKey): void {
+    this.raw(value.toBytes());
+  }
+
+  option<T>(value: T | null, write: (value: T) => void): void {
+    if (value === null) {
+      this.u8(0);
+    } else {
+      this.u8(1);
+      write(value);
+    }
+  }
+
+  vec<T>(items: T[], write: (item: T) => void): void {
+    this.u32(items.length);
+    items.forEach((item) => write(item));
+  }
+
+  raw(data: Uint8Array): void {
+    for (let i = 0; i < data.length; i++) {
+      this.bytes.push(data[i]);
+    }
+  }
+
+  toBytes(): Uint8Array {
+    return Uint8Array.from(this.bytes);
+  }
+}
+
+/**
+ * Borsh decoder
+ */
+export class BorshReader {
+  private offset = 0;
+
+  constructor(private readonly data: Uint8Array) {}
+
+  u8(): number {
+    return this.take(1)[0];
+  }
+
+  u16(): number {
+    const bytes = this.take(2);
+    return bytes[0] | (bytes[1] << 8);
+  }
+
+  u32(): number {
+    const bytes = this.take(4);
+    return (bytes[0] | (bytes[1] << 8) | (bytes[2] << 16) | (bytes[3] << 24)) >>> 0;
+  }
+
+  u64(): bigint {
+    return this.view(8).getBigUint64(0, true);
+  }
+
+  i64(): bigint {
+    return this.view(8).getBigInt64(0, true);
+  }
+
+  bool(): boolean {
+    const value = this.u8();
+    if (value > 1) {
+      throw new Error(`Invalid bool ${value}`);
+    }
+    return value === 1;
+  }
+
+  string(): string {
+    return utf8Decode(this.take(this.u32()));
+  }
+
+  pubkey(): PublicKey {
+    return new PublicKey(this.take(32));
+  }
+
+  option<T>(read: () => T): T | null {
+    const tag = this.u8();
+    if (tag > 1) {
+      throw new Error(`Invalid option tag ${tag}`);
+    }
+    return tag === 0 ? null : read();
+  }
+
+  vec<T>(read: () => T): T[] {
+    const length = this.u32();
+    const items: T[] = [];
+    for (let i = 0; i < length; i++) {
+      items.push(read());
+    }
+    return items;
+  }
+
+  take(length: number): Uint8Array {
+    if (this.offset + length > this.data.length) {
+      throw new Error('Unexpected end of data');
+    }
+    const bytes = this.data.slice(this.offset, this.offset + length);
+    this.offset += length;
+    return bytes;
+  }
+
+  expectEnd(): void {
+    if (this.offset !== this.data.length) {
+      throw new Error(`${this.data.length - this.offset} trailing bytes`);
+    }
+  }
+
+  private view(length: number): DataView {
+    const bytes = this.take(length);
+    return new DataView(bytes.buffer, bytes.byteOffset, length);
+  }
+}
+"#;
+
+/// 生成TypeScript绑定
+/// Generate the TypeScript bindings
+pub fn generate(idl: &Idl) -> String {
+    let mut out = RUNTIME.to_string();
+
+    for type_def in binding_types(idl) {
+        out.push('\n');
+        doc_comment(&mut out, "", &type_def.docs);
+        match &type_def.ty {
+            IdlTypeDefTy::Enum { variants } => {
+                assert!(
+                    is_unit_enum(idl, &type_def.name),
+                    "仅支持单元变体枚举 | only unit-variant enums are supported: {}",
+                    type_def.name
+                );
+                writeln!(out, "export enum {} {{", type_def.name).unwrap();
+                for (index, variant) in variants.iter().enumerate() {
+                    writeln!(out, "  {} = {},", variant.name, index).unwrap();
+                }
+                out.push_str("}\n");
+            }
+            IdlTypeDefTy::Struct { fields } => {
+                writeln!(out, "export interface {} {{", type_def.name).unwrap();
+                interface_fields(&mut out, fields);
+                out.push_str("}\n\n");
+                writeln!(
+                    out,
+                    "export function encode{name}(w: BorshWriter, value: {name}): void {{",
+                    name = type_def.name
+                )
+                .unwrap();
+                for field in fields {
+                    writeln!(out, "  {};", write_expr(idl, &field.ty, &format!("value.{}", camel_case(&field.name))))
+                        .unwrap();
+                }
+                out.push_str("}\n\n");
+                writeln!(out, "export function decode{name}(r: BorshReader): {name} {{", name = type_def.name).unwrap();
+                out.push_str("  return {\n");
+                for field in fields {
+                    writeln!(out, "    {}: {},", camel_case(&field.name), read_expr(idl, &field.ty)).unwrap();
+                }
+                out.push_str("  };\n}\n");
+            }
+        }
+    }
+
+    // 账户数据以 [AccountKey, 版本] 鉴别符开头, 账户可能有预留空间, 因此不检查剩余字节
+    // Account data starts with the [AccountKey, version] discriminator; accounts may be over-allocated, so trailing
+    // bytes are not checked
+    for account in &idl.accounts {
+        let constant = format!("{}_DISCRIMINATOR", screaming_snake_case(&account.name));
+        let discriminator: Vec<String> = account.discriminator.iter().map(u8::to_string).collect();
+        write!(
+            out,
+            r#"
+export const {constant} = [{discriminator}];
+
+export function encode{name}Data(value: {name}): Uint8Array {{
+  const w = new BorshWriter();
+  {constant}.forEach((byte) => w.u8(byte));
+  encode{name}(w, value);
+  return w.toBytes();
+}}
+
+export function decode{name}Data(data: Uint8Array): {name} {{
+  if ({constant}.some((byte, i) => data[i] !== byte)) {{
+    throw new Error('{name} discriminator mismatch');
+  }}
+  return decode{name}(new BorshReader(data.subarray({len})));
+}}
+"#,
+            constant = constant,
+            discriminator = discriminator.join(", "),
+            name = account.name,
+            len = account.discriminator.len(),
+        )
+        .unwrap();
+    }
+
+    // 指令: 每个变体一个接口, 以 kind 区分
+    // Instructions: one interface per variant, discriminated by kind
+    let variant_names: Vec<String> = idl
+        .instructions
+        .iter()
+        .map(|instruction| format!("{}Instruction", crate::pascal_case(&instruction.name)))
+        .collect();
+    for (instruction, variant_name) in idl.instructions.iter().zip(&variant_names) {
+        out.push('\n');
+        doc_comment(&mut out, "", &instruction.docs);
+        writeln!(out, "export interface {} {{", variant_name).unwrap();
+        writeln!(out, "  kind: '{}';", camel_case(&instruction.name)).unwrap();
+        interface_fields(&mut out, &instruction.args);
+        out.push_str("}\n");
+    }
+    out.push_str("\nexport type NFTInstruction =\n");
+    for (index, variant_name) in variant_names.iter().enumerate() {
+        let end = if index + 1 == variant_names.len() { ";" } else { "" };
+        writeln!(out, "  | {}{}", variant_name, end).unwrap();
+    }
+
+    out.push_str("\nexport function encodeNFTInstruction(instruction: NFTInstruction): Uint8Array {\n");
+    out.push_str("  const w = new BorshWriter();\n  switch (instruction.kind) {\n");
+    for instruction in &idl.instructions {
+        writeln!(out, "    case '{}':", camel_case(&instruction.name)).unwrap();
+        writeln!(out, "      w.u8({});", instruction.discriminator[0]).unwrap();
+        for arg in &instruction.args {
+            writeln!(
+                out,
+                "      {};",
+                write_expr(idl, &arg.ty, &format!("instruction.{}", camel_case(&arg.name)))
+            )
+            .unwrap();
+        }
+        out.push_str("      break;\n");
+    }
+    out.push_str("  }\n  return w.toBytes();\n}\n");
+
+    out.push_str("\nexport function decodeNFTInstruction(data: Uint8Array): NFTInstruction {\n");
+    out.push_str("  const r = new BorshReader(data);\n  let instruction: NFTInstruction;\n");
+    out.push_str("  const discriminator = r.u8();\n  switch (discriminator) {\n");
+    for instruction in &idl.instructions {
+        writeln!(out, "    case {}:", instruction.discriminator[0]).unwrap();
+        if instruction.args.is_empty() {
+            writeln!(out, "      instruction = {{ kind: '{}' }};", camel_case(&instruction.name)).unwrap();
+        } else {
+            out.push_str("      instruction = {\n");
+            writeln!(out, "        kind: '{}',", camel_case(&instruction.name)).unwrap();
+            for arg in &instruction.args {
+                writeln!(out, "        {}: {},", camel_case(&arg.name), read_expr(idl, &arg.ty)).unwrap();
+            }
+            out.push_str("      };\n");
+        }
+        out.push_str("      break;\n");
+    }
+    out.push_str("    default:\n      throw new Error(`Unknown NFTInstruction discriminator ${discriminator}`);\n");
+    out.push_str("  }\n  r.expectEnd();\n  return instruction;\n}\n");
+
+    // 错误: 自定义错误码 (ProgramError::Custom) 到名称和消息的映射
+    // Errors: map custom error codes (ProgramError::Custom) to names and messages
+    out.push_str("\nexport enum NFTError {\n");
+    for error in &idl.errors {
+        writeln!(out, "  {} = {},", error.name, error.code).unwrap();
+    }
+    out.push_str("}\n\nexport const NFT_ERROR_MESSAGES: Record<number, string> = {\n");
+    for error in &idl.errors {
+        writeln!(out, "  {}: {},", error.code, string_literal(&error.msg)).unwrap();
+    }
+    out.push_str(
+        r#"};
+
+export function decodeNFTError(code: number): { code: number; name: string; msg: string } | null {
+  const msg = NFT_ERROR_MESSAGES[code];
+  return msg === undefined ? null : { code, name: NFTError[code], msg };
+}
+"#,
+    );
+    out
+}
+
+fn doc_comment(out: &mut String, indent: &str, docs: &[String]) {
+    if docs.is_empty() {
+        return;
+    }
+    writeln!(out, "{}/**", indent).unwrap();
+    for line in docs {
+        writeln!(out, "{} * {}", indent, line).unwrap();
+    }
+    writeln!(out, "{} */", indent).unwrap();
+}
+
+fn interface_fields(out: &mut String, fields: &[IdlField]) {
+    for field in fields {
+        doc_comment(out, "  ", &field.docs);
+        writeln!(out, "  {}: {};", camel_case(&field.name), type_name(&field.ty)).unwrap();
+    }
+}
+
+fn type_name(ty: &IdlType) -> String {
+    match ty {
+        IdlType::Primitive(name) => match name.as_str() {
+            "u8" | "u16" | "u32" | "i8" | "i16" | "i32" => "number".to_string(),
+            "u64" | "i64" => "bigint".to_string(),
+            "bool" => "boolean".to_string(),
+            "string" => "string".to_string(),
+            "pubkey" => "PublicKey".to_string(),
+            other => panic!("不支持的类型 | unsupported type {}", other),
+        },
+        IdlType::Option { option } => format!("{} | null", type_name(option)),
+        IdlType::Vec { vec } | IdlType::Array { array: (vec, _) } => format!("{}[]", type_name(vec)),
+        IdlType::Defined { defined } => defined.name.clone(),
+    }
+}
+
+fn write_expr(idl: &Idl, ty: &IdlType, value: &str) -> String {
+    match ty {
+        IdlType::Primitive(name) => format!("w.{}({})", primitive_method(name), value),
+        IdlType::Option { option } => format!("w.option({}, (value) => {})", value, write_expr(idl, option, "value")),
+        IdlType::Vec { vec } => format!("w.vec({}, (item) => {})", value, write_expr(idl, vec, "item")),
+        IdlType::Array { array: (item, _) } => {
+            format!("{}.forEach((item) => {})", value, write_expr(idl, item, "item"))
+        }
+        IdlType::Defined { defined } if is_unit_enum(idl, &defined.name) => format!("w.u8({})", value),
+        IdlType::Defined { defined } => format!("encode{}(w, {})", defined.name, value),
+    }
+}
+
+fn read_expr(idl: &Idl, ty: &IdlType) -> String {
+    match ty {
+        IdlType::Primitive(name) => format!("r.{}()", primitive_method(name)),
+        IdlType::Option { option } => format!("r.option(() => {})", read_expr(idl, option)),
+        IdlType::Vec { vec } => format!("r.vec(() => {})", read_expr(idl, vec)),
+        IdlType::Array { array: (item, len) } => {
+            format!("Array.from({{ length: {} }}, () => {})", len, read_expr(idl, item))
+        }
+        IdlType::Defined { defined } if is_unit_enum(idl, &defined.name) => {
+            format!("r.u8() as {}", defined.name)
+        }
+        IdlType::Defined { defined } => format!("decode{}(r)", defined.name),
+    }
+}
+
+fn primitive_method(name: &str) -> &str {
+    match name {
+        "u8" | "u16" | "u32" | "u64" | "i64" | "bool" | "string" | "pubkey" => name,
+        other => panic!("不支持的类型 | unsupported type {}", other),
+    }
+}
+
+fn string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
@@ -0,0 +1,458 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use nft_contract::{
+    instruction,
+    state::{find_nft_address, CollectionAccount, SerialMode},
+};
+use nft_decoder::DecodedError;
+use serde_json::{json, Map, Value};
+use solana_sdk::{
+    instruction::Instruction, message::Message, packet::PACKET_DATA_SIZE, pubkey::Pubkey, signature::Signer,
+    transaction::TransactionError,
+};
+
+use crate::{
+    decode_transaction_error, describe_transaction_error, execute, fetch, parse_pubkey, CliError, Client, Config,
+};
+
+/// 清单中的一行
+/// One row of a manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestRow {
+    /// 序列号
+    /// Serial number
+    pub serial_number: u64,
+
+    /// 元数据URI
+    /// Metadata URI
+    pub metadata_uri: String,
+
+    /// 接收者, 为空时归签名者所有
+    /// Recipient, owned by the signer when empty
+    pub recipient: Option<Pubkey>,
+}
+
+/// 某一行的铸造失败
+/// Mint failure of a single row
+#[derive(Debug, Clone)]
+pub struct RowFailure {
+    /// 清单中的行号 (从1开始, 不含表头)
+    /// Row number in the manifest (1-based, excluding the header)
+    pub row: usize,
+
+    /// 序列号
+    /// Serial number
+    pub serial_number: u64,
+
+    /// 程序返回的 `NFTError`, 非程序错误时为空
+    /// `NFTError` returned by the program, empty for non-program errors
+    pub error: Option<DecodedError>,
+
+    /// 可读的错误描述
+    /// Readable error description
+    pub message: String,
+}
+
+/// 批量铸造结果
+/// Bulk mint report
+#[derive(Debug, Clone, Default)]
+pub struct BulkReport {
+    /// 本次铸造成功的序列号和交易签名
+    /// Serial numbers minted in this run and their transaction signatures
+    pub minted: Vec<(u64, String)>,
+
+    /// 模拟模式下模拟成功的序列号
+    /// Serial numbers simulated successfully in dry-run mode
+    pub simulated: Vec<u64>,
+
+    /// 状态文件中已记录为铸造完成而跳过的序列号
+    /// Serial numbers skipped because the state file already records them as minted
+    pub skipped: Vec<u64>,
+
+    /// 失败的行
+    /// Failed rows
+    pub failed: Vec<RowFailure>,
+
+    /// 顺序模式的集合在失败后 (或模拟完第一批后) 停止, 未尝试的行数
+    /// Rows not attempted because a sequential collection stops at the first failure (or after simulating the first
+    /// batch)
+    pub remaining: usize,
+
+    /// 发送 (或模拟) 的交易数
+    /// Transactions sent (or simulated)
+    pub transactions: usize,
+}
+
+impl BulkReport {
+    /// 转为命令行输出的JSON
+    /// Convert to the JSON printed by the CLI
+    pub fn to_json(&self) -> Value {
+        let minted: Vec<Value> = self
+            .minted
+            .iter()
+            .map(|(serial_number, signature)| json!({ "serial_number": serial_number, "signature": signature }))
+            .collect();
+        let failed: Vec<Value> = self
+            .failed
+            .iter()
+            .map(|failure| {
+                json!({
+                    "row": failure.row,
+                    "serial_number": failure.serial_number,
+                    "error": failure.error.and_then(|decoded| decoded.error).map(|error| format!("{:?}", error)),
+                    "message": failure.message,
+                })
+            })
+            .collect();
+        json!({
+            "transactions": self.transactions,
+            "minted": minted,
+            "simulated": self.simulated,
+            "skipped": self.skipped,
+            "failed": failed,
+            "remaining": self.remaining,
+        })
+    }
+}
+
+/// 读取清单, 按扩展名区分 `.json` 和 CSV
+/// Read a manifest, `.json` by extension and CSV otherwise
+pub fn read_manifest(path: &Path) -> Result<Vec<ManifestRow>, CliError> {
+    let contents =
+        fs::read_to_string(path).map_err(|error| CliError::File(format!("{}: {}", path.display(), error)))?;
+    if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json")) {
+        parse_manifest_json(&contents)
+    } else {
+        parse_manifest_csv(&contents)
+    }
+}
+
+/// 解析带表头 `serial,metadata_uri,recipient` 的CSV清单, recipient 列可省略或留空
+/// Parse a CSV manifest with a `serial,metadata_uri,recipient` header, the recipient column may be omitted or empty
+pub fn parse_manifest_csv(contents: &str) -> Result<Vec<ManifestRow>, CliError> {
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header = csv_fields(lines.next().unwrap_or_default(), 0)?;
+    let column = |name: &str| header.iter().position(|field| field.trim() == name);
+    let (Some(serial), Some(uri)) = (column("serial"), column("metadata_uri")) else {
+        return Err(invalid_manifest(
+            0,
+            "表头需要 serial 和 metadata_uri 列 | header needs serial and metadata_uri columns",
+        ));
+    };
+    let recipient = column("recipient");
+
+    lines
+        .enumerate()
+        .map(|(index, line)| {
+            let row = index + 1;
+            let fields = csv_fields(line, row)?;
+            let field = |column: usize| fields.get(column).map(|field| field.trim()).unwrap_or_default();
+            manifest_row(row, field(serial), field(uri), recipient.map(field).filter(|value| !value.is_empty()))
+        })
+        .collect()
+}
+
+/// 解析JSON清单: `[{"serial": 1, "metadata_uri": "..", "recipient": ".."}]`, 序列号可以是数字或字符串
+/// Parse a JSON manifest: `[{"serial": 1, "metadata_uri": "..", "recipient": ".."}]`, serials may be numbers or strings
+pub fn parse_manifest_json(contents: &str) -> Result<Vec<ManifestRow>, CliError> {
+    let value: Value = serde_json::from_str(contents)
+        .map_err(|error| invalid_manifest(0, &format!("无效的JSON | invalid JSON: {}", error)))?;
+    let rows = value.as_array().ok_or_else(|| invalid_manifest(0, "清单应为数组 | manifest must be an array"))?;
+    rows.iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let row = index + 1;
+            let serial = match &entry["serial"] {
+                Value::Number(number) => number.to_string(),
+                Value::String(serial) => serial.clone(),
+                _ => String::new(),
+            };
+            let uri = entry["metadata_uri"].as_str().unwrap_or_default();
+            manifest_row(row, &serial, uri, entry["recipient"].as_str())
+        })
+        .collect()
+}
+
+/// 批量铸造清单中的NFT
+/// Bulk mint the NFTs of a manifest
+///
+/// 铸造指令按交易大小上限尽量多地打包; 每笔交易发送前后都会把进度写入 `state_path`,
+/// 重新运行时跳过已完成的序列号, 并按链上账户核对崩溃时未确认的批次, 因此不会重复铸造。
+/// 交易失败时记录出错的行及其 `NFTError`, 其余行重新打包后继续。
+///
+/// Mint instructions are packed into transactions up to the size limit; progress is written to `state_path` before and
+/// after every transaction, so a rerun skips finished serials and checks the batch that was in flight during a crash
+/// against the chain, never minting twice. When a transaction fails the offending row is recorded with its `NFTError`
+/// and the remaining rows are repacked.
+pub async fn bulk_mint(
+    client: &mut Client,
+    config: &Config,
+    collection: &Pubkey,
+    rows: &[ManifestRow],
+    state_path: &Path,
+) -> Result<BulkReport, CliError> {
+    let collection_account = fetch::<CollectionAccount>(client, config, collection).await?;
+    let mut state = State::load(state_path, collection)?;
+    reconcile(client, config, collection, &mut state).await?;
+
+    let mut report = BulkReport::default();
+    let mut queue = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        if state.minted.contains_key(&row.serial_number) {
+            report.skipped.push(row.serial_number);
+        } else {
+            queue.push((index + 1, row));
+        }
+    }
+
+    let sequential = collection_account.serial_mode == SerialMode::Sequential;
+    if sequential {
+        let first = collection_account.total_minted + 1;
+        for (offset, (row, entry)) in queue.iter().enumerate() {
+            if entry.serial_number == first + offset as u64 {
+                continue;
+            }
+            return Err(invalid_manifest(
+                *row,
+                &format!(
+                    "顺序模式集合需要从 {} 开始的连续序列号, 实际为 {} | sequential collections need consecutive serials from {}, got {}",
+                    first, entry.serial_number, first, entry.serial_number
+                ),
+            ));
+        }
+    }
+
+    let signer = config.signer.pubkey();
+    let instructions: Vec<Instruction> = queue
+        .iter()
+        .map(|(_, row)| {
+            instruction::mint_nft(
+                &config.program_id,
+                &signer,
+                collection,
+                &signer,
+                row.serial_number,
+                &row.metadata_uri,
+                row.recipient,
+            )
+        })
+        .collect();
+
+    // 待处理的 (清单行, 指令) 队列, 失败的交易只移除出错的行
+    // Pending (manifest row, instruction) queue, a failed transaction only removes the offending row
+    let mut pending: Vec<((usize, &ManifestRow), Instruction)> = queue.into_iter().zip(instructions).collect();
+    while !pending.is_empty() {
+        let size = batch_size(pending.iter().map(|(_, instruction)| instruction), &signer);
+        let batch: Vec<Instruction> = pending[..size].iter().map(|(_, instruction)| instruction.clone()).collect();
+        let serials: Vec<u64> = pending[..size].iter().map(|((_, row), _)| row.serial_number).collect();
+
+        if !config.dry_run {
+            state.pending = serials.clone();
+            state.save(state_path)?;
+        }
+        report.transactions += 1;
+        let result = execute(client, config, &batch).await;
+        if !config.dry_run {
+            state.pending.clear();
+        }
+
+        match result {
+            Ok(result) => {
+                pending.drain(..size);
+                if config.dry_run {
+                    report.simulated.extend(serials);
+                    // 模拟不改变链上状态, 顺序模式的后续批次依赖前面的铸造而无法模拟, 只模拟第一批;
+                    // 调用方指定序列号的批次相互独立, 全部模拟
+                    // Simulation leaves the chain untouched, so later sequential batches, which depend on the earlier
+                    // mints, cannot be simulated and only the first batch is; caller-assigned batches are independent
+                    // and all of them are simulated
+                    if sequential {
+                        report.remaining = pending.len();
+                        break;
+                    }
+                    continue;
+                }
+                let signature = result["signature"].as_str().unwrap_or_default().to_string();
+                for serial in serials {
+                    state.minted.insert(serial, signature.clone());
+                    report.minted.push((serial, signature.clone()));
+                }
+            }
+            Err(CliError::Transaction { error, .. }) => {
+                let decoded = decode_transaction_error(&error);
+                let message = describe_transaction_error(&error);
+                // 能定位到指令时只记录该行失败, 否则整批失败
+                // Fail only the offending row when the instruction is known, otherwise the whole batch
+                let failing = match &error {
+                    TransactionError::InstructionError(index, _) if (*index as usize) < size => {
+                        *index as usize..*index as usize + 1
+                    }
+                    _ => 0..size,
+                };
+                for ((row, entry), _) in pending.drain(failing) {
+                    report.failed.push(RowFailure {
+                        row,
+                        serial_number: entry.serial_number,
+                        error: decoded.map(|(_, decoded)| decoded),
+                        message: message.clone(),
+                    });
+                }
+                if sequential {
+                    report.remaining = pending.len();
+                    pending.clear();
+                }
+            }
+            // 传输错误时交易可能已上链, 状态文件保留待确认的批次, 下次运行时按链上账户核对
+            // The transaction may have landed despite a transport error, so the state file keeps the batch pending
+            // for the next run to check against the chain
+            Err(error) => return Err(error),
+        }
+        if !config.dry_run {
+            state.save(state_path)?;
+        }
+    }
+    Ok(report)
+}
+
+// 从队列开头起, 一笔交易能容纳的最多指令数 (至少一条)
+// The most instructions from the front of the queue that fit in one transaction (at least one)
+fn batch_size<'a>(instructions: impl Iterator<Item = &'a Instruction>, payer: &Pubkey) -> usize {
+    let mut batch = Vec::new();
+    for instruction in instructions {
+        batch.push(instruction.clone());
+        if transaction_size(&batch, payer) > PACKET_DATA_SIZE {
+            return (batch.len() - 1).max(1);
+        }
+    }
+    batch.len()
+}
+
+/// 签名后的交易序列化大小
+/// Serialized size of the signed transaction
+pub fn transaction_size(instructions: &[Instruction], payer: &Pubkey) -> usize {
+    let message = Message::new(instructions, Some(payer));
+    let signatures = message.header.num_required_signatures as usize;
+    // 签名数的短向量长度前缀 (少于128个签名时为1字节) 加上签名和消息
+    // Short-vec length prefix of the signatures (1 byte below 128 signatures) plus the signatures and the message
+    1 + signatures * 64 + message.serialize().len()
+}
+
+// 核对崩溃时未确认的批次: NFT账户已存在的序列号视为已铸造
+// Check the batch that was in flight during a crash: serials whose NFT account exists count as minted
+async fn reconcile(
+    client: &mut Client,
+    config: &Config,
+    collection: &Pubkey,
+    state: &mut State,
+) -> Result<(), CliError> {
+    if state.pending.is_empty() {
+        return Ok(());
+    }
+    let addresses: Vec<Pubkey> =
+        state.pending.iter().map(|serial| find_nft_address(&config.program_id, collection, *serial).0).collect();
+    let accounts = client.get_accounts(&addresses).await?;
+    for (serial, account) in state.pending.iter().zip(accounts) {
+        if account.is_some_and(|account| account.owner == config.program_id) {
+            state.minted.insert(*serial, String::new());
+        }
+    }
+    state.pending.clear();
+    Ok(())
+}
+
+// 本地进度状态文件
+// Local progress state file
+#[derive(Debug, Default)]
+struct State {
+    collection: Pubkey,
+    minted: BTreeMap<u64, String>,
+    pending: Vec<u64>,
+}
+
+impl State {
+    // 读取状态文件, 不存在时从空状态开始; 拒绝属于其他集合的状态文件
+    // Read the state file, starting empty when it does not exist; rejects a state file of another collection
+    fn load(path: &Path, collection: &Pubkey) -> Result<Self, CliError> {
+        let mut state = State { collection: *collection, ..State::default() };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(state),
+            Err(error) => return Err(CliError::File(format!("{}: {}", path.display(), error))),
+        };
+        let invalid = || CliError::File(format!("无效的状态文件 | invalid state file: {}", path.display()));
+        let value: Value = serde_json::from_str(&contents).map_err(|_| invalid())?;
+        if value["collection"].as_str() != Some(collection.to_string().as_str()) {
+            return Err(CliError::File(format!(
+                "状态文件属于其他集合 | state file {} belongs to another collection",
+                path.display()
+            )));
+        }
+        for (serial, signature) in value["minted"].as_object().ok_or_else(invalid)? {
+            state
+                .minted
+                .insert(serial.parse().map_err(|_| invalid())?, signature.as_str().unwrap_or_default().to_string());
+        }
+        for serial in value["pending"].as_array().ok_or_else(invalid)? {
+            state.pending.push(serial.as_u64().ok_or_else(invalid)?);
+        }
+        Ok(state)
+    }
+
+    // 先写临时文件再重命名, 崩溃时不会留下半个状态文件
+    // Write a temporary file and rename it, so a crash never leaves a half-written state file
+    fn save(&self, path: &Path) -> Result<(), CliError> {
+        let minted: Map<String, Value> = self
+            .minted
+            .iter()
+            .map(|(serial, signature)| (serial.to_string(), Value::from(signature.as_str())))
+            .collect();
+        let value = json!({ "collection": self.collection.to_string(), "minted": minted, "pending": self.pending });
+        let temporary = path.with_extension("tmp");
+        let file_error = |error: std::io::Error| CliError::File(format!("{}: {}", path.display(), error));
+        fs::write(&temporary, serde_json::to_string_pretty(&value).unwrap()).map_err(file_error)?;
+        fs::rename(&temporary, path).map_err(file_error)
+    }
+}
+
+fn manifest_row(row: usize, serial: &str, uri: &str, recipient: Option<&str>) -> Result<ManifestRow, CliError> {
+    let serial_number =
+        serial.parse().map_err(|_| invalid_manifest(row, &format!("无效的序列号 | invalid serial: {:?}", serial)))?;
+    if uri.is_empty() {
+        return Err(invalid_manifest(row, "缺少 metadata_uri | missing metadata_uri"));
+    }
+    let recipient =
+        recipient.map(parse_pubkey).transpose().map_err(|error| invalid_manifest(row, &error.to_string()))?;
+    Ok(ManifestRow { serial_number, metadata_uri: uri.to_string(), recipient })
+}
+
+fn invalid_manifest(row: usize, message: &str) -> CliError {
+    CliError::InvalidManifest(format!("第 {} 行 | row {}: {}", row, row, message))
+}
+
+// 拆分一行CSV, 支持双引号字段和 "" 转义
+// Split one CSV line, supporting double-quoted fields and "" escapes
+fn csv_fields(line: &str, row: usize) -> Result<Vec<String>, CliError> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().unwrap();
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            (',', false) => fields.push(String::new()),
+            (c, _) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(invalid_manifest(row, "引号未闭合 | unterminated quote"));
+    }
+    Ok(fields)
+}
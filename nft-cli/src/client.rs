@@ -0,0 +1,156 @@
+use solana_banks_client::{BanksClient, BanksClientError};
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::client_error::Error as ClientError;
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{Transaction, TransactionError},
+};
+
+use crate::CliError;
+
+// RPC 单次 getMultipleAccounts 请求的账户上限
+// Account limit of a single RPC getMultipleAccounts request
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// 链上访问后端: RPC 节点, 或 solana-program-test 的 BanksClient
+/// Chain access backend: an RPC node, or solana-program-test's BanksClient
+pub enum Client {
+    /// JSON RPC 节点
+    /// JSON RPC node
+    Rpc(RpcClient),
+
+    /// 银行客户端 (测试或本地验证器)
+    /// Banks client (tests or a local validator)
+    Banks(BanksClient),
+}
+
+/// 交易模拟结果
+/// Transaction simulation result
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// 模拟执行结果
+    /// Simulated execution result
+    pub result: Result<(), TransactionError>,
+
+    /// 程序日志
+    /// Program logs
+    pub logs: Vec<String>,
+
+    /// 消耗的计算单元
+    /// Compute units consumed
+    pub units_consumed: u64,
+}
+
+impl Client {
+    /// 连接 RPC 节点, 使用 confirmed 承诺级别
+    /// Connect to an RPC node with the confirmed commitment level
+    pub fn rpc(url: &str) -> Self {
+        Client::Rpc(RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()))
+    }
+
+    /// 读取账户, 不存在时返回 `None`
+    /// Fetch an account, returning `None` when it does not exist
+    pub async fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>, CliError> {
+        match self {
+            Client::Rpc(client) => Ok(client.get_account_with_commitment(address, client.commitment()).await?.value),
+            Client::Banks(client) => Ok(client.get_account(*address).await?),
+        }
+    }
+
+    /// 批量读取账户, 结果与地址一一对应
+    /// Fetch accounts in bulk, the results line up with the addresses
+    pub async fn get_accounts(&mut self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>, CliError> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        match self {
+            Client::Rpc(client) => {
+                for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+                    let commitment = client.commitment();
+                    accounts.extend(client.get_multiple_accounts_with_commitment(chunk, commitment).await?.value);
+                }
+            }
+            Client::Banks(client) => {
+                for address in addresses {
+                    accounts.push(client.get_account(*address).await?);
+                }
+            }
+        }
+        Ok(accounts)
+    }
+
+    /// 获取最新区块哈希
+    /// Get the latest blockhash
+    pub async fn get_latest_blockhash(&mut self) -> Result<Hash, CliError> {
+        match self {
+            Client::Rpc(client) => Ok(client.get_latest_blockhash().await?),
+            Client::Banks(client) => Ok(client.get_latest_blockhash().await?),
+        }
+    }
+
+    /// 模拟交易, 不改变链上状态
+    /// Simulate a transaction without changing on-chain state
+    pub async fn simulate(&mut self, transaction: &Transaction) -> Result<Simulation, CliError> {
+        match self {
+            Client::Rpc(client) => {
+                let simulation = client.simulate_transaction(transaction).await?.value;
+                Ok(Simulation {
+                    result: simulation.err.map_or(Ok(()), Err),
+                    logs: simulation.logs.unwrap_or_default(),
+                    units_consumed: simulation.units_consumed.unwrap_or_default(),
+                })
+            }
+            Client::Banks(client) => {
+                let simulation = client.simulate_transaction(transaction.clone()).await?;
+                let (logs, units_consumed) = simulation
+                    .simulation_details
+                    .map(|details| (details.logs, details.units_consumed))
+                    .unwrap_or_default();
+                Ok(Simulation { result: simulation.result.unwrap_or(Ok(())), logs, units_consumed })
+            }
+        }
+    }
+
+    /// 发送交易并等待确认, 交易失败时返回 `CliError::Transaction`
+    /// Send a transaction and wait for confirmation, returning `CliError::Transaction` when it fails
+    pub async fn send(&mut self, transaction: Transaction) -> Result<Signature, CliError> {
+        let signature = transaction.signatures[0];
+        match self {
+            Client::Rpc(client) => match client.send_and_confirm_transaction(&transaction).await {
+                Ok(signature) => Ok(signature),
+                Err(error) => match error.get_transaction_error() {
+                    Some(error) => Err(CliError::Transaction { error, logs: Vec::new() }),
+                    None => Err(error.into()),
+                },
+            },
+            Client::Banks(client) => {
+                let processed = client.process_transaction_with_metadata(transaction).await?;
+                match processed.result {
+                    Ok(()) => Ok(signature),
+                    Err(error) => Err(CliError::Transaction {
+                        error,
+                        logs: processed.metadata.map(|metadata| metadata.log_messages).unwrap_or_default(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+impl From<BanksClientError> for CliError {
+    fn from(error: BanksClientError) -> Self {
+        match error {
+            BanksClientError::TransactionError(error) => CliError::Transaction { error, logs: Vec::new() },
+            BanksClientError::SimulationError { err, logs, .. } => CliError::Transaction { error: err, logs },
+            error => CliError::Client(error.to_string()),
+        }
+    }
+}
+
+impl From<ClientError> for CliError {
+    fn from(error: ClientError) -> Self {
+        CliError::Client(error.to_string())
+    }
+}
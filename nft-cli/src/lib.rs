@@ -0,0 +1,386 @@
+use nft_contract::{
+    instruction,
+    state::{
+        find_collection_address, find_listing_address, find_nft_address, is_uninitialized, CollectionAccount, Creator,
+        Listing, NFTAccount, ProgramAccount, SerialMode, TransferMode,
+    },
+};
+use nft_decoder::DecodedError;
+use serde_json::{json, Value};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use thiserror::Error;
+
+pub mod bulk;
+pub mod client;
+
+#[cfg(test)]
+mod test;
+
+pub use client::{Client, Simulation};
+
+// `list` 每页扫描的序列号数量, 与 RPC getMultipleAccounts 的账户上限相同
+// Serial numbers scanned per `list` page, the same as the account limit of RPC getMultipleAccounts
+const LIST_PAGE_SIZE: u64 = 100;
+
+/// 命令行工具错误
+/// Command-line tool errors
+#[derive(Error, Debug)]
+pub enum CliError {
+    /// 参数无效
+    /// Invalid argument
+    #[error("参数无效 | Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    /// 账户不存在
+    /// Account not found
+    #[error("账户不存在 | Account not found: {0}")]
+    AccountNotFound(Pubkey),
+
+    /// 账户数据无法解码
+    /// Account data cannot be decoded
+    #[error("账户无法解码 | Account cannot be decoded: {0}")]
+    InvalidAccount(String),
+
+    /// 交易执行失败, 带程序日志
+    /// Transaction execution failed, with the program logs
+    #[error("交易失败 | Transaction failed: {}", describe_transaction_error(.error))]
+    Transaction { error: TransactionError, logs: Vec<String> },
+
+    /// 清单无效
+    /// Invalid manifest
+    #[error("清单无效 | Invalid manifest: {0}")]
+    InvalidManifest(String),
+
+    /// 文件读写错误
+    /// File read or write error
+    #[error("文件错误 | File error: {0}")]
+    File(String),
+
+    /// RPC 或 BanksClient 传输错误
+    /// RPC or BanksClient transport error
+    #[error("客户端错误 | Client error: {0}")]
+    Client(String),
+}
+
+/// 命令执行配置
+/// Command execution configuration
+pub struct Config {
+    /// NFT 程序地址
+    /// NFT program address
+    pub program_id: Pubkey,
+
+    /// 签名者, 同时是付款人、集合权限和NFT所有者
+    /// Signer, also the payer, collection authority and NFT owner
+    pub signer: Keypair,
+
+    /// 只模拟交易, 不发送
+    /// Only simulate transactions without sending them
+    pub dry_run: bool,
+}
+
+/// `create-collection` 的参数
+/// Arguments of `create-collection`
+#[derive(Debug, Clone)]
+pub struct CollectionArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub royalty_percentage: u8,
+    pub is_mutable: bool,
+    pub transfer_mode: TransferMode,
+    pub creators: Vec<Creator>,
+    pub max_supply: Option<u64>,
+    pub serial_mode: SerialMode,
+}
+
+/// 将交易错误中的程序自定义错误解码为 `NFTError`, 返回出错的指令位置
+/// Decode the program custom error in a transaction error into an `NFTError`, returning the failing instruction index
+pub fn decode_transaction_error(error: &TransactionError) -> Option<(u8, DecodedError)> {
+    match error {
+        TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+            Some((*index, DecodedError::from_code(*code)))
+        }
+        _ => None,
+    }
+}
+
+/// 交易错误的可读描述, 程序错误显示为 `NFTError`
+/// Readable description of a transaction error, program errors shown as `NFTError`
+pub fn describe_transaction_error(error: &TransactionError) -> String {
+    match decode_transaction_error(error) {
+        Some((index, decoded)) => format!("指令 | instruction {}: {}", index, decoded),
+        None => error.to_string(),
+    }
+}
+
+/// 签名并发送指令; 模拟模式下只模拟, 返回日志和计算单元
+/// Sign and send instructions; in dry-run mode only simulate, returning the logs and compute units
+pub async fn execute(client: &mut Client, config: &Config, instructions: &[Instruction]) -> Result<Value, CliError> {
+    let blockhash = client.get_latest_blockhash().await?;
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(&config.signer.pubkey()), &[&config.signer], blockhash);
+    if config.dry_run {
+        let simulation = client.simulate(&transaction).await?;
+        if let Err(error) = simulation.result {
+            return Err(CliError::Transaction { error, logs: simulation.logs });
+        }
+        return Ok(json!({
+            "dry_run": true,
+            "units_consumed": simulation.units_consumed,
+            "logs": simulation.logs,
+        }));
+    }
+    let signature = client.send(transaction).await?;
+    Ok(json!({ "signature": signature.to_string() }))
+}
+
+/// 创建集合, 集合地址由签名者和名称推导
+/// Create a collection, its address is derived from the signer and the name
+pub async fn create_collection(client: &mut Client, config: &Config, args: CollectionArgs) -> Result<Value, CliError> {
+    let authority = config.signer.pubkey();
+    let (collection, _) = find_collection_address(&config.program_id, &authority, &args.name);
+    let instruction = instruction::initialize_collection(
+        &config.program_id,
+        &authority,
+        &authority,
+        &args.name,
+        &args.symbol,
+        &args.uri,
+        args.royalty_percentage,
+        args.is_mutable,
+        args.transfer_mode,
+        args.creators,
+        args.max_supply,
+        args.serial_mode,
+    );
+    let result = execute(client, config, &[instruction]).await?;
+    Ok(merge(json!({ "collection": collection.to_string() }), result))
+}
+
+/// 铸造NFT; 顺序模式的集合可省略序列号, 由集合的 total_minted + 1 得出
+/// Mint an NFT; sequential collections may omit the serial number, it is taken from the collection's total_minted + 1
+pub async fn mint(
+    client: &mut Client,
+    config: &Config,
+    collection: &Pubkey,
+    serial_number: Option<u64>,
+    metadata_uri: &str,
+    recipient: Option<Pubkey>,
+) -> Result<Value, CliError> {
+    let account = fetch::<CollectionAccount>(client, config, collection).await?;
+    let serial_number = match (account.serial_mode, serial_number) {
+        (SerialMode::Sequential, requested) => {
+            let next = account.total_minted + 1;
+            if requested.is_some_and(|requested| requested != next) {
+                return Err(CliError::InvalidArgument(format!(
+                    "顺序模式集合的下一个序列号是 {} | the next serial of a sequential collection is {}",
+                    next, next
+                )));
+            }
+            next
+        }
+        (SerialMode::CallerAssigned, Some(requested)) => requested,
+        (SerialMode::CallerAssigned, None) => {
+            return Err(CliError::InvalidArgument(
+                "该集合需要指定 --serial | this collection requires --serial".to_string(),
+            ))
+        }
+    };
+    let (nft, _) = find_nft_address(&config.program_id, collection, serial_number);
+    let signer = config.signer.pubkey();
+    let instruction =
+        instruction::mint_nft(&config.program_id, &signer, collection, &signer, serial_number, metadata_uri, recipient);
+    let result = execute(client, config, &[instruction]).await?;
+    Ok(merge(json!({ "nft": nft.to_string(), "serial_number": serial_number }), result))
+}
+
+/// 转移NFT
+/// Transfer an NFT
+pub async fn transfer(
+    client: &mut Client,
+    config: &Config,
+    nft: &Pubkey,
+    new_owner: &Pubkey,
+) -> Result<Value, CliError> {
+    let account = fetch::<NFTAccount>(client, config, nft).await?;
+    let instruction = instruction::transfer_nft(
+        &config.program_id,
+        nft,
+        &account.collection,
+        &config.signer.pubkey(),
+        new_owner,
+        &listing_seller(client, config, nft, &account).await?,
+        None,
+    );
+    let result = execute(client, config, &[instruction]).await?;
+    Ok(merge(json!({ "nft": nft.to_string(), "new_owner": new_owner.to_string() }), result))
+}
+
+/// 销毁NFT, 租金退还给NFT所有者
+/// Burn an NFT, the rent is refunded to the NFT's owner
+pub async fn burn(client: &mut Client, config: &Config, nft: &Pubkey) -> Result<Value, CliError> {
+    let account = fetch::<NFTAccount>(client, config, nft).await?;
+    let seller = listing_seller(client, config, nft, &account).await?;
+    let instruction = instruction::burn_nft(
+        &config.program_id,
+        nft,
+        &account.collection,
+        &config.signer.pubkey(),
+        &account.owner,
+        &seller,
+        None,
+    );
+    let result = execute(client, config, &[instruction]).await?;
+    Ok(merge(json!({ "nft": nft.to_string() }), result))
+}
+
+/// 更新NFT元数据URI
+/// Update an NFT's metadata URI
+pub async fn update_metadata(client: &mut Client, config: &Config, nft: &Pubkey, uri: &str) -> Result<Value, CliError> {
+    let account = fetch::<NFTAccount>(client, config, nft).await?;
+    let instruction =
+        instruction::update_nft_metadata(&config.program_id, nft, &config.signer.pubkey(), &account.collection, uri);
+    let result = execute(client, config, &[instruction]).await?;
+    Ok(merge(json!({ "nft": nft.to_string(), "metadata_uri": uri }), result))
+}
+
+/// 更新集合元数据URI
+/// Update a collection's metadata URI
+pub async fn update_collection(
+    client: &mut Client,
+    config: &Config,
+    collection: &Pubkey,
+    uri: &str,
+) -> Result<Value, CliError> {
+    let instruction =
+        instruction::update_collection_metadata(&config.program_id, collection, &config.signer.pubkey(), uri);
+    let result = execute(client, config, &[instruction]).await?;
+    Ok(merge(json!({ "collection": collection.to_string(), "uri": uri }), result))
+}
+
+/// 显示任意程序账户, 返回 `{ address, type, data }`
+/// Show any program account, returning `{ address, type, data }`
+pub async fn show(client: &mut Client, config: &Config, address: &Pubkey) -> Result<Value, CliError> {
+    let data = fetch_data(client, config, address).await?;
+    Ok(merge(json!({ "address": address.to_string() }), decode_account(&data)?))
+}
+
+/// 按序列号扫描集合中的NFT (默认 1..=total_minted), 跳过不存在的序列号和已销毁NFT的墓碑账户
+/// List a collection's NFTs by scanning serial numbers (1..=total_minted by default), skipping missing serials and the
+/// tombstones of burned NFTs
+pub async fn list(
+    client: &mut Client,
+    config: &Config,
+    collection: &Pubkey,
+    start: u64,
+    end: Option<u64>,
+) -> Result<Value, CliError> {
+    let account = fetch::<CollectionAccount>(client, config, collection).await?;
+    let end = end.unwrap_or(account.total_minted);
+
+    // 按页读取, 每页一次 getMultipleAccounts 请求, 不会一次生成整个范围的地址
+    // Fetch page by page, one getMultipleAccounts request per page, never building addresses for the whole range
+    let mut nfts = Vec::new();
+    for page_start in (start..=end).step_by(LIST_PAGE_SIZE as usize) {
+        let page_end = end.min(page_start.saturating_add(LIST_PAGE_SIZE - 1));
+        let addresses: Vec<Pubkey> =
+            (page_start..=page_end).map(|serial| find_nft_address(&config.program_id, collection, serial).0).collect();
+        for (address, account) in addresses.iter().zip(client.get_accounts(&addresses).await?) {
+            if let Some(account) =
+                account.filter(|account| account.owner == config.program_id && !is_uninitialized(&account.data))
+            {
+                let decoded = decode_account(&account.data)?;
+                nfts.push(json!({ "address": address.to_string(), "data": decoded["data"] }));
+            }
+        }
+    }
+    Ok(json!({ "collection": collection.to_string(), "nfts": nfts }))
+}
+
+/// 按程序的加载规则解码账户数据, 返回 `{ type, data }`
+/// Decode account data with the program's loading rules, returning `{ type, data }`
+pub fn decode_account(data: &[u8]) -> Result<Value, CliError> {
+    nft_decoder::decode_account_json(data).map_err(CliError::InvalidAccount)
+}
+
+// 读取属于本程序的账户数据
+// Read the data of an account owned by this program
+async fn fetch_data(client: &mut Client, config: &Config, address: &Pubkey) -> Result<Vec<u8>, CliError> {
+    let account = client.get_account(address).await?.ok_or(CliError::AccountNotFound(*address))?;
+    if account.owner != config.program_id {
+        return Err(CliError::InvalidAccount(format!(
+            "{} 不属于NFT程序 | {} is not owned by the NFT program",
+            address, address
+        )));
+    }
+    Ok(account.data)
+}
+
+// 读取并按类型加载程序账户
+// Read and load a program account of the given type
+pub(crate) async fn fetch<T: ProgramAccount>(
+    client: &mut Client,
+    config: &Config,
+    address: &Pubkey,
+) -> Result<T, CliError> {
+    let data = fetch_data(client, config, address).await?;
+    T::load(&data).map_err(|error| CliError::InvalidAccount(format!("{}: {}", address, error)))
+}
+
+// 挂单的卖家, 转移和销毁会关闭挂单并把租金退给他; 没有挂单时为当前所有者
+// The listing's seller, who gets the rent back when a transfer or burn closes the listing; the current owner when unlisted
+async fn listing_seller(
+    client: &mut Client,
+    config: &Config,
+    nft: &Pubkey,
+    account: &NFTAccount,
+) -> Result<Pubkey, CliError> {
+    let listing = find_listing_address(&config.program_id, nft).0;
+    match client.get_account(&listing).await? {
+        Some(listing) if listing.owner == config.program_id => Listing::load(&listing.data)
+            .map(|listing| listing.seller)
+            .map_err(|error| CliError::InvalidAccount(format!("{}: {}", nft, error))),
+        _ => Ok(account.owner),
+    }
+}
+
+// 合并两个JSON对象, 后者的字段在后
+// Merge two JSON objects, the latter's fields come last
+fn merge(mut base: Value, extra: Value) -> Value {
+    if let (Some(base), Value::Object(extra)) = (base.as_object_mut(), extra) {
+        base.extend(extra);
+    }
+    base
+}
+
+/// 解析公钥参数
+/// Parse a public key argument
+pub fn parse_pubkey(value: &str) -> Result<Pubkey, CliError> {
+    value.parse().map_err(|_| CliError::InvalidArgument(format!("无效的公钥 | invalid public key: {}", value)))
+}
+
+/// 解析转移模式: transferable, soulbound 或 authority-revocable
+/// Parse a transfer mode: transferable, soulbound or authority-revocable
+pub fn parse_transfer_mode(value: &str) -> Result<TransferMode, CliError> {
+    match value {
+        "transferable" => Ok(TransferMode::Transferable),
+        "soulbound" => Ok(TransferMode::Soulbound),
+        "authority-revocable" => Ok(TransferMode::AuthorityRevocable),
+        _ => Err(CliError::InvalidArgument(format!("未知的转移模式 | unknown transfer mode: {}", value))),
+    }
+}
+
+/// 解析 `地址:份额` 形式的创作者
+/// Parse a creator in `address:share` form
+pub fn parse_creator(value: &str) -> Result<Creator, CliError> {
+    let invalid = || {
+        CliError::InvalidArgument(format!("创作者格式为 地址:份额 | creators take the form address:share: {}", value))
+    };
+    let (address, share) = value.split_once(':').ok_or_else(invalid)?;
+    Ok(Creator { address: parse_pubkey(address)?, verified: false, share: share.parse().map_err(|_| invalid())? })
+}
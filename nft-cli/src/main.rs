@@ -0,0 +1,308 @@
+use std::{env, path::PathBuf, process};
+
+use clap::{Arg, ArgMatches, Command};
+use nft_cli::{bulk, parse_creator, parse_pubkey, parse_transfer_mode, CliError, Client, CollectionArgs, Config};
+use nft_contract::state::{find_nft_address, SerialMode};
+use serde_json::Value;
+use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file};
+
+fn app() -> Command<'static> {
+    let nft_args = [
+        Arg::new("nft").long("nft").takes_value(true).help("NFT地址 | NFT address"),
+        Arg::new("collection")
+            .long("collection")
+            .takes_value(true)
+            .requires("serial")
+            .help("集合地址, 与 --serial 一起代替 --nft | Collection address, with --serial instead of --nft"),
+        Arg::new("serial").long("serial").takes_value(true).requires("collection").help("序列号 | Serial number"),
+    ];
+    Command::new("nft-cli")
+        .about("Solana NFT 集合运维工具 | Solana NFT collection operator tool")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .global(true)
+                .takes_value(true)
+                .env("NFT_RPC_URL")
+                .default_value("http://127.0.0.1:8899")
+                .help("RPC 节点地址 | RPC node URL"),
+        )
+        .arg(Arg::new("keypair").long("keypair").global(true).takes_value(true).env("NFT_KEYPAIR").help(
+            "签名者密钥文件 (默认 ~/.config/solana/id.json) | Signer keypair file (default ~/.config/solana/id.json)",
+        ))
+        .arg(
+            Arg::new("program-id")
+                .long("program-id")
+                .global(true)
+                .takes_value(true)
+                .env("NFT_PROGRAM_ID")
+                .help("NFT 程序地址 | NFT program address"),
+        )
+        .arg(Arg::new("dry-run").long("dry-run").global(true).help("只模拟交易 | Only simulate transactions"))
+        .arg(Arg::new("json").long("json").global(true).help("以JSON输出 | Print JSON output"))
+        .subcommand(
+            Command::new("create-collection")
+                .about("创建集合 | Create a collection")
+                .arg(Arg::new("name").long("name").takes_value(true).required(true))
+                .arg(Arg::new("symbol").long("symbol").takes_value(true).required(true))
+                .arg(Arg::new("uri").long("uri").takes_value(true).required(true))
+                .arg(
+                    Arg::new("royalty")
+                        .long("royalty")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("版税百分比 | Royalty percentage"),
+                )
+                .arg(Arg::new("immutable").long("immutable").help("元数据不可修改 | Metadata cannot be updated"))
+                .arg(
+                    Arg::new("transfer-mode")
+                        .long("transfer-mode")
+                        .takes_value(true)
+                        .possible_values(["transferable", "soulbound", "authority-revocable"])
+                        .default_value("transferable"),
+                )
+                .arg(
+                    Arg::new("creator")
+                        .long("creator")
+                        .takes_value(true)
+                        .multiple_occurrences(true)
+                        .help("版税创作者 地址:份额 | Royalty creator address:share"),
+                )
+                .arg(Arg::new("max-supply").long("max-supply").takes_value(true).help("最大供应量 | Maximum supply"))
+                .arg(
+                    Arg::new("caller-assigned")
+                        .long("caller-assigned")
+                        .help("由铸造者指定序列号 | Serial numbers chosen by the minter"),
+                ),
+        )
+        .subcommand(
+            Command::new("mint")
+                .about("铸造NFT | Mint an NFT")
+                .arg(Arg::new("collection").long("collection").takes_value(true).required(true))
+                .arg(
+                    Arg::new("serial")
+                        .long("serial")
+                        .takes_value(true)
+                        .help("序列号, 顺序模式可省略 | Serial number, optional in sequential mode"),
+                )
+                .arg(Arg::new("uri").long("uri").takes_value(true).required(true))
+                .arg(
+                    Arg::new("recipient")
+                        .long("recipient")
+                        .takes_value(true)
+                        .help("接收者 (默认签名者) | Recipient (default the signer)"),
+                ),
+        )
+        .subcommand(
+            Command::new("transfer")
+                .about("转移NFT | Transfer an NFT")
+                .args(nft_args.clone())
+                .arg(Arg::new("to").long("to").takes_value(true).required(true).help("新所有者 | New owner")),
+        )
+        .subcommand(Command::new("burn").about("销毁NFT | Burn an NFT").args(nft_args.clone()))
+        .subcommand(
+            Command::new("update-metadata")
+                .about("更新NFT元数据 | Update NFT metadata")
+                .args(nft_args.clone())
+                .arg(Arg::new("uri").long("uri").takes_value(true).required(true)),
+        )
+        .subcommand(
+            Command::new("update-collection")
+                .about("更新集合元数据 | Update collection metadata")
+                .arg(Arg::new("collection").long("collection").takes_value(true).required(true))
+                .arg(Arg::new("uri").long("uri").takes_value(true).required(true)),
+        )
+        .subcommand(
+            Command::new("bulk-mint")
+                .about("按清单批量铸造 | Bulk mint from a manifest")
+                .arg(Arg::new("collection").long("collection").takes_value(true).required(true))
+                .arg(
+                    Arg::new("manifest")
+                        .long("manifest")
+                        .takes_value(true)
+                        .required(true)
+                        .help("CSV 或 JSON 清单 (serial, metadata_uri, recipient) | CSV or JSON manifest (serial, metadata_uri, recipient)"),
+                )
+                .arg(
+                    Arg::new("state")
+                        .long("state")
+                        .takes_value(true)
+                        .help("进度状态文件 (默认 <清单>.state.json) | Progress state file (default <manifest>.state.json)"),
+                ),
+        )
+        .subcommand(
+            Command::new("show")
+                .about("显示程序账户 | Show a program account")
+                .arg(Arg::new("address").takes_value(true).required(true)),
+        )
+        .subcommand(
+            Command::new("list")
+                .about("按序列号列出集合中的NFT | List a collection's NFTs by serial number")
+                .arg(Arg::new("collection").long("collection").takes_value(true).required(true))
+                .arg(Arg::new("start").long("start").takes_value(true).default_value("1"))
+                .arg(
+                    Arg::new("end")
+                        .long("end")
+                        .takes_value(true)
+                        .help("最后一个序列号 (默认 total_minted) | Last serial number (default total_minted)"),
+                ),
+        )
+}
+
+#[tokio::main]
+async fn main() {
+    let matches = app().get_matches();
+    let json = matches.is_present("json");
+    match run(&matches).await {
+        Ok(output) => {
+            if json {
+                println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            } else {
+                print_text(&output);
+            }
+            // 批量铸造有失败的行时以非零状态退出
+            // Exit with a non-zero status when bulk mint has failed rows
+            if output["failed"].as_array().is_some_and(|failed| !failed.is_empty()) {
+                process::exit(1);
+            }
+        }
+        Err(error) => {
+            if let CliError::Transaction { logs, .. } = &error {
+                logs.iter().for_each(|line| eprintln!("  {}", line));
+            }
+            eprintln!("错误 | Error: {}", error);
+            process::exit(1);
+        }
+    }
+}
+
+async fn run(matches: &ArgMatches) -> Result<Value, CliError> {
+    let (command, args) = matches.subcommand().expect("子命令是必需的 | a subcommand is required");
+    let config = config(matches)?;
+    let mut client = Client::rpc(matches.value_of("url").unwrap());
+    let client = &mut client;
+    match command {
+        "create-collection" => {
+            let collection = CollectionArgs {
+                name: args.value_of("name").unwrap().to_string(),
+                symbol: args.value_of("symbol").unwrap().to_string(),
+                uri: args.value_of("uri").unwrap().to_string(),
+                royalty_percentage: parse_number(args, "royalty")?.unwrap(),
+                is_mutable: !args.is_present("immutable"),
+                transfer_mode: parse_transfer_mode(args.value_of("transfer-mode").unwrap())?,
+                creators: args
+                    .values_of("creator")
+                    .into_iter()
+                    .flatten()
+                    .map(parse_creator)
+                    .collect::<Result<_, _>>()?,
+                max_supply: parse_number(args, "max-supply")?,
+                serial_mode: if args.is_present("caller-assigned") {
+                    SerialMode::CallerAssigned
+                } else {
+                    SerialMode::Sequential
+                },
+            };
+            nft_cli::create_collection(client, &config, collection).await
+        }
+        "mint" => {
+            let collection = parse_pubkey(args.value_of("collection").unwrap())?;
+            let recipient = args.value_of("recipient").map(parse_pubkey).transpose()?;
+            let uri = args.value_of("uri").unwrap();
+            nft_cli::mint(client, &config, &collection, parse_number(args, "serial")?, uri, recipient).await
+        }
+        "transfer" => {
+            let new_owner = parse_pubkey(args.value_of("to").unwrap())?;
+            nft_cli::transfer(client, &config, &nft_address(args, &config)?, &new_owner).await
+        }
+        "burn" => nft_cli::burn(client, &config, &nft_address(args, &config)?).await,
+        "update-metadata" => {
+            let uri = args.value_of("uri").unwrap();
+            nft_cli::update_metadata(client, &config, &nft_address(args, &config)?, uri).await
+        }
+        "update-collection" => {
+            let collection = parse_pubkey(args.value_of("collection").unwrap())?;
+            nft_cli::update_collection(client, &config, &collection, args.value_of("uri").unwrap()).await
+        }
+        "bulk-mint" => {
+            let collection = parse_pubkey(args.value_of("collection").unwrap())?;
+            let manifest = PathBuf::from(args.value_of("manifest").unwrap());
+            let state = match args.value_of("state") {
+                Some(state) => PathBuf::from(state),
+                None => manifest.with_extension("state.json"),
+            };
+            let rows = bulk::read_manifest(&manifest)?;
+            Ok(bulk::bulk_mint(client, &config, &collection, &rows, &state).await?.to_json())
+        }
+        "show" => nft_cli::show(client, &config, &parse_pubkey(args.value_of("address").unwrap())?).await,
+        "list" => {
+            let collection = parse_pubkey(args.value_of("collection").unwrap())?;
+            let start = parse_number(args, "start")?.unwrap();
+            nft_cli::list(client, &config, &collection, start, parse_number(args, "end")?).await
+        }
+        _ => unreachable!("未知子命令 | unknown subcommand"),
+    }
+}
+
+// 由全局参数构建执行配置
+// Build the execution configuration from the global arguments
+fn config(matches: &ArgMatches) -> Result<Config, CliError> {
+    let program_id = matches
+        .value_of("program-id")
+        .ok_or_else(|| CliError::InvalidArgument("缺少 --program-id | missing --program-id".to_string()))?;
+    let keypair_path = match matches.value_of("keypair") {
+        Some(path) => path.to_string(),
+        None => format!("{}/.config/solana/id.json", env::var("HOME").unwrap_or_default()),
+    };
+    let signer = read_keypair_file(&keypair_path).map_err(|error| {
+        CliError::InvalidArgument(format!("无法读取密钥文件 | cannot read keypair file {}: {}", keypair_path, error))
+    })?;
+    Ok(Config { program_id: parse_pubkey(program_id)?, signer, dry_run: matches.is_present("dry-run") })
+}
+
+// 由 --nft 或 --collection 加 --serial 得到NFT地址
+// Resolve the NFT address from --nft, or --collection with --serial
+fn nft_address(args: &ArgMatches, config: &Config) -> Result<Pubkey, CliError> {
+    if let Some(nft) = args.value_of("nft") {
+        return parse_pubkey(nft);
+    }
+    match (args.value_of("collection"), parse_number::<u64>(args, "serial")?) {
+        (Some(collection), Some(serial)) => {
+            Ok(find_nft_address(&config.program_id, &parse_pubkey(collection)?, serial).0)
+        }
+        _ => Err(CliError::InvalidArgument(
+            "需要 --nft 或 --collection 加 --serial | --nft or --collection with --serial is required".to_string(),
+        )),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(args: &ArgMatches, name: &str) -> Result<Option<T>, CliError> {
+    args.value_of(name)
+        .map(|value| {
+            value.parse().map_err(|_| {
+                CliError::InvalidArgument(format!("--{} 不是有效数字 | --{} is not a valid number", name, name))
+            })
+        })
+        .transpose()
+}
+
+// 文本输出: 标量字段逐行显示, 嵌套值以缩进的JSON显示
+// Text output: scalar fields one per line, nested values as indented JSON
+fn print_text(output: &Value) {
+    let Some(fields) = output.as_object() else {
+        return println!("{}", output);
+    };
+    for (key, value) in fields {
+        match value {
+            Value::String(value) => println!("{}: {}", key, value),
+            Value::Array(_) | Value::Object(_) => {
+                println!("{}:", key);
+                for line in serde_json::to_string_pretty(value).unwrap().lines() {
+                    println!("  {}", line);
+                }
+            }
+            value => println!("{}: {}", key, value),
+        }
+    }
+}
@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use nft_contract::{error::NFTError, state::find_nft_address};
+    use serde_json::json;
+    use solana_program_test::{processor, ProgramTest};
+
+    // 启动测试环境, 签名者是测试环境的付款人
+    // Start the test environment, the signer is the test payer
+    async fn setup(dry_run: bool) -> (Client, Config) {
+        let program_id = Pubkey::new_unique();
+        let program_test = ProgramTest::new("nft_contract", program_id, processor!(nft_contract::process_instruction));
+        let (banks_client, payer, _) = program_test.start().await;
+        (Client::Banks(banks_client), Config { program_id, signer: payer, dry_run })
+    }
+
+    fn collection_args(name: &str) -> CollectionArgs {
+        CollectionArgs {
+            name: name.to_string(),
+            symbol: "CLI".to_string(),
+            uri: "https://example.com/collection.json".to_string(),
+            royalty_percentage: 5,
+            is_mutable: true,
+            transfer_mode: TransferMode::Transferable,
+            creators: vec![],
+            max_supply: None,
+            serial_mode: SerialMode::Sequential,
+        }
+    }
+
+    fn address(value: &Value, field: &str) -> Pubkey {
+        value[field].as_str().unwrap().parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_collection_lifecycle() {
+        let (mut client, config) = setup(false).await;
+        let created = create_collection(&mut client, &config, collection_args("CLI Collection")).await.unwrap();
+        let collection = address(&created, "collection");
+        assert!(created["signature"].is_string());
+
+        let first = mint(&mut client, &config, &collection, None, "https://example.com/1.json", None).await.unwrap();
+        let second = mint(&mut client, &config, &collection, Some(2), "ipfs://2", None).await.unwrap();
+        assert_eq!(first["serial_number"], 1);
+        assert_eq!(address(&second, "nft"), find_nft_address(&config.program_id, &collection, 2).0);
+
+        let nft = address(&first, "nft");
+        update_metadata(&mut client, &config, &nft, "https://example.com/1-v2.json").await.unwrap();
+        update_collection(&mut client, &config, &collection, "https://example.com/collection-v2.json").await.unwrap();
+        let shown = show(&mut client, &config, &nft).await.unwrap();
+        assert_eq!(shown["type"], "NFTAccount");
+        assert_eq!(shown["data"]["metadata_uri"], "https://example.com/1-v2.json");
+
+        let new_owner = Pubkey::new_unique();
+        transfer(&mut client, &config, &address(&second, "nft"), &new_owner).await.unwrap();
+        burn(&mut client, &config, &nft).await.unwrap();
+
+        let listed = list(&mut client, &config, &collection, 1, None).await.unwrap();
+        let nfts = listed["nfts"].as_array().unwrap();
+        assert_eq!(nfts.len(), 1);
+        assert_eq!(nfts[0]["data"]["owner"], new_owner.to_string());
+
+        // 超过一页的范围按页读取, 结果相同
+        // A range longer than one page is fetched page by page with the same result
+        let paged = list(&mut client, &config, &collection, 1, Some(2 * LIST_PAGE_SIZE + 1)).await.unwrap();
+        assert_eq!(paged["nfts"], listed["nfts"]);
+        assert_eq!(list(&mut client, &config, &collection, 3, Some(2)).await.unwrap()["nfts"], json!([]));
+
+        let shown = show(&mut client, &config, &collection).await.unwrap();
+        assert_eq!(shown["data"]["uri"], "https://example.com/collection-v2.json");
+        assert_eq!(shown["data"]["total_burned"], "1");
+        assert!(matches!(show(&mut client, &config, &nft).await, Err(CliError::InvalidAccount(_))));
+        assert!(matches!(show(&mut client, &config, &Pubkey::new_unique()).await, Err(CliError::AccountNotFound(_))));
+    }
+
+    // 模拟不改变链上状态, 程序错误被解码为 NFTError
+    // Simulation leaves on-chain state untouched, program errors decode to NFTError
+    #[tokio::test]
+    async fn test_dry_run_and_decoded_errors() {
+        let (mut client, mut config) = setup(false).await;
+        let created = create_collection(&mut client, &config, collection_args("Dry Run")).await.unwrap();
+        let collection = address(&created, "collection");
+
+        config.dry_run = true;
+        let simulated =
+            mint(&mut client, &config, &collection, None, "https://example.com/1.json", None).await.unwrap();
+        assert_eq!(simulated["dry_run"], true);
+        assert!(simulated["logs"].as_array().is_some_and(|logs| !logs.is_empty()));
+        let shown = show(&mut client, &config, &collection).await.unwrap();
+        assert_eq!(shown["data"]["total_minted"], "0");
+
+        let error = mint(&mut client, &config, &collection, None, "ftp://example.com/1.json", None).await.unwrap_err();
+        let CliError::Transaction { error, logs } = error else {
+            panic!("应为交易错误 | expected a transaction error")
+        };
+        let (index, decoded) = decode_transaction_error(&error).unwrap();
+        assert_eq!((index, decoded.error), (0, Some(NFTError::InvalidMetadataUri)));
+        assert!(!logs.is_empty());
+
+        config.dry_run = false;
+        let error = mint(&mut client, &config, &collection, Some(5), "ipfs://5", None).await.unwrap_err();
+        assert!(matches!(error, CliError::InvalidArgument(_)));
+        assert!(parse_creator("not-a-creator").is_err());
+        assert_eq!(parse_transfer_mode("soulbound").unwrap(), TransferMode::Soulbound);
+    }
+
+    // 清单按交易大小打包, 出错的行带着解码后的 NFTError 单独失败, 重新运行时跳过已铸造的行
+    // Manifests are packed by transaction size, the offending row fails alone with a decoded NFTError, and reruns skip
+    // minted rows
+    #[tokio::test]
+    async fn test_bulk_mint_packs_and_reports_failures() {
+        let (mut client, config) = setup(false).await;
+        let args = CollectionArgs { serial_mode: SerialMode::CallerAssigned, ..collection_args("Bulk") };
+        let collection = address(&create_collection(&mut client, &config, args).await.unwrap(), "collection");
+
+        let recipient = Pubkey::new_unique();
+        let mut csv = "serial,metadata_uri,recipient\n".to_string();
+        for serial in 1..=25 {
+            match serial {
+                3 => csv.push_str(&format!("3,\"https://example.com/nft/3.json?a=1,2\",\"{}\"\n", recipient)),
+                7 => csv.push_str("7,ftp://example.com/nft/7.json,\n"),
+                serial => csv.push_str(&format!("{},https://example.com/nft/{}.json,\n", serial, serial)),
+            }
+        }
+        let rows = bulk::parse_manifest_csv(&csv).unwrap();
+        assert_eq!(rows[2].metadata_uri, "https://example.com/nft/3.json?a=1,2");
+        assert_eq!(rows[2].recipient, Some(recipient));
+
+        let directory = tempfile::tempdir().unwrap();
+        let state = directory.path().join("drop.state.json");
+        let report = bulk::bulk_mint(&mut client, &config, &collection, &rows, &state).await.unwrap();
+        assert_eq!(report.minted.len(), 24);
+        assert!(report.transactions > 1 && report.transactions < 24);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!((report.failed[0].row, report.failed[0].serial_number), (7, 7));
+        assert_eq!(report.failed[0].error.unwrap().error, Some(NFTError::InvalidMetadataUri));
+        assert_eq!(report.to_json()["failed"][0]["error"], "InvalidMetadataUri");
+
+        let nft = find_nft_address(&config.program_id, &collection, 3).0;
+        let shown = show(&mut client, &config, &nft).await.unwrap();
+        assert_eq!(shown["data"]["owner"], recipient.to_string());
+
+        let rerun = bulk::bulk_mint(&mut client, &config, &collection, &rows, &state).await.unwrap();
+        assert_eq!((rerun.skipped.len(), rerun.minted.len(), rerun.failed.len()), (24, 0, 1));
+        let shown = show(&mut client, &config, &collection).await.unwrap();
+        assert_eq!(shown["data"]["total_minted"], "24");
+    }
+
+    // 模拟模式下调用方指定序列号的集合模拟全部批次, 顺序模式的集合只模拟第一批
+    // Dry runs simulate every batch of a caller-assigned collection, but only the first batch of a sequential one
+    #[tokio::test]
+    async fn test_bulk_mint_dry_run_batches() {
+        let (mut client, mut config) = setup(false).await;
+        let args = CollectionArgs { serial_mode: SerialMode::CallerAssigned, ..collection_args("Dry") };
+        let assigned = address(&create_collection(&mut client, &config, args).await.unwrap(), "collection");
+        let sequential =
+            address(&create_collection(&mut client, &config, collection_args("DrySeq")).await.unwrap(), "collection");
+
+        let mut csv = "serial,metadata_uri\n".to_string();
+        for serial in 1..=25 {
+            csv.push_str(&format!("{},https://example.com/nft/{}.json\n", serial, serial));
+        }
+        let rows = bulk::parse_manifest_csv(&csv).unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        config.dry_run = true;
+
+        let report =
+            bulk::bulk_mint(&mut client, &config, &assigned, &rows, &directory.path().join("a.json")).await.unwrap();
+        assert!(report.transactions > 1);
+        assert_eq!(report.simulated, (1..=25).collect::<Vec<_>>());
+        assert_eq!((report.minted.len(), report.remaining), (0, 0));
+
+        let report =
+            bulk::bulk_mint(&mut client, &config, &sequential, &rows, &directory.path().join("s.json")).await.unwrap();
+        assert_eq!(report.transactions, 1);
+        assert_eq!(report.simulated.len() + report.remaining, 25);
+        assert!(report.remaining > 0);
+
+        // 模拟不铸造任何NFT
+        // Nothing is minted by a dry run
+        let shown = show(&mut client, &config, &assigned).await.unwrap();
+        assert_eq!(shown["data"]["total_minted"], "0");
+    }
+
+    // 崩溃时未确认的批次按链上账户核对, 不会重复铸造
+    // The batch in flight during a crash is checked against the chain and never minted twice
+    #[tokio::test]
+    async fn test_bulk_mint_resumes_after_crash() {
+        let (mut client, config) = setup(false).await;
+        let collection =
+            address(&create_collection(&mut client, &config, collection_args("Resume")).await.unwrap(), "collection");
+
+        // 第1号已上链, 但崩溃发生在写入检查点之前
+        // Serial 1 landed on chain, but the crash happened before the checkpoint was written
+        mint(&mut client, &config, &collection, Some(1), "ipfs://1", None).await.unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let state = directory.path().join("drop.state.json");
+        let checkpoint = json!({ "collection": collection.to_string(), "minted": {}, "pending": [1, 2] });
+        std::fs::write(&state, checkpoint.to_string()).unwrap();
+
+        let manifest = directory.path().join("drop.json");
+        let rows = json!([
+            { "serial": 1, "metadata_uri": "ipfs://1" },
+            { "serial": "2", "metadata_uri": "ipfs://2" },
+            { "serial": 3, "metadata_uri": "ipfs://3", "recipient": Pubkey::new_unique().to_string() },
+        ]);
+        std::fs::write(&manifest, rows.to_string()).unwrap();
+        let rows = bulk::read_manifest(&manifest).unwrap();
+
+        let report = bulk::bulk_mint(&mut client, &config, &collection, &rows, &state).await.unwrap();
+        assert_eq!(report.skipped, vec![1]);
+        assert_eq!(report.minted.iter().map(|(serial, _)| *serial).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(report.failed.is_empty());
+
+        let saved: Value = serde_json::from_str(&std::fs::read_to_string(&state).unwrap()).unwrap();
+        assert_eq!(saved["minted"].as_object().unwrap().len(), 3);
+        assert_eq!(saved["pending"], json!([]));
+
+        // 顺序模式集合的序列号必须从 total_minted + 1 连续
+        // Sequential collections need serials consecutive from total_minted + 1
+        let gap = bulk::parse_manifest_csv("serial,metadata_uri\n5,ipfs://5\n").unwrap();
+        let error = bulk::bulk_mint(&mut client, &config, &collection, &gap, &state).await.unwrap_err();
+        assert!(matches!(error, CliError::InvalidManifest(_)));
+        assert!(bulk::parse_manifest_csv("serial,uri\n1,ipfs://1\n").is_err());
+    }
+}
@@ -0,0 +1,442 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    program::invoke_signed,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    instruction::{
+        self, account_metas, build_instruction, NFTInstruction, BURN_NFT_ACCOUNTS, BUY_NFT_ACCOUNTS,
+        DELIST_NFT_ACCOUNTS, INITIALIZE_COLLECTION_ACCOUNTS, LIST_NFT_ACCOUNTS, MINT_NFT_ACCOUNTS,
+        SET_OPERATOR_APPROVAL_ACCOUNTS, TRANSFER_NFT_ACCOUNTS,
+    },
+    state::{Creator, SerialMode, TransferMode},
+};
+
+// 跨程序调用的包装函数: 账户元数据按指令的账户表由调用方提供的账户生成, 再以这些账户调用本程序.
+// 涉及PDA的指令由调用方传入bump (链下用 find_* 函数得到), 链上不搜索bump.
+// 账户参数与 `NFTInstruction` 文档中的顺序一致, signers_seeds 用于调用方的PDA签名 (没有时传入 `&[]`).
+// Cross-program invocation wrappers: the account metas are built from the caller's accounts with the instruction's
+// account table, then this program is invoked with them. Instructions involving PDAs take the bump from the caller
+// (found off chain with the find_* functions), so no bump search runs on chain. Account parameters follow the order
+// documented on `NFTInstruction`, signers_seeds lets the caller sign with its PDAs (pass `&[]` when there are none).
+
+/// 通过CPI初始化集合
+/// Initialize a collection through CPI
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_collection<'a>(
+    program: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    royalty_percentage: u8,
+    is_mutable: bool,
+    transfer_mode: TransferMode,
+    creators: Vec<Creator>,
+    max_supply: Option<u64>,
+    serial_mode: SerialMode,
+    bump: u8,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let data = NFTInstruction::InitializeCollection {
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        uri: uri.to_string(),
+        royalty_percentage,
+        is_mutable,
+        transfer_mode,
+        creators,
+        max_supply,
+        serial_mode,
+        bump,
+    };
+    let accounts = account_metas(
+        INITIALIZE_COLLECTION_ACCOUNTS,
+        [*collection.key, *authority.key, *system_program.key, *payer.key],
+    );
+    let ix = build_instruction(program.key, &data, accounts);
+    invoke_signed(
+        &ix,
+        &[collection.clone(), authority.clone(), system_program.clone(), payer.clone(), program.clone()],
+        signers_seeds,
+    )
+}
+
+/// 通过CPI铸造NFT
+/// Mint an NFT through CPI
+#[allow(clippy::too_many_arguments)]
+pub fn mint_nft<'a>(
+    program: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    serial_number: u64,
+    metadata_uri: &str,
+    recipient: Option<Pubkey>,
+    bump: u8,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let data = NFTInstruction::MintNFT { metadata_uri: metadata_uri.to_string(), serial_number, bump, recipient };
+    let accounts = account_metas(
+        MINT_NFT_ACCOUNTS,
+        [*collection.key, *nft.key, *authority.key, *system_program.key, *payer.key],
+    );
+    let ix = build_instruction(program.key, &data, accounts);
+    invoke_signed(
+        &ix,
+        &[
+            collection.clone(),
+            nft.clone(),
+            authority.clone(),
+            system_program.clone(),
+            payer.clone(),
+            program.clone(),
+        ],
+        signers_seeds,
+    )
+}
+
+/// 通过CPI转移NFT, 挂单存在时被关闭并退还给挂单卖家, 操作员签名时需传入操作员授权账户
+/// Transfer an NFT through CPI, closing any listing back to its seller, pass the operator approval account when an
+/// operator signs
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_nft<'a>(
+    program: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    signer: &AccountInfo<'a>,
+    new_owner: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    listing: &AccountInfo<'a>,
+    listing_seller: &AccountInfo<'a>,
+    operator_approval: Option<&AccountInfo<'a>>,
+    listing_bump: u8,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let keys = [*nft.key, *signer.key, *new_owner.key, *collection.key, *listing.key, *listing_seller.key];
+    let accounts =
+        account_metas(TRANSFER_NFT_ACCOUNTS, keys.into_iter().chain(operator_approval.map(|approval| *approval.key)));
+    let data = NFTInstruction::TransferNFT { new_owner: *new_owner.key, listing_bump };
+    let ix = build_instruction(program.key, &data, accounts);
+    let mut account_infos = vec![
+        nft.clone(),
+        signer.clone(),
+        new_owner.clone(),
+        collection.clone(),
+        listing.clone(),
+        listing_seller.clone(),
+    ];
+    account_infos.extend(operator_approval.cloned());
+    account_infos.push(program.clone());
+    invoke_signed(&ix, &account_infos, signers_seeds)
+}
+
+/// 通过CPI销毁NFT, NFT租金退还给所有者, 挂单存在时被关闭并退还给挂单卖家, 操作员签名时需传入操作员授权账户
+/// Burn an NFT through CPI, refunding its rent to the owner and closing any listing back to its seller, pass the
+/// operator approval account when an operator signs
+#[allow(clippy::too_many_arguments)]
+pub fn burn_nft<'a>(
+    program: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    signer: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    listing: &AccountInfo<'a>,
+    listing_seller: &AccountInfo<'a>,
+    operator_approval: Option<&AccountInfo<'a>>,
+    listing_bump: u8,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let keys = [*nft.key, *signer.key, *owner.key, *collection.key, *listing.key, *listing_seller.key];
+    let accounts =
+        account_metas(BURN_NFT_ACCOUNTS, keys.into_iter().chain(operator_approval.map(|approval| *approval.key)));
+    let ix = build_instruction(program.key, &NFTInstruction::BurnNFT { listing_bump }, accounts);
+    let mut account_infos =
+        vec![nft.clone(), signer.clone(), owner.clone(), collection.clone(), listing.clone(), listing_seller.clone()];
+    account_infos.extend(operator_approval.cloned());
+    account_infos.push(program.clone());
+    invoke_signed(&ix, &account_infos, signers_seeds)
+}
+
+/// 通过CPI更新NFT元数据
+/// Update NFT metadata through CPI
+pub fn update_nft_metadata<'a>(
+    program: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    new_metadata_uri: &str,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::update_nft_metadata(program.key, nft.key, owner.key, collection.key, new_metadata_uri);
+    invoke_signed(
+        &ix,
+        &[nft.clone(), owner.clone(), collection.clone(), program.clone()],
+        signers_seeds,
+    )
+}
+
+/// 通过CPI更新集合元数据
+/// Update collection metadata through CPI
+pub fn update_collection_metadata<'a>(
+    program: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    new_uri: &str,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::update_collection_metadata(program.key, collection.key, authority.key, new_uri);
+    invoke_signed(&ix, &[collection.clone(), authority.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI提议新的集合权限
+/// Propose a new collection authority through CPI
+pub fn propose_collection_authority<'a>(
+    program: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    new_authority: Option<Pubkey>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::propose_collection_authority(program.key, collection.key, authority.key, new_authority);
+    invoke_signed(&ix, &[collection.clone(), authority.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI接受集合权限
+/// Accept the collection authority through CPI
+pub fn accept_collection_authority<'a>(
+    program: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    pending_authority: &AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::accept_collection_authority(program.key, collection.key, pending_authority.key);
+    invoke_signed(&ix, &[collection.clone(), pending_authority.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI授权代理
+/// Approve a delegate through CPI
+pub fn approve_delegate<'a>(
+    program: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    delegate: &Pubkey,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::approve_delegate(program.key, nft.key, owner.key, delegate);
+    invoke_signed(&ix, &[nft.clone(), owner.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI撤销代理
+/// Revoke the delegate through CPI
+pub fn revoke_delegate<'a>(
+    program: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::revoke_delegate(program.key, nft.key, owner.key);
+    invoke_signed(&ix, &[nft.clone(), owner.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI设置操作员授权
+/// Set an operator approval through CPI
+#[allow(clippy::too_many_arguments)]
+pub fn set_operator_approval<'a>(
+    program: &AccountInfo<'a>,
+    operator_approval: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    operator: &Pubkey,
+    approved: bool,
+    expires_at: Option<i64>,
+    bump: u8,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let data = NFTInstruction::SetOperatorApproval { operator: *operator, approved, expires_at, bump };
+    let accounts = account_metas(
+        SET_OPERATOR_APPROVAL_ACCOUNTS,
+        [*operator_approval.key, *owner.key, *collection.key, *system_program.key],
+    );
+    let ix = build_instruction(program.key, &data, accounts);
+    invoke_signed(
+        &ix,
+        &[
+            operator_approval.clone(),
+            owner.clone(),
+            collection.clone(),
+            system_program.clone(),
+            program.clone(),
+        ],
+        signers_seeds,
+    )
+}
+
+/// 通过CPI设置冻结权限
+/// Set the freeze authority through CPI
+pub fn set_freeze_authority<'a>(
+    program: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    freeze_authority: Option<Pubkey>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::set_freeze_authority(program.key, collection.key, authority.key, freeze_authority);
+    invoke_signed(&ix, &[collection.clone(), authority.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI冻结NFT
+/// Freeze an NFT through CPI
+pub fn freeze_nft<'a>(
+    program: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    freeze_authority: &AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::freeze_nft(program.key, nft.key, collection.key, freeze_authority.key);
+    invoke_signed(
+        &ix,
+        &[nft.clone(), collection.clone(), freeze_authority.clone(), program.clone()],
+        signers_seeds,
+    )
+}
+
+/// 通过CPI解冻NFT
+/// Thaw an NFT through CPI
+pub fn thaw_nft<'a>(
+    program: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    freeze_authority: &AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::thaw_nft(program.key, nft.key, collection.key, freeze_authority.key);
+    invoke_signed(
+        &ix,
+        &[nft.clone(), collection.clone(), freeze_authority.clone(), program.clone()],
+        signers_seeds,
+    )
+}
+
+/// 通过CPI挂单NFT
+/// List an NFT through CPI
+#[allow(clippy::too_many_arguments)]
+pub fn list_nft<'a>(
+    program: &AccountInfo<'a>,
+    listing: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    seller: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    price: u64,
+    bump: u8,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = account_metas(
+        LIST_NFT_ACCOUNTS,
+        [*listing.key, *nft.key, *collection.key, *seller.key, *system_program.key],
+    );
+    let ix = build_instruction(program.key, &NFTInstruction::ListNFT { price, bump }, accounts);
+    invoke_signed(
+        &ix,
+        &[
+            listing.clone(),
+            nft.clone(),
+            collection.clone(),
+            seller.clone(),
+            system_program.clone(),
+            program.clone(),
+        ],
+        signers_seeds,
+    )
+}
+
+/// 通过CPI取消挂单
+/// Delist an NFT through CPI
+pub fn delist_nft<'a>(
+    program: &AccountInfo<'a>,
+    listing: &AccountInfo<'a>,
+    seller: &AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let accounts = account_metas(DELIST_NFT_ACCOUNTS, [*listing.key, *seller.key]);
+    let ix = build_instruction(program.key, &NFTInstruction::DelistNFT, accounts);
+    invoke_signed(&ix, &[listing.clone(), seller.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI购买NFT, 版税接收者按 `BuyNFT` 文档的顺序传入
+/// Buy an NFT through CPI, royalty recipients are passed in the order documented on `BuyNFT`
+#[allow(clippy::too_many_arguments)]
+pub fn buy_nft<'a>(
+    program: &AccountInfo<'a>,
+    listing: &AccountInfo<'a>,
+    nft: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    buyer: &AccountInfo<'a>,
+    seller: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    royalty_recipients: &[AccountInfo<'a>],
+    price: u64,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let mut accounts = account_metas(
+        BUY_NFT_ACCOUNTS,
+        [*listing.key, *nft.key, *collection.key, *buyer.key, *seller.key, *system_program.key],
+    );
+    accounts.extend(royalty_recipients.iter().map(|recipient| AccountMeta::new(*recipient.key, false)));
+    let ix = build_instruction(program.key, &NFTInstruction::BuyNFT { price }, accounts);
+    let mut account_infos = vec![
+        listing.clone(),
+        nft.clone(),
+        collection.clone(),
+        buyer.clone(),
+        seller.clone(),
+        system_program.clone(),
+    ];
+    account_infos.extend_from_slice(royalty_recipients);
+    account_infos.push(program.clone());
+    invoke_signed(&ix, &account_infos, signers_seeds)
+}
+
+/// 通过CPI签名确认创作者身份
+/// Sign as a creator through CPI
+pub fn sign_creator<'a>(
+    program: &AccountInfo<'a>,
+    collection: &AccountInfo<'a>,
+    creator: &AccountInfo<'a>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::sign_creator(program.key, collection.key, creator.key);
+    invoke_signed(&ix, &[collection.clone(), creator.clone(), program.clone()], signers_seeds)
+}
+
+/// 通过CPI迁移旧版账户
+/// Migrate a legacy account through CPI
+pub fn migrate_account<'a>(
+    program: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    original_authority: Option<&AccountInfo<'a>>,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let ix = instruction::migrate_account(
+        program.key,
+        account.key,
+        payer.key,
+        original_authority.map(|authority| authority.key),
+    );
+    let mut account_infos = vec![account.clone(), payer.clone(), system_program.clone()];
+    account_infos.extend(original_authority.cloned());
+    account_infos.push(program.clone());
+    invoke_signed(&ix, &account_infos, signers_seeds)
+}
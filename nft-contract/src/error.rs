@@ -1,9 +1,10 @@
-use solana_program::program_error::ProgramError;
+use num_derive::FromPrimitive;
+use solana_program::{decode_error::DecodeError, program_error::ProgramError};
 use thiserror::Error;
 
 /// NFT程序错误类型
 /// NFT program error types
-#[derive(Error, Debug, Copy, Clone)]
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq, FromPrimitive)]
 pub enum NFTError {
     /// 无效的指令
     /// Invalid instruction
@@ -59,10 +60,91 @@ pub enum NFTError {
     /// Immutable metadata
     #[error("不可变的元数据 | Immutable metadata")]
     ImmutableMetadata,
+
+    /// 操作员授权已过期
+    /// Operator approval expired
+    #[error("操作员授权已过期 | Operator approval expired")]
+    OperatorApprovalExpired,
+
+    /// NFT已冻结
+    /// NFT is frozen
+    #[error("NFT已冻结 | NFT is frozen")]
+    NFTFrozen,
+
+    /// NFT不可转移
+    /// NFT is non-transferable
+    #[error("NFT不可转移 | NFT is non-transferable")]
+    NonTransferable,
+
+    /// 无效的挂单
+    /// Invalid listing
+    #[error("无效的挂单 | Invalid listing")]
+    InvalidListing,
+
+    /// 无效的价格
+    /// Invalid price
+    #[error("无效的价格 | Invalid price")]
+    InvalidPrice,
+
+    /// 无效的创作者列表
+    /// Invalid creators
+    #[error("无效的创作者列表 | Invalid creators")]
+    InvalidCreators,
+
+    /// 找不到创作者
+    /// Creator not found
+    #[error("找不到创作者 | Creator not found")]
+    CreatorNotFound,
+
+    /// 已达到最大供应量
+    /// Max supply reached
+    #[error("已达到最大供应量 | Max supply reached")]
+    MaxSupplyReached,
+
+    /// 序列号已被销毁
+    /// Serial number already burned
+    #[error("序列号已被销毁 | Serial number already burned")]
+    SerialNumberBurned,
+
+    /// 账户类型不匹配
+    /// Account type mismatch
+    #[error("账户类型不匹配 | Account type mismatch")]
+    AccountTypeMismatch,
+
+    /// 不支持的账户版本
+    /// Unsupported account version
+    #[error("不支持的账户版本 | Unsupported account version")]
+    UnsupportedAccountVersion,
+
+    /// 名称过长
+    /// Name too long
+    #[error("名称过长 | Name too long")]
+    NameTooLong,
+
+    /// 符号过长
+    /// Symbol too long
+    #[error("符号过长 | Symbol too long")]
+    SymbolTooLong,
+
+    /// URI过长
+    /// URI too long
+    #[error("URI过长 | URI too long")]
+    UriTooLong,
+
+    /// 版税接收者与集合的创作者不符
+    /// Royalty recipient does not match the collection's creators
+    #[error("版税接收者与集合的创作者不符 | Royalty recipient does not match the collection's creators")]
+    InvalidRoyaltyRecipient,
 }
 
 impl From<NFTError> for ProgramError {
     fn from(e: NFTError) -> Self {
         ProgramError::Custom(e as u32)
     }
-} 
\ No newline at end of file
+}
+
+impl<T> DecodeError<T> for NFTError {
+    fn type_of() -> &'static str {
+        "NFTError"
+    }
+}
@@ -0,0 +1,198 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+/// 程序事件, 以Borsh编码通过 `sol_log_data` 输出 (日志中显示为 "Program data: <base64>")
+/// Program events, Borsh-encoded and emitted through `sol_log_data` (shown as "Program data: <base64>" in the logs)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum NFTEvent {
+    /// 集合已初始化
+    /// Collection initialized
+    CollectionInitialized {
+        collection: Pubkey,
+        authority: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+
+    /// NFT已铸造
+    /// NFT minted
+    NFTMinted {
+        collection: Pubkey,
+        nft: Pubkey,
+        owner: Pubkey,
+        minter: Pubkey,
+        serial_number: u64,
+        metadata_uri: String,
+    },
+
+    /// NFT所有权已变更 (转移和购买都会输出)
+    /// NFT ownership changed (emitted for both transfers and purchases)
+    NFTTransferred {
+        nft: Pubkey,
+        from: Pubkey,
+        to: Pubkey,
+    },
+
+    /// NFT已销毁
+    /// NFT burned
+    NFTBurned {
+        collection: Pubkey,
+        nft: Pubkey,
+        owner: Pubkey,
+        serial_number: u64,
+    },
+
+    /// NFT或集合的元数据URI已更新
+    /// Metadata URI of an NFT or collection updated
+    MetadataUpdated {
+        account: Pubkey,
+        old_uri: String,
+        new_uri: String,
+    },
+
+    /// 已提议新的集合权限, `None` 表示取消提议
+    /// New collection authority proposed, `None` cancels the proposal
+    CollectionAuthorityProposed {
+        collection: Pubkey,
+        pending_authority: Option<Pubkey>,
+    },
+
+    /// 集合权限已转移
+    /// Collection authority transferred
+    CollectionAuthorityTransferred {
+        collection: Pubkey,
+        old_authority: Pubkey,
+        new_authority: Pubkey,
+    },
+
+    /// NFT代理已授权或撤销
+    /// NFT delegate approved or revoked
+    DelegateUpdated {
+        nft: Pubkey,
+        owner: Pubkey,
+        delegate: Option<Pubkey>,
+    },
+
+    /// 操作员授权已设置或撤销
+    /// Operator approval set or revoked
+    OperatorApprovalUpdated {
+        owner: Pubkey,
+        collection: Pubkey,
+        operator: Pubkey,
+        approved: bool,
+        expires_at: Option<i64>,
+    },
+
+    /// 冻结权限已更新
+    /// Freeze authority updated
+    FreezeAuthorityUpdated {
+        collection: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    },
+
+    /// NFT已冻结
+    /// NFT frozen
+    NFTFrozen {
+        nft: Pubkey,
+    },
+
+    /// NFT已解冻
+    /// NFT thawed
+    NFTThawed {
+        nft: Pubkey,
+    },
+
+    /// NFT已挂单
+    /// NFT listed
+    NFTListed {
+        listing: Pubkey,
+        nft: Pubkey,
+        seller: Pubkey,
+        price: u64,
+    },
+
+    /// NFT已取消挂单
+    /// NFT delisted
+    NFTDelisted {
+        listing: Pubkey,
+        nft: Pubkey,
+        seller: Pubkey,
+    },
+
+    /// NFT已售出, 紧跟在对应的 `NFTTransferred` 之后
+    /// NFT sold, right after the matching `NFTTransferred`
+    NFTSold {
+        nft: Pubkey,
+        seller: Pubkey,
+        buyer: Pubkey,
+        price: u64,
+        royalty: u64,
+    },
+
+    /// 创作者已验证
+    /// Creator verified
+    CreatorVerified {
+        collection: Pubkey,
+        creator: Pubkey,
+    },
+
+    /// 账户已迁移到当前版本
+    /// Account migrated to the current version
+    AccountMigrated {
+        account: Pubkey,
+        version: u8,
+    },
+}
+
+impl NFTEvent {
+    /// 通过 `sol_log_data` 输出事件
+    /// Emit the event through `sol_log_data`
+    pub fn emit(&self) {
+        let data = self.try_to_vec().expect("序列化到内存不会失败 | serializing into memory cannot fail");
+        sol_log_data(&[&data]);
+    }
+
+    /// 从 `sol_log_data` 的数据解码事件
+    /// Decode an event from `sol_log_data` data
+    pub fn try_from_log_data(data: &[u8]) -> Option<Self> {
+        Self::try_from_slice(data).ok()
+    }
+}
+
+/// 从交易日志中解析本程序输出的事件, 按调用栈忽略其他程序 (包括被CPI调用的程序) 的数据
+/// Parse this program's events from transaction logs, using the invoke stack to skip data from other programs (including CPI callees)
+pub fn parse_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<NFTEvent> {
+    let program_id = program_id.to_string();
+    let mut invoke_stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            if invoke_stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let decoded = data.split(' ').next().and_then(|field| STANDARD.decode(field).ok());
+            events.extend(decoded.as_deref().and_then(NFTEvent::try_from_log_data));
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            // "Program <id> invoke [n]" 入栈, "Program <id> success" 或 "Program <id> failed: ..." 出栈
+            // "Program <id> invoke [n]" pushes, "Program <id> success" or "Program <id> failed: ..." pops
+            let mut words = rest.split(' ');
+            let (Some(id), Some(status)) = (words.next(), words.next()) else {
+                continue;
+            };
+            if id.parse::<Pubkey>().is_err() {
+                continue;
+            }
+            match status {
+                "invoke" => invoke_stack.push(id),
+                "success" | "failed:" => {
+                    invoke_stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
@@ -1,5 +1,14 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::state::{
+    find_collection_address, find_listing_address, find_nft_address, find_operator_approval_address,
+    Creator, SerialMode, TransferMode,
+};
 
 /// NFT程序指令
 /// NFT program instructions
@@ -11,8 +20,9 @@ pub enum NFTInstruction {
     /// 账户:
     /// Accounts:
     /// 0. `[writable]` 集合账户 (PDA) | Collection account (PDA)
-    /// 1. `[signer]` 权限账户 | Authority account
+    /// 1. `[writable, signer]` 权限账户 (未提供付款人时支付租金) | Authority account (pays rent when no payer is given)
     /// 2. `[]` 系统程序 | System program
+    /// 3. `[writable, signer]` 付款人账户 (可选, 支付租金) | Payer account (optional, pays rent)
     InitializeCollection {
         /// 集合名称
         /// Collection name
@@ -33,6 +43,26 @@ pub enum NFTInstruction {
         /// 是否可变
         /// Whether metadata can be updated
         is_mutable: bool,
+        
+        /// 转移模式
+        /// Transfer mode
+        transfer_mode: TransferMode,
+        
+        /// 版税创作者 (份额合计100, 权限账户自动验证)
+        /// Royalty creators (shares sum to 100, the authority is verified automatically)
+        creators: Vec<Creator>,
+        
+        /// 最大供应量 (为空时不限制)
+        /// Maximum supply (unlimited when empty)
+        max_supply: Option<u64>,
+        
+        /// 序列号分配模式
+        /// Serial number assignment mode
+        serial_mode: SerialMode,
+        
+        /// 集合PDA的规范bump (由 find_collection_address 得到)
+        /// Canonical bump of the collection PDA (from find_collection_address)
+        bump: u8,
     },
     
     /// 铸造NFT
@@ -40,18 +70,27 @@ pub enum NFTInstruction {
     ///
     /// 账户:
     /// Accounts:
-    /// 0. `[]` 集合账户 | Collection account
+    /// 0. `[writable]` 集合账户 | Collection account
     /// 1. `[writable]` NFT账户 (PDA) | NFT account (PDA)
-    /// 2. `[signer]` 权限账户 | Authority account
+    /// 2. `[writable, signer]` 权限账户 (未提供付款人时支付租金) | Authority account (pays rent when no payer is given)
     /// 3. `[]` 系统程序 | System program
+    /// 4. `[writable, signer]` 付款人账户 (可选, 支付租金) | Payer account (optional, pays rent)
     MintNFT {
         /// 元数据URI
         /// Metadata URI
         metadata_uri: String,
         
-        /// 序列号
-        /// Serial number
+        /// 序列号 (顺序模式的集合忽略此值, 使用 total_minted + 1)
+        /// Serial number (ignored by sequential collections, which use total_minted + 1)
         serial_number: u64,
+        
+        /// NFT PDA的规范bump
+        /// Canonical bump of the NFT PDA
+        bump: u8,
+        
+        /// 接收者 (为空时铸造给集合权限)
+        /// Recipient (mints to the collection authority when empty)
+        recipient: Option<Pubkey>,
     },
     
     /// 转移NFT
@@ -60,12 +99,20 @@ pub enum NFTInstruction {
     /// 账户:
     /// Accounts:
     /// 0. `[writable]` NFT账户 | NFT account
-    /// 1. `[signer]` 当前所有者账户 | Current owner account
+    /// 1. `[signer]` 当前所有者、代理或操作员账户 | Current owner, delegate or operator account
     /// 2. `[]` 新所有者账户 | New owner account
+    /// 3. `[]` 集合账户 | Collection account
+    /// 4. `[writable]` 挂单PDA, 存在时被关闭 | Listing PDA, closed when it exists
+    /// 5. `[writable]` 挂单卖家账户 (接收挂单租金, 通常是当前所有者) | Listing seller account (receives the listing rent, usually the current owner)
+    /// 6. `[]` 操作员授权账户 (可选, 操作员签名时需要) | Operator approval account (optional, required when an operator signs)
     TransferNFT {
         /// 新所有者
         /// New owner
         new_owner: Pubkey,
+        
+        /// 挂单PDA的规范bump (由 find_listing_address 得到)
+        /// Canonical bump of the listing PDA (from find_listing_address)
+        listing_bump: u8,
     },
     
     /// 销毁NFT
@@ -74,8 +121,17 @@ pub enum NFTInstruction {
     /// 账户:
     /// Accounts:
     /// 0. `[writable]` NFT账户 | NFT account
-    /// 1. `[signer]` 所有者账户 | Owner account
-    BurnNFT,
+    /// 1. `[signer]` 所有者、操作员或可撤销集合的权限账户 | Owner, operator or revocable collection authority account
+    /// 2. `[writable]` NFT所有者账户 (接收租金) | NFT owner account (receives rent)
+    /// 3. `[writable]` 集合账户 | Collection account
+    /// 4. `[writable]` 挂单PDA, 存在时被关闭 | Listing PDA, closed when it exists
+    /// 5. `[writable]` 挂单卖家账户 (接收挂单租金, 通常是当前所有者) | Listing seller account (receives the listing rent, usually the current owner)
+    /// 6. `[]` 操作员授权账户 (可选, 操作员签名时需要) | Operator approval account (optional, required when an operator signs)
+    BurnNFT {
+        /// 挂单PDA的规范bump (由 find_listing_address 得到)
+        /// Canonical bump of the listing PDA (from find_listing_address)
+        listing_bump: u8,
+    },
     
     /// 更新NFT元数据
     /// Update NFT metadata
@@ -103,4 +159,677 @@ pub enum NFTInstruction {
         /// New collection metadata URI
         new_uri: String,
     },
-} 
\ No newline at end of file
+    
+    /// 提议新的集合权限
+    /// Propose a new collection authority
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 集合账户 | Collection account
+    /// 1. `[signer]` 当前权限账户 | Current authority account
+    ProposeCollectionAuthority {
+        /// 新权限 (None 取消待定提议)
+        /// New authority (None cancels the pending proposal)
+        new_authority: Option<Pubkey>,
+    },
+    
+    /// 接受集合权限
+    /// Accept collection authority
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 集合账户 | Collection account
+    /// 1. `[signer]` 待定权限账户 | Pending authority account
+    AcceptCollectionAuthority,
+    
+    /// 授权代理转移NFT
+    /// Approve a delegate to transfer the NFT
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` NFT账户 | NFT account
+    /// 1. `[signer]` 所有者账户 | Owner account
+    ApproveDelegate {
+        /// 代理
+        /// Delegate
+        delegate: Pubkey,
+    },
+    
+    /// 撤销代理
+    /// Revoke the delegate
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` NFT账户 | NFT account
+    /// 1. `[signer]` 所有者账户 | Owner account
+    RevokeDelegate,
+    
+    /// 设置集合范围的操作员授权
+    /// Set a collection-wide operator approval
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 操作员授权账户 (PDA) | Operator approval account (PDA)
+    /// 1. `[writable, signer]` 所有者账户 | Owner account
+    /// 2. `[]` 集合账户 | Collection account
+    /// 3. `[]` 系统程序 | System program
+    SetOperatorApproval {
+        /// 操作员
+        /// Operator
+        operator: Pubkey,
+        
+        /// 授权或撤销
+        /// Approve or revoke
+        approved: bool,
+        
+        /// 过期时间戳 (None 表示永不过期)
+        /// Expiry timestamp (None means no expiry)
+        expires_at: Option<i64>,
+        
+        /// 操作员授权PDA的规范bump (由 find_operator_approval_address 得到)
+        /// Canonical bump of the operator approval PDA (from find_operator_approval_address)
+        bump: u8,
+    },
+    
+    /// 设置集合冻结权限
+    /// Set collection freeze authority
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 集合账户 | Collection account
+    /// 1. `[signer]` 集合权限账户 | Collection authority account
+    SetFreezeAuthority {
+        /// 新冻结权限 (None 表示移除)
+        /// New freeze authority (None removes it)
+        freeze_authority: Option<Pubkey>,
+    },
+    
+    /// 冻结NFT
+    /// Freeze NFT
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` NFT账户 | NFT account
+    /// 1. `[]` 集合账户 | Collection account
+    /// 2. `[signer]` 冻结权限账户 | Freeze authority account
+    FreezeNFT,
+    
+    /// 解冻NFT
+    /// Thaw NFT
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` NFT账户 | NFT account
+    /// 1. `[]` 集合账户 | Collection account
+    /// 2. `[signer]` 冻结权限账户 | Freeze authority account
+    ThawNFT,
+    
+    /// 以固定价格挂单NFT (NFT留在卖家名下)
+    /// List an NFT at a fixed price (the NFT stays with the seller)
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 挂单账户 (PDA) | Listing account (PDA)
+    /// 1. `[]` NFT账户 | NFT account
+    /// 2. `[]` 集合账户 | Collection account
+    /// 3. `[writable, signer]` 卖家账户 | Seller account
+    /// 4. `[]` 系统程序 | System program
+    ListNFT {
+        /// 价格 (lamports)
+        /// Price (lamports)
+        price: u64,
+        
+        /// 挂单PDA的规范bump (由 find_listing_address 得到)
+        /// Canonical bump of the listing PDA (from find_listing_address)
+        bump: u8,
+    },
+    
+    /// 取消挂单
+    /// Delist an NFT
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 挂单账户 | Listing account
+    /// 1. `[writable, signer]` 卖家账户 | Seller account
+    DelistNFT,
+    
+    /// 购买挂单的NFT并支付版税
+    /// Buy a listed NFT and pay royalties
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 挂单账户 | Listing account
+    /// 1. `[writable]` NFT账户 | NFT account
+    /// 2. `[]` 集合账户 | Collection account
+    /// 3. `[writable, signer]` 买家账户 | Buyer account
+    /// 4. `[writable]` 卖家账户 | Seller account
+    /// 5. `[]` 系统程序 | System program
+    /// 6. ..`[writable]` 版税接收账户: 按顺序列出已验证创作者, 没有时为集合权限
+    ///    Royalty recipient accounts: verified creators in order, or the collection authority if none
+    BuyNFT {
+        /// 买家同意支付的价格, 必须与挂单价格一致
+        /// Price the buyer agrees to pay, must match the listing price
+        price: u64,
+    },
+    
+    /// 创作者签名确认
+    /// Creator signs to verify themselves
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 集合账户 | Collection account
+    /// 1. `[signer]` 创作者账户 | Creator account
+    SignCreator,
+    
+    /// 将旧版布局的集合或NFT账户迁移到当前版本 (任何人都可以发起, 付款人支付新增租金)
+    /// Migrate a collection or NFT account from an old layout to the current version (anyone may call, the payer covers extra rent)
+    ///
+    /// 账户:
+    /// Accounts:
+    /// 0. `[writable]` 待迁移账户 | Account to migrate
+    /// 1. `[writable, signer]` 付款人账户 | Payer account
+    /// 2. `[]` 系统程序 | System program
+    /// 3. `[]` 集合的原始权限 (可选, 集合权限已转移时用于推导bump, 并记录为 `original_authority`) | Original collection authority (optional, used to derive the bump after an authority transfer and recorded as `original_authority`)
+    MigrateAccount,
+}
+
+/// 指令账户表中的一项; 构建指令和生成IDL都使用这些表
+/// An entry of an instruction's account table; both the instruction builders and the IDL use these tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstructionAccount {
+    /// 账户名称 (snake_case)
+    /// Account name (snake_case)
+    pub name: &'static str,
+    
+    /// 是否可写
+    /// Whether the account is writable
+    pub writable: bool,
+    
+    /// 是否需要签名
+    /// Whether the account must sign
+    pub signer: bool,
+    
+    /// 是否可选 (可选账户位于表尾, 可以省略)
+    /// Whether the account is optional (optional accounts come last and may be omitted)
+    pub optional: bool,
+}
+
+impl InstructionAccount {
+    const fn readonly(name: &'static str) -> Self {
+        Self { name, writable: false, signer: false, optional: false }
+    }
+    
+    const fn writable(name: &'static str) -> Self {
+        Self { name, writable: true, signer: false, optional: false }
+    }
+    
+    const fn signer(self) -> Self {
+        Self { signer: true, ..self }
+    }
+    
+    const fn optional(self) -> Self {
+        Self { optional: true, ..self }
+    }
+    
+    /// 为给定地址生成账户元数据
+    /// Build the account meta for the given address
+    pub fn meta(&self, pubkey: Pubkey) -> AccountMeta {
+        AccountMeta { pubkey, is_signer: self.signer, is_writable: self.writable }
+    }
+}
+
+/// `InitializeCollection` 的账户
+/// Accounts of `InitializeCollection`
+pub const INITIALIZE_COLLECTION_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("collection"),
+    InstructionAccount::writable("authority").signer(),
+    InstructionAccount::readonly("system_program"),
+    InstructionAccount::writable("payer").signer().optional(),
+];
+
+/// `MintNFT` 的账户
+/// Accounts of `MintNFT`
+pub const MINT_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("collection"),
+    InstructionAccount::writable("nft"),
+    InstructionAccount::writable("authority").signer(),
+    InstructionAccount::readonly("system_program"),
+    InstructionAccount::writable("payer").signer().optional(),
+];
+
+/// `TransferNFT` 的账户
+/// Accounts of `TransferNFT`
+pub const TRANSFER_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("signer").signer(),
+    InstructionAccount::readonly("new_owner"),
+    InstructionAccount::readonly("collection"),
+    InstructionAccount::writable("listing"),
+    InstructionAccount::writable("listing_seller"),
+    InstructionAccount::readonly("operator_approval").optional(),
+];
+
+/// `BurnNFT` 的账户
+/// Accounts of `BurnNFT`
+pub const BURN_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("signer").signer(),
+    InstructionAccount::writable("owner"),
+    InstructionAccount::writable("collection"),
+    InstructionAccount::writable("listing"),
+    InstructionAccount::writable("listing_seller"),
+    InstructionAccount::readonly("operator_approval").optional(),
+];
+
+/// `UpdateNFTMetadata` 的账户
+/// Accounts of `UpdateNFTMetadata`
+pub const UPDATE_NFT_METADATA_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("owner").signer(),
+    InstructionAccount::readonly("collection"),
+];
+
+/// `UpdateCollectionMetadata` 的账户
+/// Accounts of `UpdateCollectionMetadata`
+pub const UPDATE_COLLECTION_METADATA_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("collection"),
+    InstructionAccount::readonly("authority").signer(),
+];
+
+/// `ProposeCollectionAuthority` 的账户
+/// Accounts of `ProposeCollectionAuthority`
+pub const PROPOSE_COLLECTION_AUTHORITY_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("collection"),
+    InstructionAccount::readonly("current_authority").signer(),
+];
+
+/// `AcceptCollectionAuthority` 的账户
+/// Accounts of `AcceptCollectionAuthority`
+pub const ACCEPT_COLLECTION_AUTHORITY_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("collection"),
+    InstructionAccount::readonly("pending_authority").signer(),
+];
+
+/// `ApproveDelegate` 的账户
+/// Accounts of `ApproveDelegate`
+pub const APPROVE_DELEGATE_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("owner").signer(),
+];
+
+/// `RevokeDelegate` 的账户
+/// Accounts of `RevokeDelegate`
+pub const REVOKE_DELEGATE_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("owner").signer(),
+];
+
+/// `SetOperatorApproval` 的账户
+/// Accounts of `SetOperatorApproval`
+pub const SET_OPERATOR_APPROVAL_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("operator_approval"),
+    InstructionAccount::writable("owner").signer(),
+    InstructionAccount::readonly("collection"),
+    InstructionAccount::readonly("system_program"),
+];
+
+/// `SetFreezeAuthority` 的账户
+/// Accounts of `SetFreezeAuthority`
+pub const SET_FREEZE_AUTHORITY_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("collection"),
+    InstructionAccount::readonly("collection_authority").signer(),
+];
+
+/// `FreezeNFT` 的账户
+/// Accounts of `FreezeNFT`
+pub const FREEZE_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("collection"),
+    InstructionAccount::readonly("freeze_authority").signer(),
+];
+
+/// `ThawNFT` 的账户
+/// Accounts of `ThawNFT`
+pub const THAW_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("collection"),
+    InstructionAccount::readonly("freeze_authority").signer(),
+];
+
+/// `ListNFT` 的账户
+/// Accounts of `ListNFT`
+pub const LIST_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("listing"),
+    InstructionAccount::readonly("nft"),
+    InstructionAccount::readonly("collection"),
+    InstructionAccount::writable("seller").signer(),
+    InstructionAccount::readonly("system_program"),
+];
+
+/// `DelistNFT` 的账户
+/// Accounts of `DelistNFT`
+pub const DELIST_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("listing"),
+    InstructionAccount::writable("seller").signer(),
+];
+
+/// `BuyNFT` 的固定账户, 版税接收账户 (可写) 跟在其后
+/// Fixed accounts of `BuyNFT`, the (writable) royalty recipient accounts follow them
+pub const BUY_NFT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("listing"),
+    InstructionAccount::writable("nft"),
+    InstructionAccount::readonly("collection"),
+    InstructionAccount::writable("buyer").signer(),
+    InstructionAccount::writable("seller"),
+    InstructionAccount::readonly("system_program"),
+];
+
+/// `SignCreator` 的账户
+/// Accounts of `SignCreator`
+pub const SIGN_CREATOR_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("collection"),
+    InstructionAccount::readonly("creator").signer(),
+];
+
+/// `MigrateAccount` 的账户
+/// Accounts of `MigrateAccount`
+pub const MIGRATE_ACCOUNT_ACCOUNTS: &[InstructionAccount] = &[
+    InstructionAccount::writable("account"),
+    InstructionAccount::writable("payer").signer(),
+    InstructionAccount::readonly("system_program"),
+    InstructionAccount::readonly("original_authority").optional(),
+];
+
+/// 按指令标签排列的账户表
+/// Account tables in instruction tag order
+pub const INSTRUCTION_ACCOUNTS: [&[InstructionAccount]; 19] = [
+    INITIALIZE_COLLECTION_ACCOUNTS,
+    MINT_NFT_ACCOUNTS,
+    TRANSFER_NFT_ACCOUNTS,
+    BURN_NFT_ACCOUNTS,
+    UPDATE_NFT_METADATA_ACCOUNTS,
+    UPDATE_COLLECTION_METADATA_ACCOUNTS,
+    PROPOSE_COLLECTION_AUTHORITY_ACCOUNTS,
+    ACCEPT_COLLECTION_AUTHORITY_ACCOUNTS,
+    APPROVE_DELEGATE_ACCOUNTS,
+    REVOKE_DELEGATE_ACCOUNTS,
+    SET_OPERATOR_APPROVAL_ACCOUNTS,
+    SET_FREEZE_AUTHORITY_ACCOUNTS,
+    FREEZE_NFT_ACCOUNTS,
+    THAW_NFT_ACCOUNTS,
+    LIST_NFT_ACCOUNTS,
+    DELIST_NFT_ACCOUNTS,
+    BUY_NFT_ACCOUNTS,
+    SIGN_CREATOR_ACCOUNTS,
+    MIGRATE_ACCOUNT_ACCOUNTS,
+];
+
+/// 按账户表为地址生成账户元数据, 省略的可选账户不传地址即可
+/// Build account metas for the addresses from an account table, omitted optional accounts are simply not passed
+pub(crate) fn account_metas(table: &[InstructionAccount], keys: impl IntoIterator<Item = Pubkey>) -> Vec<AccountMeta> {
+    let metas: Vec<AccountMeta> = table.iter().zip(keys).map(|(account, key)| account.meta(key)).collect();
+    debug_assert!(
+        table[metas.len()..].iter().all(|account| account.optional),
+        "缺少必需账户 | a required account is missing"
+    );
+    metas
+}
+
+/// 序列化指令数据并构建指令
+/// Serialize instruction data and build the instruction
+pub(crate) fn build_instruction(program_id: &Pubkey, data: &NFTInstruction, accounts: Vec<AccountMeta>) -> Instruction {
+    let data = data.try_to_vec().expect("序列化到内存不会失败 | serializing into memory cannot fail");
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// 创建 `InitializeCollection` 指令, 付款人与权限相同时由权限支付租金
+/// Creates an `InitializeCollection` instruction, the authority pays rent when it is also the payer
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_collection(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    royalty_percentage: u8,
+    is_mutable: bool,
+    transfer_mode: TransferMode,
+    creators: Vec<Creator>,
+    max_supply: Option<u64>,
+    serial_mode: SerialMode,
+) -> Instruction {
+    let (collection, bump) = find_collection_address(program_id, authority, name);
+    let data = NFTInstruction::InitializeCollection {
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        uri: uri.to_string(),
+        royalty_percentage,
+        is_mutable,
+        transfer_mode,
+        creators,
+        max_supply,
+        serial_mode,
+        bump,
+    };
+    let accounts =
+        account_metas(INITIALIZE_COLLECTION_ACCOUNTS, [collection, *authority, system_program::id(), *payer]);
+    build_instruction(program_id, &data, accounts)
+}
+
+/// 创建 `MintNFT` 指令; 顺序模式的集合需传入 total_minted + 1 作为序列号
+/// Creates a `MintNFT` instruction; sequential collections must pass total_minted + 1 as the serial number
+pub fn mint_nft(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    collection: &Pubkey,
+    authority: &Pubkey,
+    serial_number: u64,
+    metadata_uri: &str,
+    recipient: Option<Pubkey>,
+) -> Instruction {
+    let (nft, bump) = find_nft_address(program_id, collection, serial_number);
+    let data = NFTInstruction::MintNFT {
+        metadata_uri: metadata_uri.to_string(),
+        serial_number,
+        bump,
+        recipient,
+    };
+    let accounts = account_metas(MINT_NFT_ACCOUNTS, [*collection, nft, *authority, system_program::id(), *payer]);
+    build_instruction(program_id, &data, accounts)
+}
+
+/// 创建 `TransferNFT` 指令, `listing_seller` 是挂单的卖家 (通常是当前所有者), 操作员签名时需传入操作员授权账户
+/// Creates a `TransferNFT` instruction, `listing_seller` is the listing's seller (usually the current owner), pass the
+/// operator approval account when an operator signs
+pub fn transfer_nft(
+    program_id: &Pubkey,
+    nft: &Pubkey,
+    collection: &Pubkey,
+    signer: &Pubkey,
+    new_owner: &Pubkey,
+    listing_seller: &Pubkey,
+    operator_approval: Option<&Pubkey>,
+) -> Instruction {
+    let (listing, listing_bump) = find_listing_address(program_id, nft);
+    let keys = [*nft, *signer, *new_owner, *collection, listing, *listing_seller];
+    let accounts = account_metas(TRANSFER_NFT_ACCOUNTS, keys.into_iter().chain(operator_approval.copied()));
+    build_instruction(program_id, &NFTInstruction::TransferNFT { new_owner: *new_owner, listing_bump }, accounts)
+}
+
+/// 创建 `BurnNFT` 指令, `owner` 是NFT的当前所有者 (接收租金), `listing_seller` 是挂单的卖家 (通常是当前所有者),
+/// 操作员签名时需传入操作员授权账户
+/// Creates a `BurnNFT` instruction, `owner` is the NFT's current owner (receives the rent), `listing_seller` is the
+/// listing's seller (usually the current owner), pass the operator approval account when an operator signs
+#[allow(clippy::too_many_arguments)]
+pub fn burn_nft(
+    program_id: &Pubkey,
+    nft: &Pubkey,
+    collection: &Pubkey,
+    signer: &Pubkey,
+    owner: &Pubkey,
+    listing_seller: &Pubkey,
+    operator_approval: Option<&Pubkey>,
+) -> Instruction {
+    let (listing, listing_bump) = find_listing_address(program_id, nft);
+    let keys = [*nft, *signer, *owner, *collection, listing, *listing_seller];
+    let accounts = account_metas(BURN_NFT_ACCOUNTS, keys.into_iter().chain(operator_approval.copied()));
+    build_instruction(program_id, &NFTInstruction::BurnNFT { listing_bump }, accounts)
+}
+
+/// 创建 `UpdateNFTMetadata` 指令
+/// Creates an `UpdateNFTMetadata` instruction
+pub fn update_nft_metadata(
+    program_id: &Pubkey,
+    nft: &Pubkey,
+    owner: &Pubkey,
+    collection: &Pubkey,
+    new_metadata_uri: &str,
+) -> Instruction {
+    let data = NFTInstruction::UpdateNFTMetadata { new_metadata_uri: new_metadata_uri.to_string() };
+    let accounts = account_metas(UPDATE_NFT_METADATA_ACCOUNTS, [*nft, *owner, *collection]);
+    build_instruction(program_id, &data, accounts)
+}
+
+/// 创建 `UpdateCollectionMetadata` 指令
+/// Creates an `UpdateCollectionMetadata` instruction
+pub fn update_collection_metadata(
+    program_id: &Pubkey,
+    collection: &Pubkey,
+    authority: &Pubkey,
+    new_uri: &str,
+) -> Instruction {
+    let data = NFTInstruction::UpdateCollectionMetadata { new_uri: new_uri.to_string() };
+    let accounts = account_metas(UPDATE_COLLECTION_METADATA_ACCOUNTS, [*collection, *authority]);
+    build_instruction(program_id, &data, accounts)
+}
+
+/// 创建 `ProposeCollectionAuthority` 指令, 传入 `None` 取消提议
+/// Creates a `ProposeCollectionAuthority` instruction, pass `None` to cancel the proposal
+pub fn propose_collection_authority(
+    program_id: &Pubkey,
+    collection: &Pubkey,
+    authority: &Pubkey,
+    new_authority: Option<Pubkey>,
+) -> Instruction {
+    let accounts = account_metas(PROPOSE_COLLECTION_AUTHORITY_ACCOUNTS, [*collection, *authority]);
+    build_instruction(program_id, &NFTInstruction::ProposeCollectionAuthority { new_authority }, accounts)
+}
+
+/// 创建 `AcceptCollectionAuthority` 指令
+/// Creates an `AcceptCollectionAuthority` instruction
+pub fn accept_collection_authority(program_id: &Pubkey, collection: &Pubkey, pending_authority: &Pubkey) -> Instruction {
+    let accounts = account_metas(ACCEPT_COLLECTION_AUTHORITY_ACCOUNTS, [*collection, *pending_authority]);
+    build_instruction(program_id, &NFTInstruction::AcceptCollectionAuthority, accounts)
+}
+
+/// 创建 `ApproveDelegate` 指令
+/// Creates an `ApproveDelegate` instruction
+pub fn approve_delegate(program_id: &Pubkey, nft: &Pubkey, owner: &Pubkey, delegate: &Pubkey) -> Instruction {
+    let accounts = account_metas(APPROVE_DELEGATE_ACCOUNTS, [*nft, *owner]);
+    build_instruction(program_id, &NFTInstruction::ApproveDelegate { delegate: *delegate }, accounts)
+}
+
+/// 创建 `RevokeDelegate` 指令
+/// Creates a `RevokeDelegate` instruction
+pub fn revoke_delegate(program_id: &Pubkey, nft: &Pubkey, owner: &Pubkey) -> Instruction {
+    let accounts = account_metas(REVOKE_DELEGATE_ACCOUNTS, [*nft, *owner]);
+    build_instruction(program_id, &NFTInstruction::RevokeDelegate, accounts)
+}
+
+/// 创建 `SetOperatorApproval` 指令
+/// Creates a `SetOperatorApproval` instruction
+pub fn set_operator_approval(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    collection: &Pubkey,
+    operator: &Pubkey,
+    approved: bool,
+    expires_at: Option<i64>,
+) -> Instruction {
+    let (approval, bump) = find_operator_approval_address(program_id, owner, collection, operator);
+    let data = NFTInstruction::SetOperatorApproval { operator: *operator, approved, expires_at, bump };
+    let accounts =
+        account_metas(SET_OPERATOR_APPROVAL_ACCOUNTS, [approval, *owner, *collection, system_program::id()]);
+    build_instruction(program_id, &data, accounts)
+}
+
+/// 创建 `SetFreezeAuthority` 指令, 传入 `None` 移除冻结权限
+/// Creates a `SetFreezeAuthority` instruction, pass `None` to remove the freeze authority
+pub fn set_freeze_authority(
+    program_id: &Pubkey,
+    collection: &Pubkey,
+    authority: &Pubkey,
+    freeze_authority: Option<Pubkey>,
+) -> Instruction {
+    let accounts = account_metas(SET_FREEZE_AUTHORITY_ACCOUNTS, [*collection, *authority]);
+    build_instruction(program_id, &NFTInstruction::SetFreezeAuthority { freeze_authority }, accounts)
+}
+
+/// 创建 `FreezeNFT` 指令
+/// Creates a `FreezeNFT` instruction
+pub fn freeze_nft(program_id: &Pubkey, nft: &Pubkey, collection: &Pubkey, freeze_authority: &Pubkey) -> Instruction {
+    let accounts = account_metas(FREEZE_NFT_ACCOUNTS, [*nft, *collection, *freeze_authority]);
+    build_instruction(program_id, &NFTInstruction::FreezeNFT, accounts)
+}
+
+/// 创建 `ThawNFT` 指令
+/// Creates a `ThawNFT` instruction
+pub fn thaw_nft(program_id: &Pubkey, nft: &Pubkey, collection: &Pubkey, freeze_authority: &Pubkey) -> Instruction {
+    let accounts = account_metas(THAW_NFT_ACCOUNTS, [*nft, *collection, *freeze_authority]);
+    build_instruction(program_id, &NFTInstruction::ThawNFT, accounts)
+}
+
+/// 创建 `ListNFT` 指令
+/// Creates a `ListNFT` instruction
+pub fn list_nft(program_id: &Pubkey, nft: &Pubkey, collection: &Pubkey, seller: &Pubkey, price: u64) -> Instruction {
+    let (listing, bump) = find_listing_address(program_id, nft);
+    let accounts = account_metas(LIST_NFT_ACCOUNTS, [listing, *nft, *collection, *seller, system_program::id()]);
+    build_instruction(program_id, &NFTInstruction::ListNFT { price, bump }, accounts)
+}
+
+/// 创建 `DelistNFT` 指令
+/// Creates a `DelistNFT` instruction
+pub fn delist_nft(program_id: &Pubkey, nft: &Pubkey, seller: &Pubkey) -> Instruction {
+    let (listing, _) = find_listing_address(program_id, nft);
+    let accounts = account_metas(DELIST_NFT_ACCOUNTS, [listing, *seller]);
+    build_instruction(program_id, &NFTInstruction::DelistNFT, accounts)
+}
+
+/// 创建 `BuyNFT` 指令; 版税接收者为按顺序排列的已验证创作者, 没有时为集合权限
+/// Creates a `BuyNFT` instruction; royalty recipients are the verified creators in order, or the collection authority if none
+pub fn buy_nft(
+    program_id: &Pubkey,
+    nft: &Pubkey,
+    collection: &Pubkey,
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    royalty_recipients: &[Pubkey],
+    price: u64,
+) -> Instruction {
+    let (listing, _) = find_listing_address(program_id, nft);
+    let mut accounts =
+        account_metas(BUY_NFT_ACCOUNTS, [listing, *nft, *collection, *buyer, *seller, system_program::id()]);
+    accounts.extend(royalty_recipients.iter().map(|recipient| AccountMeta::new(*recipient, false)));
+    build_instruction(program_id, &NFTInstruction::BuyNFT { price }, accounts)
+}
+
+/// 创建 `SignCreator` 指令
+/// Creates a `SignCreator` instruction
+pub fn sign_creator(program_id: &Pubkey, collection: &Pubkey, creator: &Pubkey) -> Instruction {
+    let accounts = account_metas(SIGN_CREATOR_ACCOUNTS, [*collection, *creator]);
+    build_instruction(program_id, &NFTInstruction::SignCreator, accounts)
+}
+
+/// 创建 `MigrateAccount` 指令, 集合权限已转移时需传入原始权限
+/// Creates a `MigrateAccount` instruction, pass the original authority when a collection's authority has been transferred
+pub fn migrate_account(
+    program_id: &Pubkey,
+    account: &Pubkey,
+    payer: &Pubkey,
+    original_authority: Option<&Pubkey>,
+) -> Instruction {
+    let keys = [*account, *payer, system_program::id()];
+    let accounts = account_metas(MIGRATE_ACCOUNT_ACCOUNTS, keys.into_iter().chain(original_authority.copied()));
+    build_instruction(program_id, &NFTInstruction::MigrateAccount, accounts)
+}
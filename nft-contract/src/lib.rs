@@ -1,32 +1,43 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     clock::Clock,
-    entrypoint,
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
-    pubkey::Pubkey,
-    system_instruction,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+    system_instruction, system_program,
     program::{invoke, invoke_signed},
     sysvar::{rent::Rent, Sysvar},
 };
 
+pub mod cpi;
 pub mod error;
+pub mod event;
 pub mod instruction;
 pub mod state;
 
+#[cfg(test)]
+mod test;
+
 use error::NFTError;
+use event::NFTEvent;
 use instruction::NFTInstruction;
 use state::{
-    CollectionAccount, NFTAccount, 
-    COLLECTION_ACCOUNT_SIZE, NFT_ACCOUNT_SIZE,
-    COLLECTION_SEED_PREFIX, NFT_SEED_PREFIX,
+    collection_name_seed, find_collection_address, find_nft_address, is_uninitialized, AccountKey,
+    CollectionAccount, CollectionAccountV0, CollectionAccountV1, CollectionAccountV2, Creator, Listing, NFTAccount,
+    NFTAccountV0, NFTAccountV1, NFTAccountV2, OperatorApproval, ProgramAccount, SerialMode, TransferMode,
+    COLLECTION_ACCOUNT_SIZE, COLLECTION_ACCOUNT_V0_SIZE, COLLECTION_ACCOUNT_V1_SIZE, COLLECTION_ACCOUNT_V2_SIZE,
+    LISTING_SIZE, NFT_ACCOUNT_SIZE, NFT_ACCOUNT_V0_SIZE, NFT_ACCOUNT_V1_SIZE, NFT_ACCOUNT_V2_SIZE,
+    OPERATOR_APPROVAL_SIZE,
+    COLLECTION_SEED_PREFIX, LISTING_SEED_PREFIX, NFT_SEED_PREFIX, OPERATOR_APPROVAL_SEED_PREFIX,
+    ALLOWED_URI_SCHEMES, MAX_CREATORS, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
 };
 
-// 程序入口点
-// Program entry point
-entrypoint!(process_instruction);
+// 程序入口点 (作为其他程序的依赖时通过 no-entrypoint 关闭)
+// Program entry point (disabled with no-entrypoint when used as a dependency of another program)
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
 
 /// 处理指令
 /// Process instruction
@@ -44,24 +55,25 @@ pub fn process_instruction(
     // Call the appropriate processing function based on instruction type
     match instruction {
         NFTInstruction::InitializeCollection { 
-            name, symbol, uri, royalty_percentage, is_mutable 
+            name, symbol, uri, royalty_percentage, is_mutable, transfer_mode, creators, max_supply, serial_mode, bump 
         } => {
             msg!("指令: 初始化集合 | Instruction: Initialize Collection");
             process_initialize_collection(
-                program_id, accounts, name, symbol, uri, royalty_percentage, is_mutable
+                program_id, accounts, name, symbol, uri, royalty_percentage, is_mutable, transfer_mode, creators,
+                max_supply, serial_mode, bump,
             )
         }
-        NFTInstruction::MintNFT { metadata_uri, serial_number } => {
+        NFTInstruction::MintNFT { metadata_uri, serial_number, bump, recipient } => {
             msg!("指令: 铸造NFT | Instruction: Mint NFT");
-            process_mint_nft(program_id, accounts, metadata_uri, serial_number)
+            process_mint_nft(program_id, accounts, metadata_uri, serial_number, bump, recipient)
         }
-        NFTInstruction::TransferNFT { new_owner } => {
+        NFTInstruction::TransferNFT { new_owner, listing_bump } => {
             msg!("指令: 转移NFT | Instruction: Transfer NFT");
-            process_transfer_nft(program_id, accounts, new_owner)
+            process_transfer_nft(program_id, accounts, new_owner, listing_bump)
         }
-        NFTInstruction::BurnNFT => {
+        NFTInstruction::BurnNFT { listing_bump } => {
             msg!("指令: 销毁NFT | Instruction: Burn NFT");
-            process_burn_nft(program_id, accounts)
+            process_burn_nft(program_id, accounts, listing_bump)
         }
         NFTInstruction::UpdateNFTMetadata { new_metadata_uri } => {
             msg!("指令: 更新NFT元数据 | Instruction: Update NFT Metadata");
@@ -71,11 +83,64 @@ pub fn process_instruction(
             msg!("指令: 更新集合元数据 | Instruction: Update Collection Metadata");
             process_update_collection_metadata(program_id, accounts, new_uri)
         }
+        NFTInstruction::ProposeCollectionAuthority { new_authority } => {
+            msg!("指令: 提议集合权限 | Instruction: Propose Collection Authority");
+            process_propose_collection_authority(program_id, accounts, new_authority)
+        }
+        NFTInstruction::AcceptCollectionAuthority => {
+            msg!("指令: 接受集合权限 | Instruction: Accept Collection Authority");
+            process_accept_collection_authority(program_id, accounts)
+        }
+        NFTInstruction::ApproveDelegate { delegate } => {
+            msg!("指令: 授权代理 | Instruction: Approve Delegate");
+            process_set_delegate(program_id, accounts, Some(delegate))
+        }
+        NFTInstruction::RevokeDelegate => {
+            msg!("指令: 撤销代理 | Instruction: Revoke Delegate");
+            process_set_delegate(program_id, accounts, None)
+        }
+        NFTInstruction::SetOperatorApproval { operator, approved, expires_at, bump } => {
+            msg!("指令: 设置操作员授权 | Instruction: Set Operator Approval");
+            process_set_operator_approval(program_id, accounts, operator, approved, expires_at, bump)
+        }
+        NFTInstruction::SetFreezeAuthority { freeze_authority } => {
+            msg!("指令: 设置冻结权限 | Instruction: Set Freeze Authority");
+            process_set_freeze_authority(program_id, accounts, freeze_authority)
+        }
+        NFTInstruction::FreezeNFT => {
+            msg!("指令: 冻结NFT | Instruction: Freeze NFT");
+            process_set_frozen(program_id, accounts, true)
+        }
+        NFTInstruction::ThawNFT => {
+            msg!("指令: 解冻NFT | Instruction: Thaw NFT");
+            process_set_frozen(program_id, accounts, false)
+        }
+        NFTInstruction::ListNFT { price, bump } => {
+            msg!("指令: 挂单NFT | Instruction: List NFT");
+            process_list_nft(program_id, accounts, price, bump)
+        }
+        NFTInstruction::DelistNFT => {
+            msg!("指令: 取消挂单 | Instruction: Delist NFT");
+            process_delist_nft(program_id, accounts)
+        }
+        NFTInstruction::BuyNFT { price } => {
+            msg!("指令: 购买NFT | Instruction: Buy NFT");
+            process_buy_nft(program_id, accounts, price)
+        }
+        NFTInstruction::SignCreator => {
+            msg!("指令: 创作者签名 | Instruction: Sign Creator");
+            process_sign_creator(program_id, accounts)
+        }
+        NFTInstruction::MigrateAccount => {
+            msg!("指令: 迁移账户 | Instruction: Migrate Account");
+            process_migrate_account(program_id, accounts)
+        }
     }
 }
 
 /// 处理初始化集合指令
 /// Process initialize collection instruction
+#[allow(clippy::too_many_arguments)]
 fn process_initialize_collection(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -84,6 +149,11 @@ fn process_initialize_collection(
     uri: String,
     royalty_percentage: u8,
     is_mutable: bool,
+    transfer_mode: TransferMode,
+    mut creators: Vec<Creator>,
+    max_supply: Option<u64>,
+    serial_mode: SerialMode,
+    bump_seed: u8,
 ) -> ProgramResult {
     // 获取账户
     // Get accounts
@@ -92,9 +162,13 @@ fn process_initialize_collection(
     let authority = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
     
+    // 可选的付款人账户, 未提供时由权限账户支付租金
+    // Optional payer account, the authority pays rent when it is absent
+    let payer = account_info_iter.next().unwrap_or(authority);
+    
     // 验证账户
     // Validate accounts
-    if !authority.is_signer {
+    if !authority.is_signer || !payer.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
@@ -104,61 +178,51 @@ fn process_initialize_collection(
         return Err(NFTError::InvalidRoyaltyPercentage.into());
     }
     
+    // 验证名称、符号和URI
+    // Validate name, symbol and URI
+    validate_length(&name, MAX_NAME_LENGTH, NFTError::NameTooLong)?;
+    validate_length(&symbol, MAX_SYMBOL_LENGTH, NFTError::SymbolTooLong)?;
+    validate_uri(&uri)?;
+    
+    // 验证创作者列表, 只有签名的权限账户可以直接标记为已验证
+    // Validate creators, only the signing authority may be marked verified up front
+    validate_creators(&creators)?;
+    for creator in creators.iter_mut() {
+        creator.verified = creator.address == *authority.key;
+    }
+    
     // 验证集合账户是否已初始化
     // Check if collection account is already initialized
-    if !collection_account.data.borrow().iter().all(|&x| x == 0) {
+    if !is_uninitialized(&collection_account.data.borrow()) {
         return Err(NFTError::AlreadyInitialized.into());
     }
     
-    // 计算集合PDA
-    // Calculate collection PDA
-    let collection_seeds = [
-        COLLECTION_SEED_PREFIX,
-        authority.key.as_ref(),
-        name.as_bytes(),
-    ];
-    let (expected_collection_address, bump_seed) = 
-        Pubkey::find_program_address(&collection_seeds, program_id);
-    
-    // 验证集合地址
-    // Validate collection address
-    if expected_collection_address != *collection_account.key {
-        return Err(ProgramError::InvalidAccountData);
-    }
+    // 用传入的bump计算并验证集合PDA
+    // Calculate and validate the collection PDA with the supplied bump
+    let name_seed = collection_name_seed(&name);
+    verify_program_address(
+        &[COLLECTION_SEED_PREFIX, authority.key.as_ref(), &name_seed],
+        bump_seed,
+        program_id,
+        collection_account.key,
+    )?;
     
     // 创建集合账户
     // Create collection account
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(COLLECTION_ACCOUNT_SIZE);
-    
-    // 创建账户
-    // Create account
-    invoke_signed(
-        &system_instruction::create_account(
-            authority.key,
-            collection_account.key,
-            rent_lamports,
-            COLLECTION_ACCOUNT_SIZE as u64,
-            program_id,
-        ),
+    create_program_account(
+        payer,
+        collection_account,
+        system_program,
+        COLLECTION_ACCOUNT_SIZE,
+        program_id,
         &[
-            authority.clone(),
-            collection_account.clone(),
-            system_program.clone(),
-        ],
-        &[&[
             COLLECTION_SEED_PREFIX,
             authority.key.as_ref(),
-            name.as_bytes(),
+            &name_seed,
             &[bump_seed],
-        ]],
+        ],
     )?;
     
-    // 获取当前时间戳
-    // Get current timestamp
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp;
-    
     // 创建集合数据
     // Create collection data
     let collection_data = CollectionAccount::new(
@@ -168,12 +232,25 @@ fn process_initialize_collection(
         uri,
         royalty_percentage,
         is_mutable,
+        transfer_mode,
+        creators,
+        max_supply,
+        serial_mode,
+        bump_seed,
     );
     
     // 序列化并存储数据
     // Serialize and store data
-    collection_data.serialize(&mut *collection_account.data.borrow_mut())?;
-    
+    collection_data.save(&mut collection_account.data.borrow_mut())?;
+    
+    NFTEvent::CollectionInitialized {
+        collection: *collection_account.key,
+        authority: collection_data.authority,
+        name: collection_data.name,
+        symbol: collection_data.symbol,
+        uri: collection_data.uri,
+    }
+    .emit();
     msg!("NFT集合已初始化 | NFT collection initialized");
     Ok(())
 }
@@ -185,6 +262,8 @@ fn process_mint_nft(
     accounts: &[AccountInfo],
     metadata_uri: String,
     serial_number: u64,
+    bump_seed: u8,
+    recipient: Option<Pubkey>,
 ) -> ProgramResult {
     // 获取账户
     // Get accounts
@@ -194,12 +273,20 @@ fn process_mint_nft(
     let authority = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
     
+    // 可选的付款人账户, 未提供时由权限账户支付租金
+    // Optional payer account, the authority pays rent when it is absent
+    let payer = account_info_iter.next().unwrap_or(authority);
+    
     // 验证账户
     // Validate accounts
-    if !authority.is_signer {
+    if !authority.is_signer || !payer.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
+    // 验证元数据URI
+    // Validate metadata URI
+    validate_uri(&metadata_uri)?;
+    
     // 验证集合账户
     // Validate collection account
     if collection_account.owner != program_id {
@@ -208,7 +295,7 @@ fn process_mint_nft(
     
     // 反序列化集合数据
     // Deserialize collection data
-    let collection_data = CollectionAccount::try_from_slice(&collection_account.data.borrow())?;
+    let collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
     
     // 验证权限
     // Validate authority
@@ -216,54 +303,48 @@ fn process_mint_nft(
         return Err(NFTError::InsufficientAuthority.into());
     }
     
+    // 验证供应量并确定序列号
+    // Check supply and determine the serial number
+    if collection_data.is_supply_exhausted() {
+        return Err(NFTError::MaxSupplyReached.into());
+    }
+    let serial_number = collection_data.next_serial_number(serial_number)?;
+    
+    // 已销毁的序列号留下墓碑账户, 不能重新铸造
+    // Burned serials leave a tombstone account and cannot be minted again
+    if nft_account.owner == program_id && nft_account.data_is_empty() {
+        return Err(NFTError::SerialNumberBurned.into());
+    }
+    
     // 验证NFT账户是否已初始化
     // Check if NFT account is already initialized
-    if !nft_account.data.borrow().iter().all(|&x| x == 0) {
+    if !is_uninitialized(&nft_account.data.borrow()) {
         return Err(NFTError::AlreadyInitialized.into());
     }
     
-    // 计算NFT PDA
-    // Calculate NFT PDA
-    let nft_seeds = [
-        NFT_SEED_PREFIX,
-        collection_account.key.as_ref(),
-        &serial_number.to_le_bytes(),
-    ];
-    let (expected_nft_address, bump_seed) = 
-        Pubkey::find_program_address(&nft_seeds, program_id);
-    
-    // 验证NFT地址
-    // Validate NFT address
-    if expected_nft_address != *nft_account.key {
-        return Err(ProgramError::InvalidAccountData);
-    }
+    // 用传入的bump计算并验证NFT PDA
+    // Calculate and validate the NFT PDA with the supplied bump
+    verify_program_address(
+        &[NFT_SEED_PREFIX, collection_account.key.as_ref(), &serial_number.to_le_bytes()],
+        bump_seed,
+        program_id,
+        nft_account.key,
+    )?;
     
     // 创建NFT账户
     // Create NFT account
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(NFT_ACCOUNT_SIZE);
-    
-    // 创建账户
-    // Create account
-    invoke_signed(
-        &system_instruction::create_account(
-            authority.key,
-            nft_account.key,
-            rent_lamports,
-            NFT_ACCOUNT_SIZE as u64,
-            program_id,
-        ),
+    create_program_account(
+        payer,
+        nft_account,
+        system_program,
+        NFT_ACCOUNT_SIZE,
+        program_id,
         &[
-            authority.clone(),
-            nft_account.clone(),
-            system_program.clone(),
-        ],
-        &[&[
             NFT_SEED_PREFIX,
             collection_account.key.as_ref(),
             &serial_number.to_le_bytes(),
             &[bump_seed],
-        ]],
+        ],
     )?;
     
     // 获取当前时间戳
@@ -274,23 +355,34 @@ fn process_mint_nft(
     // 创建NFT数据
     // Create NFT data
     let nft_data = NFTAccount::new(
+        recipient.unwrap_or(*authority.key),
         *authority.key,
         *collection_account.key,
         metadata_uri,
         serial_number,
         current_timestamp,
+        bump_seed,
     );
     
     // 序列化并存储数据
     // Serialize and store data
-    nft_data.serialize(&mut *nft_account.data.borrow_mut())?;
+    nft_data.save(&mut nft_account.data.borrow_mut())?;
     
     // 更新集合数据
     // Update collection data
     let mut updated_collection_data = collection_data;
     updated_collection_data.increment_minted();
-    updated_collection_data.serialize(&mut *collection_account.data.borrow_mut())?;
-    
+    updated_collection_data.save(&mut collection_account.data.borrow_mut())?;
+    
+    NFTEvent::NFTMinted {
+        collection: nft_data.collection,
+        nft: *nft_account.key,
+        owner: nft_data.owner,
+        minter: *authority.key,
+        serial_number: nft_data.serial_number,
+        metadata_uri: nft_data.metadata_uri,
+    }
+    .emit();
     msg!("NFT已铸造 | NFT minted");
     Ok(())
 }
@@ -301,34 +393,46 @@ fn process_transfer_nft(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     new_owner: Pubkey,
+    listing_bump: u8,
 ) -> ProgramResult {
     // 获取账户
     // Get accounts
     let account_info_iter = &mut accounts.iter();
     let nft_account = next_account_info(account_info_iter)?;
-    let current_owner = next_account_info(account_info_iter)?;
+    let signer = next_account_info(account_info_iter)?;
+    let new_owner_account = next_account_info(account_info_iter)?;
+    let collection_account = next_account_info(account_info_iter)?;
+    let listing_account = next_account_info(account_info_iter)?;
+    let listing_seller = next_account_info(account_info_iter)?;
+    let operator_approval = account_info_iter.next();
     
     // 验证账户
     // Validate accounts
-    if !current_owner.is_signer {
+    if !signer.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
+    // 验证新所有者账户与指令参数一致
+    // Validate the new owner account matches the instruction argument
+    if *new_owner_account.key != new_owner {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
     // 验证NFT账户
     // Validate NFT account
     if nft_account.owner != program_id {
         return Err(ProgramError::IncorrectProgramId);
     }
     
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
     // 反序列化NFT数据
     // Deserialize NFT data
-    let mut nft_data = NFTAccount::try_from_slice(&nft_account.data.borrow())?;
-    
-    // 验证所有权
-    // Validate ownership
-    if nft_data.owner != *current_owner.key {
-        return Err(NFTError::InvalidOwner.into());
-    }
+    let mut nft_data = NFTAccount::load(&nft_account.data.borrow())?;
     
     // 验证NFT是否已铸造
     // Validate NFT is minted
@@ -336,19 +440,59 @@ fn process_transfer_nft(
         return Err(NFTError::NotMinted.into());
     }
     
+    // 验证NFT未冻结
+    // Validate NFT is not frozen
+    if nft_data.is_frozen {
+        return Err(NFTError::NFTFrozen.into());
+    }
+    
+    // 验证集合
+    // Validate collection
+    if nft_data.collection != *collection_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    // 反序列化集合数据
+    // Deserialize collection data
+    let collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 验证集合允许转移
+    // Validate collection allows transfers
+    if !collection_data.is_transferable() {
+        return Err(NFTError::NonTransferable.into());
+    }
+    
     // 获取当前时间戳
     // Get current timestamp
     let clock = Clock::get()?;
     let current_timestamp = clock.unix_timestamp;
     
+    // 验证所有者、代理或操作员
+    // Validate owner, delegate or operator
+    if !nft_data.can_transfer(signer.key) {
+        validate_operator_approval(
+            program_id,
+            operator_approval,
+            &nft_data,
+            signer.key,
+            current_timestamp,
+        )?;
+    }
+    
+    // 关闭旧所有者的挂单
+    // Close the previous owner's listing
+    close_listing(program_id, nft_account, listing_account, listing_seller, listing_bump)?;
+    
     // 转移NFT
     // Transfer NFT
+    let previous_owner = nft_data.owner;
     nft_data.transfer(new_owner, current_timestamp);
     
     // 序列化并存储数据
     // Serialize and store data
-    nft_data.serialize(&mut *nft_account.data.borrow_mut())?;
+    nft_data.save(&mut nft_account.data.borrow_mut())?;
     
+    NFTEvent::NFTTransferred { nft: *nft_account.key, from: previous_owner, to: new_owner }.emit();
     msg!("NFT已转移 | NFT transferred");
     Ok(())
 }
@@ -358,16 +502,22 @@ fn process_transfer_nft(
 fn process_burn_nft(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    listing_bump: u8,
 ) -> ProgramResult {
     // 获取账户
     // Get accounts
     let account_info_iter = &mut accounts.iter();
     let nft_account = next_account_info(account_info_iter)?;
-    let owner = next_account_info(account_info_iter)?;
+    let signer = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    let collection_account = next_account_info(account_info_iter)?;
+    let listing_account = next_account_info(account_info_iter)?;
+    let listing_seller = next_account_info(account_info_iter)?;
+    let operator_approval = account_info_iter.next();
     
     // 验证账户
     // Validate accounts
-    if !owner.is_signer {
+    if !signer.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
@@ -377,15 +527,15 @@ fn process_burn_nft(
         return Err(ProgramError::IncorrectProgramId);
     }
     
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
     // 反序列化NFT数据
     // Deserialize NFT data
-    let nft_data = NFTAccount::try_from_slice(&nft_account.data.borrow())?;
-    
-    // 验证所有权
-    // Validate ownership
-    if nft_data.owner != *owner.key {
-        return Err(NFTError::InvalidOwner.into());
-    }
+    let nft_data = NFTAccount::load(&nft_account.data.borrow())?;
     
     // 验证NFT是否已铸造
     // Validate NFT is minted
@@ -393,21 +543,65 @@ fn process_burn_nft(
         return Err(NFTError::NotMinted.into());
     }
     
-    // 关闭账户并退还租金
-    // Close account and refund rent
-    let dest_starting_lamports = owner.lamports();
-    **owner.lamports.borrow_mut() = dest_starting_lamports
-        .checked_add(nft_account.lamports())
-        .ok_or(NFTError::Overflow)?;
-    **nft_account.lamports.borrow_mut() = 0;
+    // 验证所有者账户, 退还的租金归所有者
+    // Validate the owner account, the refunded rent belongs to the owner
+    if nft_data.owner != *owner_account.key {
+        return Err(NFTError::InvalidOwner.into());
+    }
+    
+    // 验证NFT未冻结
+    // Validate NFT is not frozen
+    if nft_data.is_frozen {
+        return Err(NFTError::NFTFrozen.into());
+    }
+    
+    // 验证集合
+    // Validate collection
+    if nft_data.collection != *collection_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
     
-    // 清空数据
-    // Clear data
-    let mut data = nft_account.data.borrow_mut();
-    for byte in data.iter_mut() {
-        *byte = 0;
+    // 反序列化集合数据
+    // Deserialize collection data
+    let collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 验证所有者、可撤销集合的权限或操作员
+    // Validate owner, revocable collection authority or operator
+    let is_revoking_authority =
+        collection_data.is_revocable() && collection_data.authority == *signer.key;
+    if nft_data.owner != *signer.key && !is_revoking_authority {
+        let clock = Clock::get()?;
+        validate_operator_approval(
+            program_id,
+            operator_approval,
+            &nft_data,
+            signer.key,
+            clock.unix_timestamp,
+        )?;
     }
     
+    // 关闭挂单
+    // Close the listing
+    close_listing(program_id, nft_account, listing_account, listing_seller, listing_bump)?;
+    
+    // 将NFT账户缩减为墓碑, 向所有者退还多余租金, 防止序列号被重新铸造
+    // Shrink the NFT account to a tombstone and refund the excess rent to the owner so the serial cannot be minted
+    // again
+    tombstone_account(nft_account, owner_account)?;
+    
+    // 更新集合的销毁数量
+    // Update the collection's burned count
+    let mut collection_data = collection_data;
+    collection_data.increment_burned()?;
+    collection_data.save(&mut collection_account.data.borrow_mut())?;
+    
+    NFTEvent::NFTBurned {
+        collection: nft_data.collection,
+        nft: *nft_account.key,
+        owner: nft_data.owner,
+        serial_number: nft_data.serial_number,
+    }
+    .emit();
     msg!("NFT已销毁 | NFT burned");
     Ok(())
 }
@@ -432,6 +626,10 @@ fn process_update_nft_metadata(
         return Err(ProgramError::MissingRequiredSignature);
     }
     
+    // 验证元数据URI
+    // Validate metadata URI
+    validate_uri(&new_metadata_uri)?;
+    
     // 验证NFT账户
     // Validate NFT account
     if nft_account.owner != program_id {
@@ -446,7 +644,7 @@ fn process_update_nft_metadata(
     
     // 反序列化NFT数据
     // Deserialize NFT data
-    let mut nft_data = NFTAccount::try_from_slice(&nft_account.data.borrow())?;
+    let mut nft_data = NFTAccount::load(&nft_account.data.borrow())?;
     
     // 验证所有权
     // Validate ownership
@@ -460,6 +658,12 @@ fn process_update_nft_metadata(
         return Err(NFTError::NotMinted.into());
     }
     
+    // 验证NFT未冻结
+    // Validate NFT is not frozen
+    if nft_data.is_frozen {
+        return Err(NFTError::NFTFrozen.into());
+    }
+    
     // 验证集合
     // Validate collection
     if nft_data.collection != *collection_account.key {
@@ -468,7 +672,7 @@ fn process_update_nft_metadata(
     
     // 反序列化集合数据
     // Deserialize collection data
-    let collection_data = CollectionAccount::try_from_slice(&collection_account.data.borrow())?;
+    let collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
     
     // 验证元数据是否可变
     // Validate metadata is mutable
@@ -483,12 +687,14 @@ fn process_update_nft_metadata(
     
     // 更新元数据
     // Update metadata
+    let old_uri = nft_data.metadata_uri.clone();
     nft_data.update_metadata(new_metadata_uri, current_timestamp);
     
     // 序列化并存储数据
     // Serialize and store data
-    nft_data.serialize(&mut *nft_account.data.borrow_mut())?;
+    nft_data.save(&mut nft_account.data.borrow_mut())?;
     
+    NFTEvent::MetadataUpdated { account: *nft_account.key, old_uri, new_uri: nft_data.metadata_uri }.emit();
     msg!("NFT元数据已更新 | NFT metadata updated");
     Ok(())
 }
@@ -512,6 +718,10 @@ fn process_update_collection_metadata(
         return Err(ProgramError::MissingRequiredSignature);
     }
     
+    // 验证URI
+    // Validate URI
+    validate_uri(&new_uri)?;
+    
     // 验证集合账户
     // Validate collection account
     if collection_account.owner != program_id {
@@ -520,7 +730,7 @@ fn process_update_collection_metadata(
     
     // 反序列化集合数据
     // Deserialize collection data
-    let mut collection_data = CollectionAccount::try_from_slice(&collection_account.data.borrow())?;
+    let mut collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
     
     // 验证权限
     // Validate authority
@@ -536,12 +746,1101 @@ fn process_update_collection_metadata(
     
     // 更新元数据
     // Update metadata
-    collection_data.uri = new_uri;
+    let old_uri = std::mem::replace(&mut collection_data.uri, new_uri);
     
     // 序列化并存储数据
     // Serialize and store data
-    collection_data.serialize(&mut *collection_account.data.borrow_mut())?;
+    collection_data.save(&mut collection_account.data.borrow_mut())?;
     
+    NFTEvent::MetadataUpdated { account: *collection_account.key, old_uri, new_uri: collection_data.uri }.emit();
     msg!("集合元数据已更新 | Collection metadata updated");
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// 处理提议集合权限指令
+/// Process propose collection authority instruction
+fn process_propose_collection_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_authority: Option<Pubkey>,
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let collection_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化集合数据
+    // Deserialize collection data
+    let mut collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 验证权限
+    // Validate authority
+    if collection_data.authority != *authority.key {
+        return Err(NFTError::InsufficientAuthority.into());
+    }
+    
+    // 记录待定权限
+    // Record pending authority
+    collection_data.propose_authority(new_authority);
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    collection_data.save(&mut collection_account.data.borrow_mut())?;
+    
+    NFTEvent::CollectionAuthorityProposed { collection: *collection_account.key, pending_authority: new_authority }
+        .emit();
+    msg!("集合权限已提议 | Collection authority proposed");
+    Ok(())
+}
+
+/// 处理接受集合权限指令
+/// Process accept collection authority instruction
+fn process_accept_collection_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let collection_account = next_account_info(account_info_iter)?;
+    let new_authority = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !new_authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化集合数据
+    // Deserialize collection data
+    let mut collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 验证待定权限
+    // Validate pending authority
+    if collection_data.pending_authority != Some(*new_authority.key) {
+        return Err(NFTError::InsufficientAuthority.into());
+    }
+    
+    // 接受权限
+    // Accept authority
+    let old_authority = collection_data.authority;
+    collection_data.accept_authority();
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    collection_data.save(&mut collection_account.data.borrow_mut())?;
+    
+    NFTEvent::CollectionAuthorityTransferred {
+        collection: *collection_account.key,
+        old_authority,
+        new_authority: *new_authority.key,
+    }
+    .emit();
+    msg!("集合权限已转移 | Collection authority transferred");
+    Ok(())
+}
+
+/// 处理授权/撤销代理指令
+/// Process approve / revoke delegate instruction
+fn process_set_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delegate: Option<Pubkey>,
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let nft_account = next_account_info(account_info_iter)?;
+    let owner = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证NFT账户
+    // Validate NFT account
+    if nft_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化NFT数据
+    // Deserialize NFT data
+    let mut nft_data = NFTAccount::load(&nft_account.data.borrow())?;
+    
+    // 验证所有权
+    // Validate ownership
+    if nft_data.owner != *owner.key {
+        return Err(NFTError::InvalidOwner.into());
+    }
+    
+    // 验证NFT是否已铸造
+    // Validate NFT is minted
+    if !nft_data.is_minted {
+        return Err(NFTError::NotMinted.into());
+    }
+    
+    // 获取当前时间戳
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
+    
+    // 设置代理
+    // Set delegate
+    nft_data.set_delegate(delegate, current_timestamp);
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    nft_data.save(&mut nft_account.data.borrow_mut())?;
+    
+    NFTEvent::DelegateUpdated { nft: *nft_account.key, owner: *owner.key, delegate }.emit();
+    if delegate.is_some() {
+        msg!("NFT代理已授权 | NFT delegate approved");
+    } else {
+        msg!("NFT代理已撤销 | NFT delegate revoked");
+    }
+    Ok(())
+}
+
+/// 处理设置操作员授权指令
+/// Process set operator approval instruction
+fn process_set_operator_approval(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    operator: Pubkey,
+    approved: bool,
+    expires_at: Option<i64>,
+    bump_seed: u8,
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let approval_account = next_account_info(account_info_iter)?;
+    let owner = next_account_info(account_info_iter)?;
+    let collection_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 用传入的bump计算并验证操作员授权PDA
+    // Calculate and validate the operator approval PDA with the supplied bump
+    verify_program_address(
+        &[OPERATOR_APPROVAL_SEED_PREFIX, owner.key.as_ref(), collection_account.key.as_ref(), operator.as_ref()],
+        bump_seed,
+        program_id,
+        approval_account.key,
+    )?;
+    
+    if !approved {
+        // 关闭授权账户并退还租金
+        // Close approval account and refund rent
+        if approval_account.owner == program_id {
+            close_account(approval_account, owner)?;
+        }
+        
+        NFTEvent::OperatorApprovalUpdated {
+            owner: *owner.key,
+            collection: *collection_account.key,
+            operator,
+            approved,
+            expires_at: None,
+        }
+        .emit();
+        msg!("操作员授权已撤销 | Operator approval revoked");
+        return Ok(());
+    }
+    
+    // 首次授权时创建账户
+    // Create the account on first approval
+    if approval_account.owner != program_id {
+        create_program_account(
+            owner,
+            approval_account,
+            system_program,
+            OPERATOR_APPROVAL_SIZE,
+            program_id,
+            &[
+                OPERATOR_APPROVAL_SEED_PREFIX,
+                owner.key.as_ref(),
+                collection_account.key.as_ref(),
+                operator.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+    
+    // 创建授权数据
+    // Create approval data
+    let approval_data = OperatorApproval::new(
+        *owner.key,
+        *collection_account.key,
+        operator,
+        expires_at,
+        bump_seed,
+    );
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    approval_data.save(&mut approval_account.data.borrow_mut())?;
+    
+    NFTEvent::OperatorApprovalUpdated {
+        owner: *owner.key,
+        collection: *collection_account.key,
+        operator,
+        approved,
+        expires_at,
+    }
+    .emit();
+    msg!("操作员授权已设置 | Operator approval set");
+    Ok(())
+}
+
+/// 验证操作员对NFT的集合范围授权
+/// Validate an operator's collection-wide approval over an NFT
+fn validate_operator_approval(
+    program_id: &Pubkey,
+    approval_account: Option<&AccountInfo>,
+    nft_data: &NFTAccount,
+    operator: &Pubkey,
+    timestamp: i64,
+) -> ProgramResult {
+    // 没有授权账户时签名者无权操作
+    // Without an approval account the signer has no rights
+    let approval_account = approval_account.ok_or(NFTError::InvalidOwner)?;
+    if approval_account.owner != program_id {
+        return Err(NFTError::InvalidOwner.into());
+    }
+    
+    // 反序列化授权数据
+    // Deserialize approval data
+    let approval_data = OperatorApproval::load(&approval_account.data.borrow())?;
+    
+    // 验证授权地址和范围
+    // Validate approval address and scope
+    let expected_approval_address = Pubkey::create_program_address(
+        &[
+            OPERATOR_APPROVAL_SEED_PREFIX,
+            nft_data.owner.as_ref(),
+            nft_data.collection.as_ref(),
+            operator.as_ref(),
+            &[approval_data.bump],
+        ],
+        program_id,
+    )
+    .map_err(|_| NFTError::InvalidOwner)?;
+    if expected_approval_address != *approval_account.key
+        || approval_data.owner != nft_data.owner
+        || approval_data.collection != nft_data.collection
+        || approval_data.operator != *operator
+    {
+        return Err(NFTError::InvalidOwner.into());
+    }
+    
+    // 验证授权是否过期
+    // Validate approval has not expired
+    if !approval_data.is_active(timestamp) {
+        return Err(NFTError::OperatorApprovalExpired.into());
+    }
+    
+    Ok(())
+}
+
+/// 处理设置冻结权限指令
+/// Process set freeze authority instruction
+fn process_set_freeze_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    freeze_authority: Option<Pubkey>,
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let collection_account = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化集合数据
+    // Deserialize collection data
+    let mut collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 验证权限
+    // Validate authority
+    if collection_data.authority != *authority.key {
+        return Err(NFTError::InsufficientAuthority.into());
+    }
+    
+    // 更新冻结权限
+    // Update freeze authority
+    collection_data.freeze_authority = freeze_authority;
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    collection_data.save(&mut collection_account.data.borrow_mut())?;
+    
+    NFTEvent::FreezeAuthorityUpdated { collection: *collection_account.key, freeze_authority }.emit();
+    msg!("冻结权限已更新 | Freeze authority updated");
+    Ok(())
+}
+
+/// 处理冻结/解冻NFT指令
+/// Process freeze / thaw NFT instruction
+fn process_set_frozen(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    is_frozen: bool,
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let nft_account = next_account_info(account_info_iter)?;
+    let collection_account = next_account_info(account_info_iter)?;
+    let freeze_authority = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !freeze_authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证NFT账户
+    // Validate NFT account
+    if nft_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化NFT数据
+    // Deserialize NFT data
+    let mut nft_data = NFTAccount::load(&nft_account.data.borrow())?;
+    
+    // 验证NFT是否已铸造
+    // Validate NFT is minted
+    if !nft_data.is_minted {
+        return Err(NFTError::NotMinted.into());
+    }
+    
+    // 验证集合
+    // Validate collection
+    if nft_data.collection != *collection_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    // 反序列化集合数据
+    // Deserialize collection data
+    let collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 验证冻结权限
+    // Validate freeze authority
+    if collection_data.freeze_authority != Some(*freeze_authority.key) {
+        return Err(NFTError::InsufficientAuthority.into());
+    }
+    
+    // 获取当前时间戳
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
+    
+    // 更新冻结状态
+    // Update frozen state
+    nft_data.set_frozen(is_frozen, current_timestamp);
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    nft_data.save(&mut nft_account.data.borrow_mut())?;
+    
+    if is_frozen {
+        NFTEvent::NFTFrozen { nft: *nft_account.key }.emit();
+        msg!("NFT已冻结 | NFT frozen");
+    } else {
+        NFTEvent::NFTThawed { nft: *nft_account.key }.emit();
+        msg!("NFT已解冻 | NFT thawed");
+    }
+    Ok(())
+}
+
+/// 处理挂单NFT指令
+/// Process list NFT instruction
+fn process_list_nft(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    price: u64,
+    bump_seed: u8,
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let listing_account = next_account_info(account_info_iter)?;
+    let nft_account = next_account_info(account_info_iter)?;
+    let collection_account = next_account_info(account_info_iter)?;
+    let seller = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !seller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证NFT账户和集合账户
+    // Validate NFT and collection accounts
+    if nft_account.owner != program_id || collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 验证价格
+    // Validate price
+    if price == 0 {
+        return Err(NFTError::InvalidPrice.into());
+    }
+    
+    // 反序列化NFT数据
+    // Deserialize NFT data
+    let nft_data = NFTAccount::load(&nft_account.data.borrow())?;
+    
+    // 验证所有权
+    // Validate ownership
+    if nft_data.owner != *seller.key {
+        return Err(NFTError::InvalidOwner.into());
+    }
+    
+    // 验证NFT是否已铸造
+    // Validate NFT is minted
+    if !nft_data.is_minted {
+        return Err(NFTError::NotMinted.into());
+    }
+    
+    // 验证NFT未冻结
+    // Validate NFT is not frozen
+    if nft_data.is_frozen {
+        return Err(NFTError::NFTFrozen.into());
+    }
+    
+    // 验证集合
+    // Validate collection
+    if nft_data.collection != *collection_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    // 验证集合允许转移
+    // Validate collection allows transfers
+    let collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    if !collection_data.is_transferable() {
+        return Err(NFTError::NonTransferable.into());
+    }
+    
+    // 用传入的bump计算并验证挂单PDA
+    // Calculate and validate the listing PDA with the supplied bump
+    verify_program_address(
+        &[LISTING_SEED_PREFIX, nft_account.key.as_ref()],
+        bump_seed,
+        program_id,
+        listing_account.key,
+    )?;
+    
+    // 首次挂单时创建账户, 已有挂单时更新价格
+    // Create the account on first listing, otherwise update the price
+    if listing_account.owner != program_id {
+        create_program_account(
+            seller,
+            listing_account,
+            system_program,
+            LISTING_SIZE,
+            program_id,
+            &[
+                LISTING_SEED_PREFIX,
+                nft_account.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    } else {
+        // 旧挂单的租金属于旧卖家, 只有旧卖家可以覆盖
+        // Rent of a stale listing belongs to its seller, only they may overwrite it
+        let listing_data = Listing::load(&listing_account.data.borrow())?;
+        if listing_data.seller != *seller.key {
+            return Err(NFTError::InvalidListing.into());
+        }
+    }
+    
+    // 创建挂单数据
+    // Create listing data
+    let listing_data = Listing::new(*nft_account.key, *seller.key, price, bump_seed);
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    listing_data.save(&mut listing_account.data.borrow_mut())?;
+    
+    NFTEvent::NFTListed { listing: *listing_account.key, nft: *nft_account.key, seller: *seller.key, price }.emit();
+    msg!("NFT已挂单 | NFT listed");
+    Ok(())
+}
+
+/// 处理取消挂单指令
+/// Process delist NFT instruction
+fn process_delist_nft(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let listing_account = next_account_info(account_info_iter)?;
+    let seller = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !seller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证挂单账户
+    // Validate listing account
+    if listing_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化挂单数据
+    // Deserialize listing data
+    let listing_data = Listing::load(&listing_account.data.borrow())?;
+    
+    // 验证卖家
+    // Validate seller
+    if listing_data.seller != *seller.key {
+        return Err(NFTError::InvalidOwner.into());
+    }
+    
+    // 关闭挂单并退还租金
+    // Close listing and refund rent
+    close_account(listing_account, seller)?;
+    
+    NFTEvent::NFTDelisted { listing: *listing_account.key, nft: listing_data.nft, seller: *seller.key }.emit();
+    msg!("NFT已取消挂单 | NFT delisted");
+    Ok(())
+}
+
+/// 处理购买NFT指令
+/// Process buy NFT instruction
+fn process_buy_nft(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    price: u64,
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let listing_account = next_account_info(account_info_iter)?;
+    let nft_account = next_account_info(account_info_iter)?;
+    let collection_account = next_account_info(account_info_iter)?;
+    let buyer = next_account_info(account_info_iter)?;
+    let seller = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !buyer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证程序账户
+    // Validate program accounts
+    if listing_account.owner != program_id
+        || nft_account.owner != program_id
+        || collection_account.owner != program_id
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化挂单数据
+    // Deserialize listing data
+    let listing_data = Listing::load(&listing_account.data.borrow())?;
+    
+    // 验证挂单
+    // Validate listing
+    if listing_data.nft != *nft_account.key || listing_data.seller != *seller.key {
+        return Err(NFTError::InvalidListing.into());
+    }
+    
+    // 验证价格 (防止卖家抢先改价)
+    // Validate price (guards against the seller front-running a price change)
+    if listing_data.price != price {
+        return Err(NFTError::InvalidPrice.into());
+    }
+    
+    // 反序列化NFT数据
+    // Deserialize NFT data
+    let mut nft_data = NFTAccount::load(&nft_account.data.borrow())?;
+    
+    // 验证NFT是否已铸造
+    // Validate NFT is minted
+    if !nft_data.is_minted {
+        return Err(NFTError::NotMinted.into());
+    }
+    
+    // 卖家必须仍然持有NFT
+    // Seller must still own the NFT
+    if nft_data.owner != listing_data.seller {
+        return Err(NFTError::InvalidListing.into());
+    }
+    
+    // 验证NFT未冻结
+    // Validate NFT is not frozen
+    if nft_data.is_frozen {
+        return Err(NFTError::NFTFrozen.into());
+    }
+    
+    // 验证集合
+    // Validate collection
+    if nft_data.collection != *collection_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    
+    // 反序列化集合数据
+    // Deserialize collection data
+    let collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 验证集合允许转移
+    // Validate collection allows transfers
+    if !collection_data.is_transferable() {
+        return Err(NFTError::NonTransferable.into());
+    }
+    
+    // 计算版税和卖家所得
+    // Calculate royalty and seller proceeds
+    let royalty = collection_data.royalty_amount(price).ok_or(NFTError::Overflow)?;
+    let seller_proceeds = price.checked_sub(royalty).ok_or(NFTError::Overflow)?;
+    let royalty_splits = collection_data.royalty_splits(royalty).ok_or(NFTError::Overflow)?;
+    
+    // 支付卖家
+    // Pay seller
+    invoke(
+        &system_instruction::transfer(buyer.key, seller.key, seller_proceeds),
+        &[buyer.clone(), seller.clone(), system_program.clone()],
+    )?;
+    
+    // 按份额支付版税
+    // Pay royalties by share
+    for (recipient_key, amount) in royalty_splits {
+        let royalty_recipient = next_account_info(account_info_iter)?;
+        if *royalty_recipient.key != recipient_key {
+            return Err(NFTError::InvalidRoyaltyRecipient.into());
+        }
+        if amount > 0 {
+            invoke(
+                &system_instruction::transfer(buyer.key, royalty_recipient.key, amount),
+                &[buyer.clone(), royalty_recipient.clone(), system_program.clone()],
+            )?;
+        }
+    }
+    
+    // 获取当前时间戳
+    // Get current timestamp
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
+    
+    // 转移NFT
+    // Transfer NFT
+    nft_data.transfer(*buyer.key, current_timestamp);
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    nft_data.save(&mut nft_account.data.borrow_mut())?;
+    
+    // 关闭挂单并将租金退还卖家
+    // Close listing and refund rent to the seller
+    close_account(listing_account, seller)?;
+    
+    NFTEvent::NFTTransferred { nft: *nft_account.key, from: *seller.key, to: *buyer.key }.emit();
+    NFTEvent::NFTSold { nft: *nft_account.key, seller: *seller.key, buyer: *buyer.key, price, royalty }.emit();
+    msg!("NFT已售出 | NFT sold");
+    Ok(())
+}
+
+/// 关闭NFT的挂单 (如果存在) 并将租金退还卖家, NFT易手后旧挂单不能再成交
+/// Close the NFT's listing, if any, refunding rent to its seller so it cannot fill after the NFT changes hands
+fn close_listing(
+    program_id: &Pubkey,
+    nft_account: &AccountInfo,
+    listing_account: &AccountInfo,
+    listing_seller: &AccountInfo,
+    listing_bump: u8,
+) -> ProgramResult {
+    // 用传入的bump验证挂单地址, 挂单不存在时同样需要验证
+    // Validate the listing address with the supplied bump, which is needed even when no listing exists
+    verify_program_address(
+        &[LISTING_SEED_PREFIX, nft_account.key.as_ref()],
+        listing_bump,
+        program_id,
+        listing_account.key,
+    )?;
+    
+    // 没有挂单时无需处理
+    // Nothing to do without a listing
+    if listing_account.owner != program_id {
+        return Ok(());
+    }
+    
+    // 租金退还给挂单的卖家
+    // Rent goes back to the listing's seller
+    let listing_data = Listing::load(&listing_account.data.borrow())?;
+    if listing_data.seller != *listing_seller.key {
+        return Err(NFTError::InvalidListing.into());
+    }
+    close_account(listing_account, listing_seller)?;
+    
+    NFTEvent::NFTDelisted { listing: *listing_account.key, nft: listing_data.nft, seller: listing_data.seller }.emit();
+    Ok(())
+}
+
+/// 关闭程序账户并将租金转给目标账户
+/// Close a program account and move its rent to the destination
+fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let dest_starting_lamports = destination.lamports();
+    **destination.lamports.borrow_mut() = dest_starting_lamports
+        .checked_add(account.lamports())
+        .ok_or(NFTError::Overflow)?;
+    **account.lamports.borrow_mut() = 0;
+    
+    // 清空数据并交还系统程序, 同一交易内可以重新创建该账户
+    // Clear data and hand the account back to the system program, so it can be recreated in the same transaction
+    account.data.borrow_mut().fill(0);
+    account.realloc(0, false)?;
+    account.assign(&system_program::id());
+    Ok(())
+}
+
+/// 将程序账户缩减为空的墓碑账户, 保留零数据的免租金额, 其余租金转给目标账户
+/// Shrink a program account to an empty tombstone, keeping the rent-exempt minimum for zero bytes and moving the rest to the destination
+fn tombstone_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    account.realloc(0, false)?;
+    
+    let tombstone_lamports = Rent::get()?.minimum_balance(0);
+    let refund = account.lamports().saturating_sub(tombstone_lamports);
+    **account.lamports.borrow_mut() -= refund;
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(refund)
+        .ok_or(NFTError::Overflow)?;
+    Ok(())
+}
+
+/// 处理创作者签名指令
+/// Process sign creator instruction
+fn process_sign_creator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let collection_account = next_account_info(account_info_iter)?;
+    let creator = next_account_info(account_info_iter)?;
+    
+    // 验证账户
+    // Validate accounts
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // 验证集合账户
+    // Validate collection account
+    if collection_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 反序列化集合数据
+    // Deserialize collection data
+    let mut collection_data = CollectionAccount::load(&collection_account.data.borrow())?;
+    
+    // 标记创作者已验证
+    // Mark creator verified
+    if !collection_data.verify_creator(creator.key) {
+        return Err(NFTError::CreatorNotFound.into());
+    }
+    
+    // 序列化并存储数据
+    // Serialize and store data
+    collection_data.save(&mut collection_account.data.borrow_mut())?;
+    
+    NFTEvent::CreatorVerified { collection: *collection_account.key, creator: *creator.key }.emit();
+    msg!("创作者已验证 | Creator verified");
+    Ok(())
+}
+
+/// 用给定的bump验证程序派生地址 (不搜索bump)
+/// Validate a program derived address with a given bump (no bump search)
+fn verify_program_address(
+    seeds: &[&[u8]],
+    bump_seed: u8,
+    program_id: &Pubkey,
+    expected: &Pubkey,
+) -> ProgramResult {
+    let bump = [bump_seed];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump);
+    match Pubkey::create_program_address(&seeds_with_bump, program_id) {
+        Ok(address) if address == *expected => Ok(()),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// 验证字符串字节长度不超过上限
+/// Validate that a string's byte length is within the limit
+fn validate_length(value: &str, max_length: usize, error: NFTError) -> ProgramResult {
+    if value.len() > max_length {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// 验证URI: 长度上限且使用允许的协议
+/// Validate a URI: length limit and an allowed scheme
+fn validate_uri(uri: &str) -> ProgramResult {
+    validate_length(uri, MAX_URI_LENGTH, NFTError::UriTooLong)?;
+    if !ALLOWED_URI_SCHEMES.iter().any(|scheme| uri.starts_with(scheme)) {
+        return Err(NFTError::InvalidMetadataUri.into());
+    }
+    Ok(())
+}
+
+/// 验证创作者列表: 数量上限、地址不重复、份额合计100
+/// Validate creators: count limit, unique addresses, shares summing to 100
+fn validate_creators(creators: &[Creator]) -> ProgramResult {
+    if creators.is_empty() {
+        return Ok(());
+    }
+    if creators.len() > MAX_CREATORS {
+        return Err(NFTError::InvalidCreators.into());
+    }
+    
+    let mut total_shares: u16 = 0;
+    for (index, creator) in creators.iter().enumerate() {
+        if creators[..index].iter().any(|c| c.address == creator.address) {
+            return Err(NFTError::InvalidCreators.into());
+        }
+        total_shares += creator.share as u16;
+    }
+    if total_shares != 100 {
+        return Err(NFTError::InvalidCreators.into());
+    }
+    Ok(())
+}
+
+/// 处理迁移账户指令
+/// Process migrate account instruction
+fn process_migrate_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    // 获取账户
+    // Get accounts
+    let account_info_iter = &mut accounts.iter();
+    let account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let seed_authority = account_info_iter.next();
+    
+    // 验证账户
+    // Validate accounts
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 读取旧布局: 带类型标识的版本必须同时匹配类型标识、版本和大小;
+    // v0 以公钥开头, 没有类型标识, 只能按大小识别, 其地址在下面按PDA种子验证
+    // Read the old layout: tagged versions must match the discriminator, version and size together;
+    // v0 starts with a pubkey and has no discriminator, so it is recognised by size and its address is checked against the PDA seeds below
+    let (old_collection, old_nft) = {
+        let data = account.data.borrow();
+        let layout = (data.first().copied(), data.get(1).copied(), data.len());
+        const COLLECTION: u8 = AccountKey::Collection as u8;
+        const NFT: u8 = AccountKey::NFT as u8;
+        match layout {
+            (Some(COLLECTION), Some(1), COLLECTION_ACCOUNT_V1_SIZE) => {
+                (Some(CollectionAccount::from(CollectionAccountV1::deserialize(&mut &data[..])?)), None)
+            }
+            (Some(COLLECTION), Some(2), COLLECTION_ACCOUNT_V2_SIZE) => {
+                (Some(CollectionAccount::from(CollectionAccountV2::deserialize(&mut &data[..])?)), None)
+            }
+            (Some(NFT), Some(1), NFT_ACCOUNT_V1_SIZE) => {
+                (None, Some(NFTAccount::from(NFTAccountV1::deserialize(&mut &data[..])?)))
+            }
+            (Some(NFT), Some(2), NFT_ACCOUNT_V2_SIZE) => {
+                (None, Some(NFTAccount::from(NFTAccountV2::deserialize(&mut &data[..])?)))
+            }
+            (_, _, COLLECTION_ACCOUNT_V0_SIZE) => {
+                (Some(CollectionAccount::from(CollectionAccountV0::deserialize(&mut &data[..])?)), None)
+            }
+            (_, _, NFT_ACCOUNT_V0_SIZE) => {
+                (None, Some(NFTAccount::from(NFTAccountV0::deserialize(&mut &data[..])?)))
+            }
+            _ => (None, None),
+        }
+    };
+    
+    // 早期布局没有保存bump和原始权限, 迁移时推导一次
+    // Early layouts store neither the bump nor the original authority, so both are derived once during migration
+    if let Some(mut collection_data) = old_collection {
+        let seed_authority = seed_authority.map_or(collection_data.authority, |authority| *authority.key);
+        collection_data.bump =
+            find_collection_bump(program_id, account.key, &seed_authority, &collection_data.name)?;
+        collection_data.original_authority = seed_authority;
+        resize_account(account, payer, system_program, COLLECTION_ACCOUNT_SIZE)?;
+        collection_data.save(&mut account.data.borrow_mut())?;
+        NFTEvent::AccountMigrated { account: *account.key, version: CollectionAccount::VERSION }.emit();
+    } else if let Some(mut nft_data) = old_nft {
+        let (expected_nft_address, bump_seed) =
+            find_nft_address(program_id, &nft_data.collection, nft_data.serial_number);
+        if expected_nft_address != *account.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        nft_data.bump = bump_seed;
+        resize_account(account, payer, system_program, NFT_ACCOUNT_SIZE)?;
+        nft_data.save(&mut account.data.borrow_mut())?;
+        NFTEvent::AccountMigrated { account: *account.key, version: NFTAccount::VERSION }.emit();
+    } else {
+        // 已是当前版本的账户无需迁移
+        // Accounts already on the current version need no migration
+        let data = account.data.borrow();
+        if CollectionAccount::load(&data).is_err() && NFTAccount::load(&data).is_err() {
+            return Err(NFTError::UnsupportedAccountVersion.into());
+        }
+        msg!("账户已是最新版本 | Account is already current");
+        return Ok(());
+    }
+    
+    msg!("账户已迁移 | Account migrated");
+    Ok(())
+}
+
+/// 推导已有集合账户的bump: 先尝试名称哈希种子, 再尝试早期的原始名称种子
+/// Derive the bump of an existing collection: the name-hash seed first, then the earlier raw-name seed
+fn find_collection_bump(
+    program_id: &Pubkey,
+    collection: &Pubkey,
+    authority: &Pubkey,
+    name: &str,
+) -> Result<u8, ProgramError> {
+    let (expected_collection_address, bump_seed) = find_collection_address(program_id, authority, name);
+    if expected_collection_address == *collection {
+        return Ok(bump_seed);
+    }
+    if name.len() <= MAX_SEED_LEN {
+        let (expected_collection_address, bump_seed) = Pubkey::find_program_address(
+            &[COLLECTION_SEED_PREFIX, authority.as_ref(), name.as_bytes()],
+            program_id,
+        );
+        if expected_collection_address == *collection {
+            return Ok(bump_seed);
+        }
+    }
+    Err(ProgramError::InvalidSeeds)
+}
+
+/// 创建程序派生账户, 由付款人支付免租金额
+/// Create a program derived account, with the payer funding the rent-exempt balance
+///
+/// 账户已有预存的lamports时 `create_account` 会失败, 此时改为补足差额后分配空间并指定所有者
+/// `create_account` fails when the account already holds lamports, so top up the difference then allocate and assign
+fn create_program_account<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, rent_lamports, space as u64, program_id),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[seeds],
+        );
+    }
+    
+    let shortfall = rent_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, shortfall),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        &[seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, program_id),
+        &[account.clone(), system_program.clone()],
+        &[seeds],
+    )
+}
+
+/// 调整程序账户大小, 由付款人补足免租金额
+/// Resize a program account, with the payer topping up the rent-exempt balance
+fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_size: usize,
+) -> ProgramResult {
+    let required_lamports = Rent::get()?.minimum_balance(new_size);
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, shortfall),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    account.realloc(new_size, true)
+}
@@ -1,10 +1,149 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use solana_program::{entrypoint::ProgramResult, hash::hash, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::NFTError;
+
+/// 账户类型标识, 存储在每个程序账户的第一个字节
+/// Account type discriminator, stored in the first byte of every program account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKey {
+    /// 未初始化 (全零账户)
+    /// Uninitialized (all-zero account)
+    Uninitialized,
+    
+    /// 集合账户
+    /// Collection account
+    Collection,
+    
+    /// NFT账户
+    /// NFT account
+    NFT,
+    
+    /// 操作员授权账户
+    /// Operator approval account
+    OperatorApproval,
+    
+    /// 挂单账户
+    /// Listing account
+    Listing,
+}
+
+/// 带类型标识和版本号的程序账户
+/// Program account with a type discriminator and a schema version
+pub trait ProgramAccount: BorshSerialize + BorshDeserialize {
+    /// 账户类型标识
+    /// Account type discriminator
+    const KEY: AccountKey;
+    
+    /// 当前数据结构版本
+    /// Current schema version
+    const VERSION: u8;
+    
+    /// 当前版本的账户数据大小
+    /// Account data size of the current version
+    const SIZE: usize;
+    
+    /// 校验类型标识、版本和数据大小后反序列化账户数据
+    /// Deserialize account data after checking the discriminator, version and data size
+    fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        match data.first() {
+            None => return Err(NFTError::NotInitialized.into()),
+            Some(&key) if key == AccountKey::Uninitialized as u8 => {
+                return Err(NFTError::NotInitialized.into())
+            }
+            Some(&key) if key != Self::KEY as u8 => return Err(NFTError::AccountTypeMismatch.into()),
+            _ => {}
+        }
+        if data.get(1) != Some(&Self::VERSION) {
+            return Err(NFTError::UnsupportedAccountVersion.into());
+        }
+        if data.len() != Self::SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self::deserialize(&mut &data[..])?)
+    }
+    
+    /// 序列化并写入账户数据
+    /// Serialize and write account data
+    fn save(&self, data: &mut [u8]) -> ProgramResult {
+        let mut remaining = &mut data[..];
+        self.serialize(&mut remaining)?;
+        
+        // 清除较短数据留下的旧尾部字节
+        // Clear stale trailing bytes left behind by shorter data
+        remaining.fill(0);
+        Ok(())
+    }
+}
+
+/// 账户数据是否尚未初始化
+/// Whether account data is still uninitialized
+pub fn is_uninitialized(data: &[u8]) -> bool {
+    match data.first() {
+        Some(&key) => key == AccountKey::Uninitialized as u8,
+        None => true,
+    }
+}
+
+/// 集合转移模式
+/// Collection transfer mode
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMode {
+    /// 可自由转移
+    /// Freely transferable
+    Transferable,
+    
+    /// 灵魂绑定: 不可转移, 仅所有者可销毁
+    /// Soulbound: non-transferable, only the owner can burn
+    Soulbound,
+    
+    /// 灵魂绑定, 且集合权限可以撤销 (销毁)
+    /// Soulbound, and the collection authority can revoke (burn)
+    AuthorityRevocable,
+}
+
+/// 序列号分配模式
+/// Serial number assignment mode
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerialMode {
+    /// 由铸造者指定序列号
+    /// Serial number chosen by the minter
+    CallerAssigned,
+    
+    /// 由程序按顺序分配 (total_minted + 1)
+    /// Assigned sequentially by the program (total_minted + 1)
+    Sequential,
+}
+
+/// 版税创作者
+/// Royalty creator
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Creator {
+    /// 创作者地址
+    /// Creator address
+    pub address: Pubkey,
+    
+    /// 创作者是否已签名确认
+    /// Whether the creator has signed to confirm
+    pub verified: bool,
+    
+    /// 版税份额 (所有创作者合计100)
+    /// Royalty share (all creators sum to 100)
+    pub share: u8,
+}
 
 /// NFT集合账户
 /// NFT collection account
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub struct CollectionAccount {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
     /// 集合权限
     /// Collection authority
     pub authority: Pubkey,
@@ -32,12 +171,57 @@ pub struct CollectionAccount {
     /// 已铸造的NFT数量
     /// Number of minted NFTs
     pub total_minted: u64,
+    
+    /// 待接受的新权限
+    /// Pending authority awaiting acceptance
+    pub pending_authority: Option<Pubkey>,
+    
+    /// 冻结权限
+    /// Freeze authority
+    pub freeze_authority: Option<Pubkey>,
+    
+    /// 转移模式
+    /// Transfer mode
+    pub transfer_mode: TransferMode,
+    
+    /// 版税创作者 (为空时版税归集合权限)
+    /// Royalty creators (royalties go to the collection authority when empty)
+    pub creators: Vec<Creator>,
+    
+    /// 最大供应量 (为空时不限制)
+    /// Maximum supply (unlimited when empty)
+    pub max_supply: Option<u64>,
+    
+    /// 序列号分配模式
+    /// Serial number assignment mode
+    pub serial_mode: SerialMode,
+    
+    /// 已销毁的NFT数量
+    /// Number of burned NFTs
+    pub total_burned: u64,
+    
+    /// PDA种子bump
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// 创建集合时的权限, 集合PDA由它推导, 权限转移后保持不变
+    /// Authority at creation time, the collection PDA is derived from it and it stays unchanged after authority
+    /// transfers
+    pub original_authority: Pubkey,
 }
 
 /// NFT账户
 /// NFT account
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub struct NFTAccount {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
     /// NFT所有者
     /// NFT owner
     pub owner: Pubkey,
@@ -65,29 +249,165 @@ pub struct NFTAccount {
     /// 最后更新时间戳
     /// Last update timestamp
     pub updated_at: i64,
+    
+    /// 被授权转移此NFT的代理
+    /// Delegate approved to transfer this NFT
+    pub delegate: Option<Pubkey>,
+    
+    /// 是否已冻结
+    /// Whether the NFT is frozen
+    pub is_frozen: bool,
+    
+    /// PDA种子bump
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// 原始铸造者 (为空表示在记录铸造者之前铸造)
+    /// Original minter (empty for NFTs minted before minters were recorded)
+    pub minter: Option<Pubkey>,
+}
+
+/// 操作员授权账户 (PDA: 所有者, 集合, 操作员)
+/// Operator approval account (PDA: owner, collection, operator)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct OperatorApproval {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
+    /// NFT所有者
+    /// NFT owner
+    pub owner: Pubkey,
+    
+    /// 授权范围内的集合
+    /// Collection covered by the approval
+    pub collection: Pubkey,
+    
+    /// 被授权的操作员
+    /// Approved operator
+    pub operator: Pubkey,
+    
+    /// 过期时间戳 (None 表示永不过期)
+    /// Expiry timestamp (None means no expiry)
+    pub expires_at: Option<i64>,
+    
+    /// PDA种子bump
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// 一口价挂单账户 (PDA: NFT)
+/// Fixed-price listing account (PDA: NFT)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Listing {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
+    /// 挂单的NFT
+    /// Listed NFT
+    pub nft: Pubkey,
+    
+    /// 卖家 (挂单时的NFT所有者)
+    /// Seller (NFT owner at listing time)
+    pub seller: Pubkey,
+    
+    /// 价格 (lamports)
+    /// Price (lamports)
+    pub price: u64,
+    
+    /// PDA种子bump
+    /// PDA bump seed
+    pub bump: u8,
 }
 
 /// 集合账户大小常量
 /// Collection account size constants
 pub const COLLECTION_ACCOUNT_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
     32 +                // authority
-    4 + 50 +           // name (max 50 chars)
-    4 + 10 +           // symbol (max 10 chars)
-    4 + 200 +          // uri (max 200 chars)
+    4 + MAX_NAME_LENGTH + // name
+    4 + MAX_SYMBOL_LENGTH + // symbol
+    4 + MAX_URI_LENGTH + // uri
     1 +                // royalty_percentage
     1 +                // is_mutable
-    8;                 // total_minted
+    8 +                // total_minted
+    1 + 32 +           // pending_authority
+    1 + 32 +           // freeze_authority
+    1 +                // transfer_mode
+    4 + MAX_CREATORS * (32 + 1 + 1) + // creators
+    1 + 8 +            // max_supply
+    1 +                // serial_mode
+    8 +                // total_burned
+    1 +                // bump
+    32;                // original_authority
+
+/// 名称最大字节数
+/// Maximum name length in bytes
+pub const MAX_NAME_LENGTH: usize = 50;
+
+/// 符号最大字节数
+/// Maximum symbol length in bytes
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+
+/// URI最大字节数
+/// Maximum URI length in bytes
+pub const MAX_URI_LENGTH: usize = 200;
+
+/// 允许的URI协议
+/// Allowed URI schemes
+pub const ALLOWED_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+
+/// 每个集合的最大创作者数量
+/// Maximum number of creators per collection
+pub const MAX_CREATORS: usize = 5;
 
 /// NFT账户大小常量
 /// NFT account size constants
 pub const NFT_ACCOUNT_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
     32 +                // owner
     32 +                // collection
-    4 + 200 +           // metadata_uri (max 200 chars)
+    4 + MAX_URI_LENGTH + // metadata_uri
     1 +                 // is_minted
     8 +                 // serial_number
     8 +                 // created_at
-    8;                  // updated_at
+    8 +                 // updated_at
+    1 + 32 +            // delegate
+    1 +                 // is_frozen
+    1 +                 // bump
+    1 + 32;             // minter
+
+/// 操作员授权账户大小常量
+/// Operator approval account size constants
+pub const OPERATOR_APPROVAL_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
+    32 +                // owner
+    32 +                // collection
+    32 +                // operator
+    1 + 8 +             // expires_at
+    1;                  // bump
+
+/// 挂单账户大小常量
+/// Listing account size constants
+pub const LISTING_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
+    32 +                // nft
+    32 +                // seller
+    8 +                 // price
+    1;                  // bump
 
 /// 集合种子前缀
 /// Collection seed prefix
@@ -97,9 +417,74 @@ pub const COLLECTION_SEED_PREFIX: &[u8] = b"collection";
 /// NFT seed prefix
 pub const NFT_SEED_PREFIX: &[u8] = b"nft";
 
+/// 操作员授权种子前缀
+/// Operator approval seed prefix
+pub const OPERATOR_APPROVAL_SEED_PREFIX: &[u8] = b"operator";
+
+/// 挂单种子前缀
+/// Listing seed prefix
+pub const LISTING_SEED_PREFIX: &[u8] = b"listing";
+
+/// 集合名称种子: 名称的SHA-256哈希, 不受32字节种子长度限制
+/// Collection name seed: SHA-256 hash of the name, free of the 32-byte seed length limit
+pub fn collection_name_seed(name: &str) -> [u8; 32] {
+    hash(name.as_bytes()).to_bytes()
+}
+
+/// 根据 (集合, 序列号) 查找NFT PDA
+/// Find the NFT PDA from (collection, serial number)
+pub fn find_nft_address(program_id: &Pubkey, collection: &Pubkey, serial_number: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[NFT_SEED_PREFIX, collection.as_ref(), &serial_number.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// 根据 (所有者, 集合, 操作员) 查找操作员授权PDA
+/// Find the operator approval PDA from (owner, collection, operator)
+pub fn find_operator_approval_address(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    collection: &Pubkey,
+    operator: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OPERATOR_APPROVAL_SEED_PREFIX, owner.as_ref(), collection.as_ref(), operator.as_ref()],
+        program_id,
+    )
+}
+
+/// 根据NFT查找挂单PDA
+/// Find the listing PDA from the NFT
+pub fn find_listing_address(program_id: &Pubkey, nft: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LISTING_SEED_PREFIX, nft.as_ref()], program_id)
+}
+
+/// 根据 (原始权限, 名称) 查找集合PDA
+/// Find the collection PDA from (original authority, name)
+///
+/// `authority` 必须是创建集合时的权限 (保存在 `original_authority`), 权限转移后用新权限查找会得到另一个地址;
+/// 名称是种子的一部分, 因此集合不能改名
+/// `authority` must be the authority at creation time (stored as `original_authority`), looking up with a new
+/// authority after a transfer yields a different address; the name is part of the seed, so collections cannot be
+/// renamed
+pub fn find_collection_address(program_id: &Pubkey, authority: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COLLECTION_SEED_PREFIX, authority.as_ref(), &collection_name_seed(name)],
+        program_id,
+    )
+}
+
+impl ProgramAccount for CollectionAccount {
+    const KEY: AccountKey = AccountKey::Collection;
+    const VERSION: u8 = 3;
+    const SIZE: usize = COLLECTION_ACCOUNT_SIZE;
+}
+
 impl CollectionAccount {
     /// 创建新集合
     /// Create a new collection
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         authority: Pubkey,
         name: String,
@@ -107,8 +492,15 @@ impl CollectionAccount {
         uri: String,
         royalty_percentage: u8,
         is_mutable: bool,
+        transfer_mode: TransferMode,
+        creators: Vec<Creator>,
+        max_supply: Option<u64>,
+        serial_mode: SerialMode,
+        bump: u8,
     ) -> Self {
         Self {
+            key: Self::KEY,
+            version: Self::VERSION,
             authority,
             name,
             symbol,
@@ -116,6 +508,15 @@ impl CollectionAccount {
             royalty_percentage,
             is_mutable,
             total_minted: 0,
+            pending_authority: None,
+            freeze_authority: None,
+            transfer_mode,
+            creators,
+            max_supply,
+            serial_mode,
+            total_burned: 0,
+            bump,
+            original_authority: authority,
         }
     }
     
@@ -124,6 +525,113 @@ impl CollectionAccount {
     pub fn increment_minted(&mut self) {
         self.total_minted += 1;
     }
+    
+    /// 增加已销毁NFT数量
+    /// Increment burned NFT count
+    pub fn increment_burned(&mut self) -> Result<(), NFTError> {
+        self.total_burned = self.total_burned.checked_add(1).ok_or(NFTError::Overflow)?;
+        Ok(())
+    }
+    
+    /// 是否已达到最大供应量
+    /// Whether the maximum supply has been reached
+    pub fn is_supply_exhausted(&self) -> bool {
+        self.max_supply.is_some_and(|max| self.total_minted >= max)
+    }
+    
+    /// 下一个NFT的序列号, 顺序模式下忽略请求的序列号, 调用方指定时不能超过最大供应量
+    /// Serial number for the next NFT, the requested one is ignored in sequential mode and may not exceed the max
+    /// supply when the caller assigns it
+    pub fn next_serial_number(&self, requested: u64) -> Result<u64, NFTError> {
+        match self.serial_mode {
+            SerialMode::CallerAssigned if self.max_supply.is_some_and(|max| requested > max) => {
+                Err(NFTError::MaxSupplyReached)
+            }
+            SerialMode::CallerAssigned => Ok(requested),
+            SerialMode::Sequential => self.total_minted.checked_add(1).ok_or(NFTError::Overflow),
+        }
+    }
+    
+    /// 集合内的NFT是否可以转移
+    /// Whether NFTs in the collection can be transferred
+    pub fn is_transferable(&self) -> bool {
+        self.transfer_mode == TransferMode::Transferable
+    }
+    
+    /// 集合权限是否可以销毁持有者的NFT
+    /// Whether the collection authority can burn holders' NFTs
+    pub fn is_revocable(&self) -> bool {
+        self.transfer_mode == TransferMode::AuthorityRevocable
+    }
+    
+    /// 计算成交价对应的版税
+    /// Calculate the royalty owed on a sale price
+    pub fn royalty_amount(&self, price: u64) -> Option<u64> {
+        let royalty = (price as u128)
+            .checked_mul(self.royalty_percentage as u128)?
+            .checked_div(100)?;
+        u64::try_from(royalty).ok()
+    }
+    
+    /// 按份额在已验证创作者之间分配版税
+    /// (没有已验证创作者时全部归集合权限, 余数归最后一位)
+    /// Split a royalty between verified creators by share
+    /// (all to the collection authority when none are verified, remainder to the last one)
+    pub fn royalty_splits(&self, royalty: u64) -> Option<Vec<(Pubkey, u64)>> {
+        let verified: Vec<&Creator> = self.creators.iter().filter(|c| c.verified).collect();
+        let total_shares: u64 = verified.iter().map(|c| c.share as u64).sum();
+        if total_shares == 0 {
+            return Some(vec![(self.authority, royalty)]);
+        }
+        
+        let mut splits = Vec::with_capacity(verified.len());
+        let mut remaining = royalty;
+        for (index, creator) in verified.iter().enumerate() {
+            let amount = if index == verified.len() - 1 {
+                remaining
+            } else {
+                let amount = (royalty as u128)
+                    .checked_mul(creator.share as u128)?
+                    .checked_div(total_shares as u128)?;
+                u64::try_from(amount).ok()?
+            };
+            remaining = remaining.checked_sub(amount)?;
+            splits.push((creator.address, amount));
+        }
+        Some(splits)
+    }
+    
+    /// 标记创作者已验证, 返回是否找到该创作者
+    /// Mark a creator as verified, returns whether the creator was found
+    pub fn verify_creator(&mut self, address: &Pubkey) -> bool {
+        match self.creators.iter_mut().find(|c| c.address == *address) {
+            Some(creator) => {
+                creator.verified = true;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// 提议新的权限
+    /// Propose a new authority
+    pub fn propose_authority(&mut self, new_authority: Option<Pubkey>) {
+        self.pending_authority = new_authority;
+    }
+    
+    /// 接受待定权限
+    /// Accept the pending authority
+    pub fn accept_authority(&mut self) {
+        if let Some(new_authority) = self.pending_authority.take() {
+            self.authority = new_authority;
+        }
+    }
+}
+
+impl ProgramAccount for NFTAccount {
+    const KEY: AccountKey = AccountKey::NFT;
+    const VERSION: u8 = 3;
+    const SIZE: usize = NFT_ACCOUNT_SIZE;
 }
 
 impl NFTAccount {
@@ -131,12 +639,16 @@ impl NFTAccount {
     /// Create a new NFT
     pub fn new(
         owner: Pubkey,
+        minter: Pubkey,
         collection: Pubkey,
         metadata_uri: String,
         serial_number: u64,
         timestamp: i64,
+        bump: u8,
     ) -> Self {
         Self {
+            key: Self::KEY,
+            version: Self::VERSION,
             owner,
             collection,
             metadata_uri,
@@ -144,20 +656,635 @@ impl NFTAccount {
             serial_number,
             created_at: timestamp,
             updated_at: timestamp,
+            delegate: None,
+            is_frozen: false,
+            bump,
+            minter: Some(minter),
         }
     }
     
-    /// 转移NFT
-    /// Transfer NFT
+    /// 转移NFT (同时清除代理)
+    /// Transfer NFT (also clears the delegate)
     pub fn transfer(&mut self, new_owner: Pubkey, timestamp: i64) {
         self.owner = new_owner;
+        self.delegate = None;
+        self.updated_at = timestamp;
+    }
+    
+    /// 设置或清除代理
+    /// Set or clear the delegate
+    pub fn set_delegate(&mut self, delegate: Option<Pubkey>, timestamp: i64) {
+        self.delegate = delegate;
+        self.updated_at = timestamp;
+    }
+    
+    /// 冻结或解冻NFT
+    /// Freeze or thaw the NFT
+    pub fn set_frozen(&mut self, is_frozen: bool, timestamp: i64) {
+        self.is_frozen = is_frozen;
         self.updated_at = timestamp;
     }
     
+    /// 检查账户是否可以转移此NFT
+    /// Check whether a key may transfer this NFT
+    pub fn can_transfer(&self, signer: &Pubkey) -> bool {
+        self.owner == *signer || self.delegate == Some(*signer)
+    }
+    
     /// 更新元数据
     /// Update metadata
     pub fn update_metadata(&mut self, new_metadata_uri: String, timestamp: i64) {
         self.metadata_uri = new_metadata_uri;
         self.updated_at = timestamp;
     }
-} 
\ No newline at end of file
+}
+
+impl ProgramAccount for OperatorApproval {
+    const KEY: AccountKey = AccountKey::OperatorApproval;
+    const VERSION: u8 = 1;
+    const SIZE: usize = OPERATOR_APPROVAL_SIZE;
+}
+
+impl OperatorApproval {
+    /// 创建新的操作员授权
+    /// Create a new operator approval
+    pub fn new(
+        owner: Pubkey,
+        collection: Pubkey,
+        operator: Pubkey,
+        expires_at: Option<i64>,
+        bump: u8,
+    ) -> Self {
+        Self {
+            key: Self::KEY,
+            version: Self::VERSION,
+            owner,
+            collection,
+            operator,
+            expires_at,
+            bump,
+        }
+    }
+    
+    /// 授权在给定时间是否有效
+    /// Whether the approval is valid at the given time
+    pub fn is_active(&self, timestamp: i64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => timestamp < expires_at,
+            None => true,
+        }
+    }
+}
+
+impl ProgramAccount for Listing {
+    const KEY: AccountKey = AccountKey::Listing;
+    const VERSION: u8 = 1;
+    const SIZE: usize = LISTING_SIZE;
+}
+
+impl Listing {
+    /// 创建新挂单
+    /// Create a new listing
+    pub fn new(nft: Pubkey, seller: Pubkey, price: u64, bump: u8) -> Self {
+        Self {
+            key: Self::KEY,
+            version: Self::VERSION,
+            nft,
+            seller,
+            price,
+            bump,
+        }
+    }
+}
+
+/// 旧版 (v0, 无类型标识) 集合账户布局
+/// Legacy (v0, no discriminator) collection account layout
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct CollectionAccountV0 {
+    /// 集合权限
+    /// Collection authority
+    pub authority: Pubkey,
+    
+    /// 集合名称
+    /// Collection name
+    pub name: String,
+    
+    /// 集合符号
+    /// Collection symbol
+    pub symbol: String,
+    
+    /// 集合元数据URI
+    /// Collection metadata URI
+    pub uri: String,
+    
+    /// 版税百分比 (0-100)
+    /// Royalty percentage (0-100)
+    pub royalty_percentage: u8,
+    
+    /// 是否可变
+    /// Whether metadata can be updated
+    pub is_mutable: bool,
+    
+    /// 已铸造的NFT数量
+    /// Number of minted NFTs
+    pub total_minted: u64,
+}
+
+/// 旧版 (v0, 无类型标识) NFT账户布局
+/// Legacy (v0, no discriminator) NFT account layout
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct NFTAccountV0 {
+    /// NFT所有者
+    /// NFT owner
+    pub owner: Pubkey,
+    
+    /// 所属集合
+    /// Collection this NFT belongs to
+    pub collection: Pubkey,
+    
+    /// 元数据URI
+    /// Metadata URI
+    pub metadata_uri: String,
+    
+    /// 是否已铸造
+    /// Whether the NFT is minted
+    pub is_minted: bool,
+    
+    /// 序列号
+    /// Serial number within collection
+    pub serial_number: u64,
+    
+    /// 创建时间戳
+    /// Creation timestamp
+    pub created_at: i64,
+    
+    /// 最后更新时间戳
+    /// Last update timestamp
+    pub updated_at: i64,
+}
+
+/// 旧版集合账户大小, 用于识别待迁移账户
+/// Legacy collection account size, used to recognise accounts awaiting migration
+pub const COLLECTION_ACCOUNT_V0_SIZE: usize = 
+    32 +                // authority
+    4 + 50 +           // name (max 50 chars)
+    4 + 10 +           // symbol (max 10 chars)
+    4 + 200 +          // uri (max 200 chars)
+    1 +                // royalty_percentage
+    1 +                // is_mutable
+    8;                 // total_minted
+
+/// 旧版NFT账户大小, 用于识别待迁移账户
+/// Legacy NFT account size, used to recognise accounts awaiting migration
+pub const NFT_ACCOUNT_V0_SIZE: usize = 
+    32 +                // owner
+    32 +                // collection
+    4 + 200 +           // metadata_uri (max 200 chars)
+    1 +                 // is_minted
+    8 +                 // serial_number
+    8 +                 // created_at
+    8;                  // updated_at
+
+impl From<CollectionAccountV0> for CollectionAccount {
+    fn from(old: CollectionAccountV0) -> Self {
+        let mut collection = CollectionAccount::new(
+            old.authority,
+            old.name,
+            old.symbol,
+            old.uri,
+            old.royalty_percentage,
+            old.is_mutable,
+            TransferMode::Transferable,
+            Vec::new(),
+            None,
+            SerialMode::CallerAssigned,
+            0,
+        );
+        collection.total_minted = old.total_minted;
+        collection
+    }
+}
+
+impl From<NFTAccountV0> for NFTAccount {
+    fn from(old: NFTAccountV0) -> Self {
+        let mut nft = NFTAccount::new(
+            old.owner,
+            old.owner,
+            old.collection,
+            old.metadata_uri,
+            old.serial_number,
+            old.created_at,
+            0,
+        );
+        nft.is_minted = old.is_minted;
+        nft.updated_at = old.updated_at;
+        nft.minter = None;
+        nft
+    }
+}
+
+/// v1 集合账户布局 (无bump)
+/// v1 collection account layout (no bump)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct CollectionAccountV1 {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
+    /// 集合权限
+    /// Collection authority
+    pub authority: Pubkey,
+    
+    /// 集合名称
+    /// Collection name
+    pub name: String,
+    
+    /// 集合符号
+    /// Collection symbol
+    pub symbol: String,
+    
+    /// 集合元数据URI
+    /// Collection metadata URI
+    pub uri: String,
+    
+    /// 版税百分比 (0-100)
+    /// Royalty percentage (0-100)
+    pub royalty_percentage: u8,
+    
+    /// 是否可变
+    /// Whether metadata can be updated
+    pub is_mutable: bool,
+    
+    /// 已铸造的NFT数量
+    /// Number of minted NFTs
+    pub total_minted: u64,
+    
+    /// 待接受的新权限
+    /// Pending authority awaiting acceptance
+    pub pending_authority: Option<Pubkey>,
+    
+    /// 冻结权限
+    /// Freeze authority
+    pub freeze_authority: Option<Pubkey>,
+    
+    /// 转移模式
+    /// Transfer mode
+    pub transfer_mode: TransferMode,
+    
+    /// 版税创作者 (为空时版税归集合权限)
+    /// Royalty creators (royalties go to the collection authority when empty)
+    pub creators: Vec<Creator>,
+    
+    /// 最大供应量 (为空时不限制)
+    /// Maximum supply (unlimited when empty)
+    pub max_supply: Option<u64>,
+    
+    /// 序列号分配模式
+    /// Serial number assignment mode
+    pub serial_mode: SerialMode,
+    
+    /// 已销毁的NFT数量
+    /// Number of burned NFTs
+    pub total_burned: u64,
+}
+
+/// v1 NFT账户布局 (无bump)
+/// v1 NFT account layout (no bump)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct NFTAccountV1 {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
+    /// NFT所有者
+    /// NFT owner
+    pub owner: Pubkey,
+    
+    /// 所属集合
+    /// Collection this NFT belongs to
+    pub collection: Pubkey,
+    
+    /// 元数据URI
+    /// Metadata URI
+    pub metadata_uri: String,
+    
+    /// 是否已铸造
+    /// Whether the NFT is minted
+    pub is_minted: bool,
+    
+    /// 序列号
+    /// Serial number within collection
+    pub serial_number: u64,
+    
+    /// 创建时间戳
+    /// Creation timestamp
+    pub created_at: i64,
+    
+    /// 最后更新时间戳
+    /// Last update timestamp
+    pub updated_at: i64,
+    
+    /// 被授权转移此NFT的代理
+    /// Delegate approved to transfer this NFT
+    pub delegate: Option<Pubkey>,
+    
+    /// 是否已冻结
+    /// Whether the NFT is frozen
+    pub is_frozen: bool,
+}
+
+/// v1 集合账户大小
+/// v1 collection account size
+pub const COLLECTION_ACCOUNT_V1_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
+    32 +                // authority
+    4 + 50 +           // name (max 50 chars)
+    4 + 10 +           // symbol (max 10 chars)
+    4 + 200 +          // uri (max 200 chars)
+    1 +                // royalty_percentage
+    1 +                // is_mutable
+    8 +                // total_minted
+    1 + 32 +           // pending_authority
+    1 + 32 +           // freeze_authority
+    1 +                // transfer_mode
+    4 + 5 * (32 + 1 + 1) + // creators (max 5)
+    1 + 8 +            // max_supply
+    1 +                // serial_mode
+    8;                 // total_burned
+
+/// v1 NFT账户大小
+/// v1 NFT account size
+pub const NFT_ACCOUNT_V1_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
+    32 +                // owner
+    32 +                // collection
+    4 + 200 +           // metadata_uri (max 200 chars)
+    1 +                 // is_minted
+    8 +                 // serial_number
+    8 +                 // created_at
+    8 +                 // updated_at
+    1 + 32 +            // delegate
+    1;                  // is_frozen
+
+impl From<CollectionAccountV1> for CollectionAccount {
+    fn from(old: CollectionAccountV1) -> Self {
+        Self {
+            key: AccountKey::Collection,
+            version: Self::VERSION,
+            authority: old.authority,
+            name: old.name,
+            symbol: old.symbol,
+            uri: old.uri,
+            royalty_percentage: old.royalty_percentage,
+            is_mutable: old.is_mutable,
+            total_minted: old.total_minted,
+            pending_authority: old.pending_authority,
+            freeze_authority: old.freeze_authority,
+            transfer_mode: old.transfer_mode,
+            creators: old.creators,
+            max_supply: old.max_supply,
+            serial_mode: old.serial_mode,
+            total_burned: old.total_burned,
+            bump: 0,
+            original_authority: old.authority,
+        }
+    }
+}
+
+impl From<NFTAccountV1> for NFTAccount {
+    fn from(old: NFTAccountV1) -> Self {
+        Self {
+            key: AccountKey::NFT,
+            version: Self::VERSION,
+            owner: old.owner,
+            collection: old.collection,
+            metadata_uri: old.metadata_uri,
+            is_minted: old.is_minted,
+            serial_number: old.serial_number,
+            created_at: old.created_at,
+            updated_at: old.updated_at,
+            delegate: old.delegate,
+            is_frozen: old.is_frozen,
+            bump: 0,
+            minter: None,
+        }
+    }
+}
+
+/// v2 集合账户布局 (无原始权限)
+/// v2 collection account layout (no original authority)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct CollectionAccountV2 {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
+    /// 集合权限
+    /// Collection authority
+    pub authority: Pubkey,
+    
+    /// 集合名称
+    /// Collection name
+    pub name: String,
+    
+    /// 集合符号
+    /// Collection symbol
+    pub symbol: String,
+    
+    /// 集合元数据URI
+    /// Collection metadata URI
+    pub uri: String,
+    
+    /// 版税百分比 (0-100)
+    /// Royalty percentage (0-100)
+    pub royalty_percentage: u8,
+    
+    /// 是否可变
+    /// Whether metadata can be updated
+    pub is_mutable: bool,
+    
+    /// 已铸造的NFT数量
+    /// Number of minted NFTs
+    pub total_minted: u64,
+    
+    /// 待接受的新权限
+    /// Pending authority awaiting acceptance
+    pub pending_authority: Option<Pubkey>,
+    
+    /// 冻结权限
+    /// Freeze authority
+    pub freeze_authority: Option<Pubkey>,
+    
+    /// 转移模式
+    /// Transfer mode
+    pub transfer_mode: TransferMode,
+    
+    /// 版税创作者 (为空时版税归集合权限)
+    /// Royalty creators (royalties go to the collection authority when empty)
+    pub creators: Vec<Creator>,
+    
+    /// 最大供应量 (为空时不限制)
+    /// Maximum supply (unlimited when empty)
+    pub max_supply: Option<u64>,
+    
+    /// 序列号分配模式
+    /// Serial number assignment mode
+    pub serial_mode: SerialMode,
+    
+    /// 已销毁的NFT数量
+    /// Number of burned NFTs
+    pub total_burned: u64,
+    
+    /// PDA种子bump
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// v2 集合账户大小
+/// v2 collection account size
+pub const COLLECTION_ACCOUNT_V2_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
+    32 +                // authority
+    4 + 50 +           // name (max 50 chars)
+    4 + 10 +           // symbol (max 10 chars)
+    4 + 200 +          // uri (max 200 chars)
+    1 +                // royalty_percentage
+    1 +                // is_mutable
+    8 +                // total_minted
+    1 + 32 +           // pending_authority
+    1 + 32 +           // freeze_authority
+    1 +                // transfer_mode
+    4 + 5 * (32 + 1 + 1) + // creators (max 5)
+    1 + 8 +            // max_supply
+    1 +                // serial_mode
+    8 +                // total_burned
+    1;                 // bump
+
+impl From<CollectionAccountV2> for CollectionAccount {
+    fn from(old: CollectionAccountV2) -> Self {
+        Self {
+            key: AccountKey::Collection,
+            version: Self::VERSION,
+            authority: old.authority,
+            name: old.name,
+            symbol: old.symbol,
+            uri: old.uri,
+            royalty_percentage: old.royalty_percentage,
+            is_mutable: old.is_mutable,
+            total_minted: old.total_minted,
+            pending_authority: old.pending_authority,
+            freeze_authority: old.freeze_authority,
+            transfer_mode: old.transfer_mode,
+            creators: old.creators,
+            max_supply: old.max_supply,
+            serial_mode: old.serial_mode,
+            total_burned: old.total_burned,
+            bump: old.bump,
+            original_authority: old.authority,
+        }
+    }
+}
+
+/// v2 NFT账户布局 (无铸造者)
+/// v2 NFT account layout (no minter)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct NFTAccountV2 {
+    /// 账户类型标识
+    /// Account type discriminator
+    pub key: AccountKey,
+    
+    /// 数据结构版本
+    /// Schema version
+    pub version: u8,
+    
+    /// NFT所有者
+    /// NFT owner
+    pub owner: Pubkey,
+    
+    /// 所属集合
+    /// Collection this NFT belongs to
+    pub collection: Pubkey,
+    
+    /// 元数据URI
+    /// Metadata URI
+    pub metadata_uri: String,
+    
+    /// 是否已铸造
+    /// Whether the NFT is minted
+    pub is_minted: bool,
+    
+    /// 序列号
+    /// Serial number within collection
+    pub serial_number: u64,
+    
+    /// 创建时间戳
+    /// Creation timestamp
+    pub created_at: i64,
+    
+    /// 最后更新时间戳
+    /// Last update timestamp
+    pub updated_at: i64,
+    
+    /// 被授权转移此NFT的代理
+    /// Delegate approved to transfer this NFT
+    pub delegate: Option<Pubkey>,
+    
+    /// 是否已冻结
+    /// Whether the NFT is frozen
+    pub is_frozen: bool,
+    
+    /// PDA种子bump
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// v2 NFT账户大小
+/// v2 NFT account size
+pub const NFT_ACCOUNT_V2_SIZE: usize = 
+    1 +                 // key
+    1 +                 // version
+    32 +                // owner
+    32 +                // collection
+    4 + 200 +           // metadata_uri (max 200 chars)
+    1 +                 // is_minted
+    8 +                 // serial_number
+    8 +                 // created_at
+    8 +                 // updated_at
+    1 + 32 +            // delegate
+    1 +                 // is_frozen
+    1;                  // bump
+
+impl From<NFTAccountV2> for NFTAccount {
+    fn from(old: NFTAccountV2) -> Self {
+        Self {
+            key: AccountKey::NFT,
+            version: Self::VERSION,
+            owner: old.owner,
+            collection: old.collection,
+            metadata_uri: old.metadata_uri,
+            is_minted: old.is_minted,
+            serial_number: old.serial_number,
+            created_at: old.created_at,
+            updated_at: old.updated_at,
+            delegate: old.delegate,
+            is_frozen: old.is_frozen,
+            bump: old.bump,
+            minter: None,
+        }
+    }
+}
@@ -1,141 +1,1737 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::*;
+    use crate::state::{
+        find_listing_address, find_operator_approval_address, AccountKey, CollectionAccountV2, NFTAccountV1,
+        COLLECTION_ACCOUNT_V0_SIZE, COLLECTION_ACCOUNT_V2_SIZE, NFT_ACCOUNT_V0_SIZE, NFT_ACCOUNT_V1_SIZE,
+    };
+    use borsh::BorshSerialize;
     use solana_program::{
-        account_info::AccountInfo,
-        clock::Clock,
-        entrypoint::ProgramResult,
-        program_pack::Pack,
+        instruction::{AccountMeta, Instruction, InstructionError},
         pubkey::Pubkey,
-        rent::Rent,
-        sysvar::Sysvar,
+        system_program,
     };
     use solana_program_test::*;
     use solana_sdk::{
-        account::Account,
         signature::{Keypair, Signer},
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
     };
-    use std::mem;
 
-    // 测试初始化集合
-    // Test initialize collection
-    #[test]
-    fn test_initialize_collection() {
+    // 启动测试环境
+    // Start test environment
+    async fn setup() -> (ProgramTestContext, Pubkey) {
         let program_id = Pubkey::new_unique();
-        let authority_key = Pubkey::new_unique();
-        let system_program_key = Pubkey::new_unique();
-        
-        // 创建集合账户
-        // Create collection account
-        let collection_seeds = [
-            COLLECTION_SEED_PREFIX,
-            &authority_key.to_bytes(),
-            "Test Collection".as_bytes(),
-        ];
-        let (collection_key, _) = Pubkey::find_program_address(&collection_seeds, &program_id);
-        
-        let mut collection_account_data = vec![0; COLLECTION_ACCOUNT_SIZE];
-        let mut lamports = 0;
-        
-        let collection_account = AccountInfo::new(
-            &collection_key,
-            false,
-            true,
-            &mut lamports,
-            &mut collection_account_data,
-            &program_id,
-            false,
-            0,
+        let program_test = ProgramTest::new(
+            "nft_contract",
+            program_id,
+            processor!(process_instruction),
+        );
+        (program_test.start_with_context().await, program_id)
+    }
+
+    // 发送交易 (手续费由payer支付, 每笔交易使用新的区块哈希)
+    // Send transaction (fees paid by payer, fresh blockhash per transaction)
+    async fn process(
+        context: &mut ProgramTestContext,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        context.last_blockhash = context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            context.last_blockhash,
         );
-        
-        let authority_account = AccountInfo::new(
-            &authority_key,
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|e| e.unwrap())
+    }
+
+    // 程序自定义错误
+    // Program custom error
+    fn custom_error(error: NFTError) -> TransactionError {
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+    }
+
+    // 计算集合地址
+    // Calculate collection address
+    fn collection_address(program_id: &Pubkey, authority: &Pubkey, name: &str) -> Pubkey {
+        find_collection_address(program_id, authority, name).0
+    }
+
+    // 初始化集合指令
+    // Initialize collection instruction
+    fn initialize_collection_ix(program_id: &Pubkey, authority: &Pubkey, name: &str) -> Instruction {
+        initialize_collection_with_ix(program_id, authority, name, TransferMode::Transferable, vec![])
+    }
+
+    // 以指定转移模式和创作者初始化集合指令
+    // Initialize collection instruction with a given transfer mode and creators
+    fn initialize_collection_with_ix(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+        name: &str,
+        transfer_mode: TransferMode,
+        creators: Vec<Creator>,
+    ) -> Instruction {
+        instruction::initialize_collection(
+            program_id,
+            authority,
+            authority,
+            name,
+            "TEST",
+            "https://test.com",
+            5,
             true,
-            false,
-            &mut 0,
-            &mut vec![],
-            &system_program_key,
-            false,
-            0,
-        );
-        
-        let system_program_account = AccountInfo::new(
-            &system_program_key,
-            false,
-            false,
-            &mut 0,
-            &mut vec![],
-            &system_program_key,
-            false,
-            0,
-        );
-        
-        let accounts = vec![
-            collection_account,
-            authority_account,
-            system_program_account,
+            transfer_mode,
+            creators,
+            None,
+            SerialMode::CallerAssigned,
+        )
+    }
+
+    // 计算NFT地址
+    // Calculate NFT address
+    fn nft_address(program_id: &Pubkey, collection: &Pubkey, serial_number: u64) -> Pubkey {
+        find_nft_address(program_id, collection, serial_number).0
+    }
+
+    // 铸造NFT指令
+    // Mint NFT instruction
+    fn mint_nft_ix(
+        program_id: &Pubkey,
+        collection: &Pubkey,
+        authority: &Pubkey,
+        serial_number: u64,
+    ) -> Instruction {
+        let metadata_uri = format!("https://test.com/{}.json", serial_number);
+        instruction::mint_nft(program_id, authority, collection, authority, serial_number, &metadata_uri, None)
+    }
+
+    // 转移NFT指令
+    // Transfer NFT instruction
+    fn transfer_nft_ix(
+        program_id: &Pubkey,
+        nft: &Pubkey,
+        collection: &Pubkey,
+        signer: &Pubkey,
+        new_owner: &Pubkey,
+    ) -> Instruction {
+        instruction::transfer_nft(program_id, nft, collection, signer, new_owner, signer, None)
+    }
+
+    // 销毁NFT指令
+    // Burn NFT instruction
+    fn burn_nft_ix(
+        program_id: &Pubkey,
+        nft: &Pubkey,
+        collection: &Pubkey,
+        signer: &Pubkey,
+        owner: &Pubkey,
+    ) -> Instruction {
+        instruction::burn_nft(program_id, nft, collection, signer, owner, owner, None)
+    }
+
+    // 销毁后的NFT账户是否为墓碑
+    // Whether a burned NFT account is a tombstone
+    async fn is_tombstone(context: &mut ProgramTestContext, program_id: &Pubkey, address: Pubkey) -> bool {
+        let account = context.banks_client.get_account(address).await.unwrap();
+        account.is_some_and(|account| account.owner == *program_id && account.data.is_empty())
+    }
+
+    // 更新NFT元数据指令
+    // Update NFT metadata instruction
+    fn update_nft_metadata_ix(program_id: &Pubkey, nft: &Pubkey, owner: &Pubkey, collection: &Pubkey) -> Instruction {
+        instruction::update_nft_metadata(program_id, nft, owner, collection, "https://test.com/updated.json")
+    }
+
+    // 计算操作员授权地址
+    // Calculate operator approval address
+    fn operator_approval_address(
+        program_id: &Pubkey,
+        owner: &Pubkey,
+        collection: &Pubkey,
+        operator: &Pubkey,
+    ) -> Pubkey {
+        find_operator_approval_address(program_id, owner, collection, operator).0
+    }
+
+    // 计算挂单地址
+    // Calculate listing address
+    fn listing_address(program_id: &Pubkey, nft: &Pubkey) -> Pubkey {
+        find_listing_address(program_id, nft).0
+    }
+
+    // 为账户充值
+    // Fund an account
+    async fn fund(context: &mut ProgramTestContext, to: &Pubkey, lamports: u64) {
+        let ix = solana_program::system_instruction::transfer(&context.payer.pubkey(), to, lamports);
+        process(context, &[ix], &[]).await.unwrap();
+    }
+
+    // 读取账户余额
+    // Read account balance
+    async fn balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+        context.banks_client.get_balance(address).await.unwrap()
+    }
+
+    // 创建集合并铸造一个NFT, 返回 (集合地址, NFT地址)
+    // Create a collection and mint one NFT, returns (collection address, NFT address)
+    async fn setup_nft(context: &mut ProgramTestContext, program_id: &Pubkey, name: &str) -> (Pubkey, Pubkey) {
+        let authority = context.payer.pubkey();
+        let collection_key = collection_address(program_id, &authority, name);
+        let ixs = [
+            initialize_collection_ix(program_id, &authority, name),
+            mint_nft_ix(program_id, &collection_key, &authority, 1),
         ];
-        
-        // 创建指令数据
-        // Create instruction data
-        let instruction = NFTInstruction::InitializeCollection {
-            name: "Test Collection".to_string(),
-            symbol: "TEST".to_string(),
-            uri: "https://test.com".to_string(),
-            royalty_percentage: 5,
-            is_mutable: true,
-        };
-        
-        let mut instruction_data = vec![];
-        instruction.serialize(&mut instruction_data).unwrap();
-        
+        process(context, &ixs, &[]).await.unwrap();
+        (collection_key, nft_address(program_id, &collection_key, 1))
+    }
+
+    // 读取集合账户
+    // Read collection account
+    async fn get_collection(context: &mut ProgramTestContext, address: Pubkey) -> CollectionAccount {
+        let account = context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .expect("集合账户不存在 | Collection account not found");
+        CollectionAccount::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    // 读取NFT账户
+    // Read NFT account
+    async fn get_nft(context: &mut ProgramTestContext, address: Pubkey) -> NFTAccount {
+        let account = context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .expect("NFT账户不存在 | NFT account not found");
+        NFTAccount::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    // 测试初始化集合
+    // Test initialize collection
+    #[tokio::test]
+    async fn test_initialize_collection() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+
         // 执行指令
         // Execute instruction
-        let result = process_instruction(&program_id, &accounts, &instruction_data);
-        
+        let ix = initialize_collection_ix(&program_id, &authority, "Test Collection");
+        let result = process(&mut context, &[ix], &[]).await;
+
         // 验证结果
         // Verify result
         assert!(result.is_ok());
+        let collection_key = collection_address(&program_id, &authority, "Test Collection");
+        let collection = get_collection(&mut context, collection_key).await;
+        assert_eq!(collection.authority, authority);
+        assert_eq!(collection.name, "Test Collection");
+        assert_eq!(collection.royalty_percentage, 5);
+        assert_eq!(collection.total_minted, 0);
+        assert_eq!(collection.pending_authority, None);
+        assert_eq!(collection.transfer_mode, TransferMode::Transferable);
     }
-    
+
     // 测试铸造NFT
     // Test mint NFT
-    #[test]
-    fn test_mint_nft() {
-        // 类似于上面的测试，但针对铸造NFT指令
-        // Similar to the above test, but for the mint NFT instruction
+    #[tokio::test]
+    async fn test_mint_nft() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Mint").await;
+
+        let nft = get_nft(&mut context, nft_key).await;
+        assert_eq!(nft.owner, authority);
+        assert_eq!(nft.collection, collection_key);
+        assert_eq!(nft.serial_number, 1);
+        assert!(nft.is_minted);
+        assert_eq!(get_collection(&mut context, collection_key).await.total_minted, 1);
     }
-    
+
     // 测试转移NFT
     // Test transfer NFT
-    #[test]
-    fn test_transfer_nft() {
-        // 类似于上面的测试，但针对转移NFT指令
-        // Similar to the above test, but for the transfer NFT instruction
+    #[tokio::test]
+    async fn test_transfer_nft() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let recipient = Keypair::new();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Transfer").await;
+
+        // 新所有者账户必须与指令参数一致
+        // The new owner account must match the instruction argument
+        let mut ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &recipient.pubkey());
+        ix.accounts[2].pubkey = Pubkey::new_unique();
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::InvalidArgument));
+
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &recipient.pubkey());
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert_eq!(get_nft(&mut context, nft_key).await.owner, recipient.pubkey());
+
+        // 原所有者不能再转移
+        // Previous owner can no longer transfer
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &authority);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
     }
-    
+
     // 测试销毁NFT
     // Test burn NFT
-    #[test]
-    fn test_burn_nft() {
-        // 类似于上面的测试，但针对销毁NFT指令
-        // Similar to the above test, but for the burn NFT instruction
+    #[tokio::test]
+    async fn test_burn_nft() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Test Collection").await;
+        let stranger = Keypair::new();
+        fund(&mut context, &stranger.pubkey(), 10_000_000).await;
+
+        // 非所有者不能销毁
+        // Non-owners cannot burn
+        let ix = burn_nft_ix(&program_id, &nft_key, &collection_key, &stranger.pubkey(), &authority);
+        let result = process(&mut context, &[ix], &[&stranger]).await;
+        assert!(result.is_err());
+
+        let ix = burn_nft_ix(&program_id, &nft_key, &collection_key, &authority, &authority);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert!(is_tombstone(&mut context, &program_id, nft_key).await);
+        let collection = get_collection(&mut context, collection_key).await;
+        assert_eq!(collection.total_minted, 1);
+        assert_eq!(collection.total_burned, 1);
+
+        // 已销毁的序列号不能重新铸造
+        // A burned serial cannot be minted again
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 1);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::SerialNumberBurned));
     }
-    
+
     // 测试更新NFT元数据
     // Test update NFT metadata
-    #[test]
-    fn test_update_nft_metadata() {
-        // 类似于上面的测试，但针对更新NFT元数据指令
-        // Similar to the above test, but for the update NFT metadata instruction
+    #[tokio::test]
+    async fn test_update_nft_metadata() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Metadata").await;
+
+        let ix = update_nft_metadata_ix(&program_id, &nft_key, &authority, &collection_key);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert_eq!(
+            get_nft(&mut context, nft_key).await.metadata_uri,
+            "https://test.com/updated.json"
+        );
     }
-    
+
     // 测试更新集合元数据
     // Test update collection metadata
+    #[tokio::test]
+    async fn test_update_collection_metadata() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let (collection_key, _) = setup_nft(&mut context, &program_id, "Test Collection").await;
+        let update = |new_uri: &str| {
+            instruction::update_collection_metadata(&program_id, &collection_key, &authority, new_uri)
+        };
+
+        // 只允许 https、ipfs 和 ar 协议
+        // Only https, ipfs and ar schemes are allowed
+        let result = process(&mut context, &[update("http://test.com/collection.json")], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidMetadataUri));
+        let result = process(&mut context, &[update(&format!("https://{}", "a".repeat(200)))], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::UriTooLong));
+
+        // 较短的URI不会留下旧的尾部字节
+        // A shorter URI leaves no stale trailing bytes
+        process(&mut context, &[update("ipfs://bafy")], &[]).await.unwrap();
+        let collection = get_collection(&mut context, collection_key).await;
+        assert_eq!(collection.uri, "ipfs://bafy");
+        let account = context.banks_client.get_account(collection_key).await.unwrap().unwrap();
+        let used = collection.try_to_vec().unwrap().len();
+        assert!(account.data[used..].iter().all(|&byte| byte == 0));
+    }
+
+    // 测试名称和符号长度校验
+    // Test name and symbol length validation
+    #[tokio::test]
+    async fn test_name_and_symbol_validation() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let initialize = |name: &str, symbol: &str| {
+            let mut ix = initialize_collection_ix(&program_id, &authority, name);
+            let mut data = NFTInstruction::try_from_slice(&ix.data).unwrap();
+            if let NFTInstruction::InitializeCollection { symbol: ref mut value, .. } = data {
+                *value = symbol.to_string();
+            }
+            ix.data = data.try_to_vec().unwrap();
+            ix
+        };
+
+        let result = process(&mut context, &[initialize(&"N".repeat(51), "TEST")], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::NameTooLong));
+        let result = process(&mut context, &[initialize("Named", "SYMBOLTOOLONG")], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::SymbolTooLong));
+        process(&mut context, &[initialize(&"N".repeat(50), "TENLETTERS")], &[]).await.unwrap();
+    }
+
+    // 测试两步转移集合权限
+    // Test two-step collection authority transfer
+    #[tokio::test]
+    async fn test_collection_authority_transfer() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let new_authority = Keypair::new();
+        let intruder = Keypair::new();
+        let collection_key = collection_address(&program_id, &authority, "Authority");
+
+        let ix = initialize_collection_ix(&program_id, &authority, "Authority");
+        process(&mut context, &[ix], &[]).await.unwrap();
+
+        let propose = |signer: &Pubkey, new_authority: Option<Pubkey>| {
+            instruction::propose_collection_authority(&program_id, &collection_key, signer, new_authority)
+        };
+        let accept = |signer: &Pubkey| instruction::accept_collection_authority(&program_id, &collection_key, signer);
+
+        // 非权限账户不能提议
+        // Non-authority cannot propose
+        let result = process(
+            &mut context,
+            &[propose(&intruder.pubkey(), Some(intruder.pubkey()))],
+            &[&intruder],
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InsufficientAuthority));
+
+        // 没有待定提议时不能接受
+        // Cannot accept without a pending proposal
+        let result = process(&mut context, &[accept(&new_authority.pubkey())], &[&new_authority]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InsufficientAuthority));
+
+        // 提议后权限不变
+        // Authority is unchanged after proposing
+        process(&mut context, &[propose(&authority, Some(new_authority.pubkey()))], &[])
+            .await
+            .unwrap();
+        let collection = get_collection(&mut context, collection_key).await;
+        assert_eq!(collection.authority, authority);
+        assert_eq!(collection.pending_authority, Some(new_authority.pubkey()));
+
+        // 只有待定权限可以接受
+        // Only the pending authority can accept
+        let result = process(&mut context, &[accept(&intruder.pubkey())], &[&intruder]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InsufficientAuthority));
+
+        process(&mut context, &[accept(&new_authority.pubkey())], &[&new_authority])
+            .await
+            .unwrap();
+        let collection = get_collection(&mut context, collection_key).await;
+        assert_eq!(collection.authority, new_authority.pubkey());
+        assert_eq!(collection.pending_authority, None);
+
+        // 集合仍由原始权限查找
+        // The collection is still looked up by its original authority
+        assert_eq!(collection.original_authority, authority);
+        assert_eq!(collection_address(&program_id, &collection.original_authority, &collection.name), collection_key);
+
+        // 旧权限失去控制
+        // Previous authority loses control
+        let result = process(&mut context, &[propose(&authority, None)], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InsufficientAuthority));
+    }
+
+    // 测试取消待定权限
+    // Test cancelling a pending authority
+    #[tokio::test]
+    async fn test_cancel_collection_authority_proposal() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let new_authority = Keypair::new();
+        let collection_key = collection_address(&program_id, &authority, "Cancel");
+
+        let ix = initialize_collection_ix(&program_id, &authority, "Cancel");
+        process(&mut context, &[ix], &[]).await.unwrap();
+
+        for pending in [Some(new_authority.pubkey()), None] {
+            let ix = instruction::propose_collection_authority(&program_id, &collection_key, &authority, pending);
+            process(&mut context, &[ix], &[]).await.unwrap();
+        }
+
+        let accept = instruction::accept_collection_authority(&program_id, &collection_key, &new_authority.pubkey());
+        let result = process(&mut context, &[accept], &[&new_authority]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InsufficientAuthority));
+        assert_eq!(get_collection(&mut context, collection_key).await.authority, authority);
+    }
+
+    // 测试代理转移NFT
+    // Test transfer by delegate
+    #[tokio::test]
+    async fn test_delegate_transfer() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let delegate = Keypair::new();
+        let buyer = Keypair::new();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Delegate").await;
+
+        // 未授权的代理不能转移
+        // Unapproved delegate cannot transfer
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &delegate.pubkey(), &buyer.pubkey());
+        let result = process(&mut context, &[ix], &[&delegate]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        // 只有所有者可以授权代理
+        // Only the owner can approve a delegate
+        let ix = instruction::approve_delegate(&program_id, &nft_key, &delegate.pubkey(), &delegate.pubkey());
+        let result = process(&mut context, &[ix], &[&delegate]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        let ix = instruction::approve_delegate(&program_id, &nft_key, &authority, &delegate.pubkey());
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert_eq!(get_nft(&mut context, nft_key).await.delegate, Some(delegate.pubkey()));
+
+        // 代理转移后清除代理
+        // Delegate is cleared after transferring
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &delegate.pubkey(), &buyer.pubkey());
+        process(&mut context, &[ix], &[&delegate]).await.unwrap();
+        let nft = get_nft(&mut context, nft_key).await;
+        assert_eq!(nft.owner, buyer.pubkey());
+        assert_eq!(nft.delegate, None);
+
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &delegate.pubkey(), &delegate.pubkey());
+        let result = process(&mut context, &[ix], &[&delegate]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+    }
+
+    // 测试撤销代理
+    // Test revoke delegate
+    #[tokio::test]
+    async fn test_revoke_delegate() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let delegate = Keypair::new();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Revoke").await;
+        let approve = instruction::approve_delegate(&program_id, &nft_key, &authority, &delegate.pubkey());
+        let revoke = instruction::revoke_delegate(&program_id, &nft_key, &authority);
+        process(&mut context, &[approve, revoke], &[]).await.unwrap();
+        assert_eq!(get_nft(&mut context, nft_key).await.delegate, None);
+
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &delegate.pubkey(), &delegate.pubkey());
+        let result = process(&mut context, &[ix], &[&delegate]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+    }
+
+    // 测试集合范围的操作员授权
+    // Test collection-wide operator approval
+    #[tokio::test]
+    async fn test_operator_approval() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let operator = Keypair::new();
+        let buyer = Keypair::new();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Operator").await;
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 2);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        let second_nft_key = nft_address(&program_id, &collection_key, 2);
+        let approval_key = operator_approval_address(&program_id, &authority, &collection_key, &operator.pubkey());
+
+        // 没有授权账户时操作员不能转移
+        // Operator cannot transfer without an approval account
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &operator.pubkey(), &buyer.pubkey());
+        let result = process(&mut context, &[ix], &[&operator]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        let ix = instruction::set_operator_approval(&program_id, &authority, &collection_key, &operator.pubkey(), true, None);
+        process(&mut context, &[ix], &[]).await.unwrap();
+
+        // 操作员可以转移集合内的任意NFT
+        // Operator can transfer any NFT in the collection
+        let mut ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &operator.pubkey(), &buyer.pubkey());
+        ix.accounts.push(AccountMeta::new_readonly(approval_key, false));
+        process(&mut context, &[ix], &[&operator]).await.unwrap();
+        assert_eq!(get_nft(&mut context, nft_key).await.owner, buyer.pubkey());
+
+        // 授权不覆盖新所有者的NFT
+        // Approval does not cover the new owner's NFT
+        let mut ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &operator.pubkey(), &operator.pubkey());
+        ix.accounts.push(AccountMeta::new_readonly(approval_key, false));
+        let result = process(&mut context, &[ix], &[&operator]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        // 操作员不能把租金退还给自己
+        // Operator cannot redirect the rent to itself
+        let mut ix =
+            burn_nft_ix(&program_id, &second_nft_key, &collection_key, &operator.pubkey(), &operator.pubkey());
+        ix.accounts.push(AccountMeta::new_readonly(approval_key, false));
+        let result = process(&mut context, &[ix], &[&operator]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        // 操作员可以销毁
+        // Operator can burn
+        let mut ix = burn_nft_ix(&program_id, &second_nft_key, &collection_key, &operator.pubkey(), &authority);
+        ix.accounts.push(AccountMeta::new_readonly(approval_key, false));
+        process(&mut context, &[ix], &[&operator]).await.unwrap();
+        assert!(is_tombstone(&mut context, &program_id, second_nft_key).await);
+    }
+
+    // 测试操作员授权的撤销与过期
+    // Test operator approval revocation and expiry
+    #[tokio::test]
+    async fn test_operator_approval_revoke_and_expiry() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let operator = Keypair::new();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Expiry").await;
+        let approval_key = operator_approval_address(&program_id, &authority, &collection_key, &operator.pubkey());
+        let operator_transfer = || {
+            let mut ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &operator.pubkey(), &operator.pubkey());
+            ix.accounts.push(AccountMeta::new_readonly(approval_key, false));
+            ix
+        };
+
+        // 已过期的授权被拒绝
+        // Expired approval is rejected
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        let ix = instruction::set_operator_approval(
+            &program_id,
+            &authority,
+            &collection_key,
+            &operator.pubkey(),
+            true,
+            Some(clock.unix_timestamp - 1),
+        );
+        process(&mut context, &[ix], &[]).await.unwrap();
+        let result = process(&mut context, &[operator_transfer()], &[&operator]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::OperatorApprovalExpired));
+
+        // 撤销后账户被关闭
+        // Account is closed after revocation
+        let ix = instruction::set_operator_approval(&program_id, &authority, &collection_key, &operator.pubkey(), false, None);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert!(context.banks_client.get_account(approval_key).await.unwrap().is_none());
+        let result = process(&mut context, &[operator_transfer()], &[&operator]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+    }
+
+    // 测试关闭的账户交还系统程序, 以及预存lamports的PDA仍可创建
+    // Test closed accounts go back to the system program, and PDAs holding lamports can still be created
+    #[tokio::test]
+    async fn test_recreate_closed_and_prefunded_accounts() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let operator = Keypair::new();
+
+        let (collection_key, _) = setup_nft(&mut context, &program_id, "Recreate").await;
+        let approval_key = operator_approval_address(&program_id, &authority, &collection_key, &operator.pubkey());
+        let approve = |approved| {
+            instruction::set_operator_approval(&program_id, &authority, &collection_key, &operator.pubkey(), approved, None)
+        };
+
+        // 同一交易内撤销后重新授权, 授权保留
+        // Revoking then re-approving in one transaction keeps the approval
+        process(&mut context, &[approve(true)], &[]).await.unwrap();
+        process(&mut context, &[approve(false), approve(true)], &[]).await.unwrap();
+        let account = context.banks_client.get_account(approval_key).await.unwrap().unwrap();
+        assert_eq!(account.owner, program_id);
+        assert_eq!(OperatorApproval::load(&account.data).unwrap().operator, operator.pubkey());
+
+        // 有人预先向NFT地址转账也不能阻止铸造
+        // Pre-funding the NFT address does not block minting
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let nft_key = nft_address(&program_id, &collection_key, 2);
+        fund(&mut context, &nft_key, rent.minimum_balance(0)).await;
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 2);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert_eq!(get_nft(&mut context, nft_key).await.serial_number, 2);
+        assert_eq!(balance(&mut context, nft_key).await, rent.minimum_balance(NFT_ACCOUNT_SIZE));
+    }
+
+    // 测试冻结和解冻NFT
+    // Test freeze and thaw NFT
+    #[tokio::test]
+    async fn test_freeze_and_thaw() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let freeze_authority = Keypair::new();
+        let recipient = Keypair::new();
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Freeze").await;
+        let freeze = |signer: &Pubkey| instruction::freeze_nft(&program_id, &nft_key, &collection_key, signer);
+
+        // 未设置冻结权限时不能冻结
+        // Cannot freeze before a freeze authority is set
+        let ix = freeze(&freeze_authority.pubkey());
+        let result = process(&mut context, &[ix], &[&freeze_authority]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InsufficientAuthority));
+
+        let ix = instruction::set_freeze_authority(
+            &program_id,
+            &collection_key,
+            &authority,
+            Some(freeze_authority.pubkey()),
+        );
+        process(&mut context, &[ix], &[]).await.unwrap();
+
+        // NFT所有者不能冻结
+        // NFT owner cannot freeze
+        let ix = freeze(&authority);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InsufficientAuthority));
+
+        let ix = freeze(&freeze_authority.pubkey());
+        process(&mut context, &[ix], &[&freeze_authority]).await.unwrap();
+        assert!(get_nft(&mut context, nft_key).await.is_frozen);
+
+        // 冻结的NFT不能转移、销毁或更新
+        // Frozen NFT cannot be transferred, burned or updated
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &recipient.pubkey());
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::NFTFrozen));
+        let ix = burn_nft_ix(&program_id, &nft_key, &collection_key, &authority, &authority);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::NFTFrozen));
+        let ix = update_nft_metadata_ix(&program_id, &nft_key, &authority, &collection_key);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::NFTFrozen));
+
+        // 解冻后恢复转移
+        // Transfers resume after thawing
+        let ix = instruction::thaw_nft(&program_id, &nft_key, &collection_key, &freeze_authority.pubkey());
+        process(&mut context, &[ix], &[&freeze_authority]).await.unwrap();
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &recipient.pubkey());
+        process(&mut context, &[ix], &[]).await.unwrap();
+        let nft = get_nft(&mut context, nft_key).await;
+        assert!(!nft.is_frozen);
+        assert_eq!(nft.owner, recipient.pubkey());
+    }
+
+    // 直接改写NFT所有者 (模拟灵魂绑定NFT的持有者)
+    // Overwrite the NFT owner directly (simulates a holder of a soulbound NFT)
+    async fn set_nft_owner(context: &mut ProgramTestContext, nft_key: Pubkey, owner: Pubkey) {
+        let mut account = context.banks_client.get_account(nft_key).await.unwrap().unwrap();
+        let mut nft = NFTAccount::deserialize(&mut &account.data[..]).unwrap();
+        nft.owner = owner;
+        nft.serialize(&mut &mut account.data[..]).unwrap();
+        context.set_account(&nft_key, &account.into());
+    }
+
+    // 测试灵魂绑定集合
+    // Test soulbound collection
+    #[tokio::test]
+    async fn test_soulbound_collection() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let holder = Keypair::new();
+        let collection_key = collection_address(&program_id, &authority, "Badge");
+        let nft_key = nft_address(&program_id, &collection_key, 1);
+
+        let ixs = [
+            initialize_collection_with_ix(&program_id, &authority, "Badge", TransferMode::Soulbound, vec![]),
+            mint_nft_ix(&program_id, &collection_key, &authority, 1),
+        ];
+        process(&mut context, &ixs, &[]).await.unwrap();
+        set_nft_owner(&mut context, nft_key, holder.pubkey()).await;
+
+        // 持有者不能转移
+        // Holder cannot transfer
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &holder.pubkey(), &authority);
+        let result = process(&mut context, &[ix], &[&holder]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::NonTransferable));
+
+        // 集合权限不能撤销
+        // Collection authority cannot revoke
+        let ix = burn_nft_ix(&program_id, &nft_key, &collection_key, &authority, &holder.pubkey());
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        // 持有者仍然可以销毁
+        // Holder can still burn
+        let ix = burn_nft_ix(&program_id, &nft_key, &collection_key, &holder.pubkey(), &holder.pubkey());
+        process(&mut context, &[ix], &[&holder]).await.unwrap();
+        assert!(is_tombstone(&mut context, &program_id, nft_key).await);
+    }
+
+    // 测试集合权限撤销灵魂绑定NFT
+    // Test collection authority revoking a soulbound NFT
+    #[tokio::test]
+    async fn test_authority_revocable_collection() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let holder = Keypair::new();
+        let stranger = Keypair::new();
+        let collection_key = collection_address(&program_id, &authority, "Credential");
+        let nft_key = nft_address(&program_id, &collection_key, 1);
+
+        let ixs = [
+            initialize_collection_with_ix(
+                &program_id,
+                &authority,
+                "Credential",
+                TransferMode::AuthorityRevocable,
+                vec![],
+            ),
+            mint_nft_ix(&program_id, &collection_key, &authority, 1),
+        ];
+        process(&mut context, &ixs, &[]).await.unwrap();
+        set_nft_owner(&mut context, nft_key, holder.pubkey()).await;
+
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &holder.pubkey(), &authority);
+        let result = process(&mut context, &[ix], &[&holder]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::NonTransferable));
+
+        // 只有集合权限可以撤销
+        // Only the collection authority can revoke
+        let ix = burn_nft_ix(&program_id, &nft_key, &collection_key, &stranger.pubkey(), &holder.pubkey());
+        let result = process(&mut context, &[ix], &[&stranger]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        // 撤销时租金退还给持有者而不是集合权限
+        // Revoking refunds the rent to the holder rather than the collection authority
+        let nft_lamports = context.banks_client.get_balance(nft_key).await.unwrap();
+        let ix = burn_nft_ix(&program_id, &nft_key, &collection_key, &authority, &holder.pubkey());
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert!(is_tombstone(&mut context, &program_id, nft_key).await);
+        let tombstone_lamports = context.banks_client.get_balance(nft_key).await.unwrap();
+        let holder_lamports = context.banks_client.get_balance(holder.pubkey()).await.unwrap();
+        assert_eq!(holder_lamports, nft_lamports - tombstone_lamports);
+    }
+
+    // 测试一口价购买并支付版税
+    // Test fixed-price purchase with royalty payment
+    #[tokio::test]
+    async fn test_buy_nft_pays_royalty() {
+        let (mut context, program_id) = setup().await;
+        let authority = Keypair::new();
+        let seller = Keypair::new();
+        let buyer = Keypair::new();
+        let price = 1_000_000_000;
+        fund(&mut context, &authority.pubkey(), 100_000_000).await;
+        fund(&mut context, &seller.pubkey(), 100_000_000).await;
+        fund(&mut context, &buyer.pubkey(), 2 * price).await;
+
+        let collection_key = collection_address(&program_id, &authority.pubkey(), "Market");
+        let nft_key = nft_address(&program_id, &collection_key, 1);
+        let ixs = [
+            initialize_collection_ix(&program_id, &authority.pubkey(), "Market"),
+            mint_nft_ix(&program_id, &collection_key, &authority.pubkey(), 1),
+            transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority.pubkey(), &seller.pubkey()),
+        ];
+        process(&mut context, &ixs, &[&authority]).await.unwrap();
+
+        // 非所有者不能挂单
+        // Non-owner cannot list
+        let ix = instruction::list_nft(&program_id, &nft_key, &collection_key, &buyer.pubkey(), price);
+        let result = process(&mut context, &[ix], &[&buyer]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidOwner));
+
+        let ix = instruction::list_nft(&program_id, &nft_key, &collection_key, &seller.pubkey(), price);
+        process(&mut context, &[ix], &[&seller]).await.unwrap();
+        let listing_key = listing_address(&program_id, &nft_key);
+        let listing_rent = balance(&mut context, listing_key).await;
+
+        // 价格不一致时拒绝
+        // Rejected when the price does not match
+        let ix = instruction::buy_nft(
+            &program_id,
+            &nft_key,
+            &collection_key,
+            &buyer.pubkey(),
+            &seller.pubkey(),
+            &[authority.pubkey()],
+            price - 1,
+        );
+        let result = process(&mut context, &[ix], &[&buyer]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidPrice));
+
+        // 版税必须付给集合权限
+        // Royalty must go to the collection authority
+        let ix = instruction::buy_nft(
+            &program_id,
+            &nft_key,
+            &collection_key,
+            &buyer.pubkey(),
+            &seller.pubkey(),
+            &[buyer.pubkey()],
+            price,
+        );
+        let result = process(&mut context, &[ix], &[&buyer]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidRoyaltyRecipient));
+
+        let authority_before = balance(&mut context, authority.pubkey()).await;
+        let seller_before = balance(&mut context, seller.pubkey()).await;
+        let buyer_before = balance(&mut context, buyer.pubkey()).await;
+        let ix = instruction::buy_nft(
+            &program_id,
+            &nft_key,
+            &collection_key,
+            &buyer.pubkey(),
+            &seller.pubkey(),
+            &[authority.pubkey()],
+            price,
+        );
+        process(&mut context, &[ix], &[&buyer]).await.unwrap();
+
+        // 5% 版税给集合权限, 其余和挂单租金给卖家
+        // 5% royalty to the collection authority, the rest plus listing rent to the seller
+        let royalty = price / 20;
+        assert_eq!(balance(&mut context, authority.pubkey()).await, authority_before + royalty);
+        assert_eq!(
+            balance(&mut context, seller.pubkey()).await,
+            seller_before + price - royalty + listing_rent
+        );
+        assert_eq!(balance(&mut context, buyer.pubkey()).await, buyer_before - price);
+        assert_eq!(get_nft(&mut context, nft_key).await.owner, buyer.pubkey());
+        assert!(context.banks_client.get_account(listing_key).await.unwrap().is_none());
+    }
+
+    // 测试取消挂单和失效挂单
+    // Test delisting and stale listings
+    #[tokio::test]
+    async fn test_delist_and_stale_listing() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let buyer = Keypair::new();
+        let recipient = Keypair::new();
+        let price = 1_000_000;
+        fund(&mut context, &buyer.pubkey(), 100_000_000).await;
+
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Delist").await;
+        let listing_key = listing_address(&program_id, &nft_key);
+        let buy = instruction::buy_nft(
+            &program_id,
+            &nft_key,
+            &collection_key,
+            &buyer.pubkey(),
+            &authority,
+            &[authority],
+            price,
+        );
+
+        // 取消挂单后不能购买
+        // Cannot buy after delisting
+        let delist = instruction::delist_nft(&program_id, &nft_key, &authority);
+        let ix = instruction::list_nft(&program_id, &nft_key, &collection_key, &authority, price);
+        process(&mut context, &[ix, delist], &[]).await.unwrap();
+        assert!(context.banks_client.get_account(listing_key).await.unwrap().is_none());
+        let result = process(&mut context, std::slice::from_ref(&buy), &[&buyer]).await;
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(0, InstructionError::IncorrectProgramId)
+        );
+
+        // 转移NFT时挂单被关闭, 租金退还卖家
+        // Transferring the NFT closes the listing and refunds its rent to the seller
+        let ix = instruction::list_nft(&program_id, &nft_key, &collection_key, &authority, price);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        let listing_rent = balance(&mut context, listing_key).await;
+        let ix = instruction::transfer_nft(
+            &program_id,
+            &nft_key,
+            &collection_key,
+            &authority,
+            &recipient.pubkey(),
+            &buyer.pubkey(),
+            None,
+        );
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidListing));
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &recipient.pubkey());
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert!(context.banks_client.get_account(listing_key).await.unwrap().is_none());
+        assert_eq!(get_nft(&mut context, nft_key).await.owner, recipient.pubkey());
+
+        // 新所有者可以挂单
+        // The new owner can list
+        fund(&mut context, &recipient.pubkey(), 100_000_000).await;
+        let ix = instruction::list_nft(&program_id, &nft_key, &collection_key, &recipient.pubkey(), 2 * price);
+        process(&mut context, &[ix], &[&recipient]).await.unwrap();
+        let listing = context.banks_client.get_account(listing_key).await.unwrap().unwrap();
+        let listing = Listing::load(&listing.data).unwrap();
+        assert_eq!((listing.seller, listing.price), (recipient.pubkey(), 2 * price));
+
+        // NFT回到旧卖家手中时旧挂单不会复活
+        // The old listing does not revive when the NFT returns to the old seller
+        let recipient_before = balance(&mut context, recipient.pubkey()).await;
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &recipient.pubkey(), &authority);
+        process(&mut context, &[ix], &[&recipient]).await.unwrap();
+        assert_eq!(balance(&mut context, recipient.pubkey()).await, recipient_before + listing_rent);
+        let result = process(&mut context, &[buy], &[&buyer]).await;
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(0, InstructionError::IncorrectProgramId)
+        );
+
+        // 销毁同样关闭挂单
+        // Burning closes the listing too
+        let ixs = [
+            instruction::list_nft(&program_id, &nft_key, &collection_key, &authority, price),
+            burn_nft_ix(&program_id, &nft_key, &collection_key, &authority, &authority),
+        ];
+        process(&mut context, &ixs, &[]).await.unwrap();
+        assert!(context.banks_client.get_account(listing_key).await.unwrap().is_none());
+        assert!(is_tombstone(&mut context, &program_id, nft_key).await);
+    }
+
+    // 测试创作者验证和版税分成
+    // Test creator verification and royalty splits
+    #[tokio::test]
+    async fn test_creator_royalty_split() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let first_creator = Keypair::new();
+        let second_creator = Keypair::new();
+        let buyer = Keypair::new();
+        let price = 1_000_000_000;
+        fund(&mut context, &first_creator.pubkey(), 10_000_000).await;
+        fund(&mut context, &second_creator.pubkey(), 10_000_000).await;
+        fund(&mut context, &buyer.pubkey(), 2 * price).await;
+
+        let creator = |address: Pubkey, share: u8| Creator { address, verified: true, share };
+        let collection_key = collection_address(&program_id, &authority, "Split");
+        let nft_key = nft_address(&program_id, &collection_key, 1);
+
+        // 份额合计必须为100
+        // Shares must sum to 100
+        let ix = initialize_collection_with_ix(
+            &program_id,
+            &authority,
+            "Split",
+            TransferMode::Transferable,
+            vec![creator(first_creator.pubkey(), 60), creator(second_creator.pubkey(), 30)],
+        );
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::InvalidCreators));
+
+        // 未签名的创作者不能被直接标记为已验证
+        // Creators that did not sign cannot be marked verified up front
+        let ixs = [
+            initialize_collection_with_ix(
+                &program_id,
+                &authority,
+                "Split",
+                TransferMode::Transferable,
+                vec![creator(first_creator.pubkey(), 60), creator(second_creator.pubkey(), 40)],
+            ),
+            mint_nft_ix(&program_id, &collection_key, &authority, 1),
+        ];
+        process(&mut context, &ixs, &[]).await.unwrap();
+        let collection = get_collection(&mut context, collection_key).await;
+        assert!(collection.creators.iter().all(|c| !c.verified));
+
+        // 只有列表中的创作者可以签名
+        // Only listed creators can sign
+        let sign = |signer: &Pubkey| instruction::sign_creator(&program_id, &collection_key, signer);
+        let result = process(&mut context, &[sign(&buyer.pubkey())], &[&buyer]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::CreatorNotFound));
+
+        process(
+            &mut context,
+            &[sign(&first_creator.pubkey()), sign(&second_creator.pubkey())],
+            &[&first_creator, &second_creator],
+        )
+        .await
+        .unwrap();
+        let collection = get_collection(&mut context, collection_key).await;
+        assert!(collection.creators.iter().all(|c| c.verified));
+
+        // 版税按 60/40 分给已验证创作者
+        // Royalty is split 60/40 between verified creators
+        let ix = instruction::list_nft(&program_id, &nft_key, &collection_key, &authority, price);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        let first_before = balance(&mut context, first_creator.pubkey()).await;
+        let second_before = balance(&mut context, second_creator.pubkey()).await;
+        let ix = instruction::buy_nft(
+            &program_id,
+            &nft_key,
+            &collection_key,
+            &buyer.pubkey(),
+            &authority,
+            &[first_creator.pubkey(), second_creator.pubkey()],
+            price,
+        );
+        process(&mut context, &[ix], &[&buyer]).await.unwrap();
+
+        let royalty = price / 20;
+        assert_eq!(balance(&mut context, first_creator.pubkey()).await, first_before + royalty * 60 / 100);
+        assert_eq!(balance(&mut context, second_creator.pubkey()).await, second_before + royalty * 40 / 100);
+        assert_eq!(get_nft(&mut context, nft_key).await.owner, buyer.pubkey());
+    }
+
+    // 测试顺序序列号和最大供应量
+    // Test sequential serial numbers and max supply
+    #[tokio::test]
+    async fn test_sequential_serials_and_max_supply() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let collection_key = collection_address(&program_id, &authority, "Capped");
+
+        let ix = instruction::initialize_collection(
+            &program_id,
+            &authority,
+            &authority,
+            "Capped",
+            "CAP",
+            "https://test.com",
+            5,
+            true,
+            TransferMode::Transferable,
+            vec![],
+            Some(2),
+            SerialMode::Sequential,
+        );
+        process(&mut context, &[ix], &[]).await.unwrap();
+
+        // 程序分配序列号, 请求的序列号被忽略
+        // The program assigns the serial, the requested one is ignored
+        let mut ix = mint_nft_ix(&program_id, &collection_key, &authority, 1);
+        ix.data = NFTInstruction::MintNFT {
+            metadata_uri: "https://test.com/1.json".to_string(),
+            serial_number: 42,
+            bump: find_nft_address(&program_id, &collection_key, 1).1,
+            recipient: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        process(&mut context, &[ix], &[]).await.unwrap();
+        let nft = get_nft(&mut context, nft_address(&program_id, &collection_key, 1)).await;
+        assert_eq!(nft.serial_number, 1);
+
+        // NFT账户必须由分配的序列号推导
+        // The NFT account must be derived from the assigned serial
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 3);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 2);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        let collection = get_collection(&mut context, collection_key).await;
+        assert_eq!(collection.total_minted, 2);
+
+        // 超过最大供应量的铸造被拒绝
+        // Mints past the cap are rejected
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 3);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::MaxSupplyReached));
+    }
+
+    // 测试调用方指定的序列号不能超过最大供应量
+    // Test caller-assigned serials cannot exceed the max supply
+    #[tokio::test]
+    async fn test_caller_assigned_serials_within_max_supply() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let collection_key = collection_address(&program_id, &authority, "Numbered");
+
+        let ix = instruction::initialize_collection(
+            &program_id,
+            &authority,
+            &authority,
+            "Numbered",
+            "NUM",
+            "https://test.com",
+            5,
+            true,
+            TransferMode::Transferable,
+            vec![],
+            Some(3),
+            SerialMode::CallerAssigned,
+        );
+        process(&mut context, &[ix], &[]).await.unwrap();
+
+        // 超出上限的序列号被拒绝, 即使供应量还有余量
+        // Serials past the cap are rejected even while supply remains
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 4);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::MaxSupplyReached));
+
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 3);
+        process(&mut context, &[ix], &[]).await.unwrap();
+        assert_eq!(get_nft(&mut context, nft_address(&program_id, &collection_key, 3)).await.serial_number, 3);
+    }
+
+    // 测试账户类型标识和版本号
+    // Test account discriminators and schema versions
+    #[tokio::test]
+    async fn test_account_discriminators() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Tagged").await;
+
+        // 索引器可以从原始字节区分账户类型
+        // Indexers can tell account kinds apart from raw bytes
+        let collection = context.banks_client.get_account(collection_key).await.unwrap().unwrap();
+        let nft = context.banks_client.get_account(nft_key).await.unwrap().unwrap();
+        assert_eq!(collection.data[..2], [AccountKey::Collection as u8, CollectionAccount::VERSION]);
+        assert_eq!(nft.data[..2], [AccountKey::NFT as u8, NFTAccount::VERSION]);
+
+        // 把NFT账户当作集合账户传入会被拒绝
+        // Passing an NFT account where a collection is expected is rejected
+        let ix = mint_nft_ix(&program_id, &nft_key, &authority, 2);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::AccountTypeMismatch));
+
+        // 未知版本会被拒绝
+        // Unknown versions are rejected
+        let mut account = context.banks_client.get_account(nft_key).await.unwrap().unwrap();
+        account.data[1] = NFTAccount::VERSION + 1;
+        context.set_account(&nft_key, &account.into());
+        let ix = update_nft_metadata_ix(&program_id, &nft_key, &authority, &collection_key);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::UnsupportedAccountVersion));
+
+        // 数据大小与版本不符会被拒绝
+        // Data whose size does not match the version is rejected
+        let mut data = collection.data;
+        assert!(CollectionAccount::load(&data).is_ok());
+        assert_eq!(CollectionAccount::load(&data[..data.len() - 1]).unwrap_err(), ProgramError::InvalidAccountData);
+        data.push(0);
+        assert_eq!(CollectionAccount::load(&data).unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    // 旧版 (v0) 账户字节: 按原始布局手工编码并补零到旧账户大小
+    // Legacy (v0) account bytes: hand-encoded in the original layout and zero-padded to the old size
+    fn legacy_fixture(fields: &[&[u8]], size: usize) -> Vec<u8> {
+        let mut data = fields.concat();
+        data.resize(size, 0);
+        data
+    }
+
+    // Borsh 字符串编码 (长度前缀)
+    // Borsh string encoding (length prefixed)
+    fn borsh_string(value: &str) -> Vec<u8> {
+        [&(value.len() as u32).to_le_bytes()[..], value.as_bytes()].concat()
+    }
+
+    // 写入由程序拥有的账户
+    // Write an account owned by the program
+    async fn set_program_account(context: &mut ProgramTestContext, program_id: &Pubkey, address: Pubkey, data: Vec<u8>) {
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let account = solana_sdk::account::Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: *program_id,
+            executable: false,
+            rent_epoch: 0,
+        };
+        context.set_account(&address, &account.into());
+    }
+
+    // 测试旧版账户迁移
+    // Test legacy account migration
+    #[tokio::test]
+    async fn test_migrate_legacy_accounts() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let payer = Keypair::new();
+        fund(&mut context, &payer.pubkey(), 100_000_000).await;
+        let collection_key = collection_address(&program_id, &authority, "Legacy");
+        let nft_key = nft_address(&program_id, &collection_key, 7);
+
+        let collection_fixture = legacy_fixture(
+            &[
+                authority.as_ref(),
+                &borsh_string("Legacy"),
+                &borsh_string("OLD"),
+                &borsh_string("https://test.com/legacy"),
+                &[5],
+                &[1],
+                &7u64.to_le_bytes(),
+            ],
+            COLLECTION_ACCOUNT_V0_SIZE,
+        );
+        let nft_fixture = legacy_fixture(
+            &[
+                authority.as_ref(),
+                collection_key.as_ref(),
+                &borsh_string("https://test.com/7.json"),
+                &[1],
+                &7u64.to_le_bytes(),
+                &1_600_000_000i64.to_le_bytes(),
+                &1_650_000_000i64.to_le_bytes(),
+            ],
+            NFT_ACCOUNT_V0_SIZE,
+        );
+        set_program_account(&mut context, &program_id, collection_key, collection_fixture).await;
+        set_program_account(&mut context, &program_id, nft_key, nft_fixture).await;
+
+        // 迁移前旧账户无法使用
+        // Legacy accounts are unusable before migration
+        let ix = mint_nft_ix(&program_id, &collection_key, &authority, 8);
+        assert!(process(&mut context, &[ix], &[]).await.is_err());
+
+        let migrate_ix = |account: Pubkey| instruction::migrate_account(&program_id, &account, &payer.pubkey(), None);
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let payer_before = balance(&mut context, payer.pubkey()).await;
+        process(&mut context, &[migrate_ix(collection_key), migrate_ix(nft_key)], &[&payer])
+            .await
+            .unwrap();
+
+        // 付款人支付新增的租金
+        // The payer covers the extra rent
+        let extra_rent = rent.minimum_balance(COLLECTION_ACCOUNT_SIZE) - rent.minimum_balance(COLLECTION_ACCOUNT_V0_SIZE)
+            + rent.minimum_balance(NFT_ACCOUNT_SIZE) - rent.minimum_balance(NFT_ACCOUNT_V0_SIZE);
+        assert_eq!(balance(&mut context, payer.pubkey()).await, payer_before - extra_rent);
+
+        let account = context.banks_client.get_account(collection_key).await.unwrap().unwrap();
+        assert_eq!(account.data.len(), COLLECTION_ACCOUNT_SIZE);
+        let collection = CollectionAccount::load(&account.data).unwrap();
+        assert_eq!(collection.authority, authority);
+        assert_eq!(collection.name, "Legacy");
+        assert_eq!(collection.symbol, "OLD");
+        assert_eq!(collection.uri, "https://test.com/legacy");
+        assert_eq!(collection.royalty_percentage, 5);
+        assert!(collection.is_mutable);
+        assert_eq!(collection.total_minted, 7);
+        assert_eq!(collection.transfer_mode, TransferMode::Transferable);
+        assert_eq!(collection.serial_mode, SerialMode::CallerAssigned);
+        assert_eq!(collection.bump, find_collection_address(&program_id, &authority, "Legacy").1);
+        assert_eq!(collection.original_authority, authority);
+
+        let account = context.banks_client.get_account(nft_key).await.unwrap().unwrap();
+        assert_eq!(account.data.len(), NFT_ACCOUNT_SIZE);
+        let nft = NFTAccount::load(&account.data).unwrap();
+        assert_eq!(nft.owner, authority);
+        assert_eq!(nft.collection, collection_key);
+        assert_eq!(nft.metadata_uri, "https://test.com/7.json");
+        assert_eq!(nft.serial_number, 7);
+        assert_eq!(nft.created_at, 1_600_000_000);
+        assert_eq!(nft.updated_at, 1_650_000_000);
+        assert_eq!(nft.delegate, None);
+        assert!(!nft.is_frozen);
+        assert_eq!(nft.bump, find_nft_address(&program_id, &collection_key, 7).1);
+        assert_eq!(nft.minter, None);
+
+        // 再次迁移是空操作
+        // Migrating again is a no-op
+        process(&mut context, &[migrate_ix(collection_key)], &[&payer]).await.unwrap();
+
+        // v1 账户 (有类型标识, 无bump) 也会被迁移
+        // v1 accounts (discriminator, no bump) are migrated too
+        let v1_nft_key = nft_address(&program_id, &collection_key, 6);
+        let mut v1_fixture = NFTAccountV1 {
+            key: AccountKey::NFT,
+            version: 1,
+            owner: authority,
+            collection: collection_key,
+            metadata_uri: "https://test.com/6.json".to_string(),
+            is_minted: true,
+            serial_number: 6,
+            created_at: 1_600_000_000,
+            updated_at: 1_600_000_000,
+            delegate: None,
+            is_frozen: true,
+        }
+        .try_to_vec()
+        .unwrap();
+        v1_fixture.resize(NFT_ACCOUNT_V1_SIZE, 0);
+        set_program_account(&mut context, &program_id, v1_nft_key, v1_fixture).await;
+        process(&mut context, &[migrate_ix(v1_nft_key)], &[&payer]).await.unwrap();
+        let nft = get_nft(&mut context, v1_nft_key).await;
+        assert_eq!(nft.version, NFTAccount::VERSION);
+        assert!(nft.is_frozen);
+        assert_eq!(nft.bump, find_nft_address(&program_id, &collection_key, 6).1);
+
+        // v2 集合 (有bump, 无原始权限) 在权限转移后迁移, 需传入原始权限
+        // A v2 collection (bump, no original authority) migrated after an authority transfer needs the original
+        // authority
+        let v2_collection_key = collection_address(&program_id, &authority, "Handed Over");
+        let mut v2_fixture = CollectionAccountV2 {
+            key: AccountKey::Collection,
+            version: 2,
+            authority: payer.pubkey(),
+            name: "Handed Over".to_string(),
+            symbol: "OLD".to_string(),
+            uri: "https://test.com/v2".to_string(),
+            royalty_percentage: 5,
+            is_mutable: true,
+            total_minted: 3,
+            pending_authority: None,
+            freeze_authority: None,
+            transfer_mode: TransferMode::Transferable,
+            creators: vec![],
+            max_supply: None,
+            serial_mode: SerialMode::Sequential,
+            total_burned: 0,
+            bump: find_collection_address(&program_id, &authority, "Handed Over").1,
+        }
+        .try_to_vec()
+        .unwrap();
+        v2_fixture.resize(COLLECTION_ACCOUNT_V2_SIZE, 0);
+        set_program_account(&mut context, &program_id, v2_collection_key, v2_fixture).await;
+        let result = process(&mut context, &[migrate_ix(v2_collection_key)], &[&payer]).await;
+        assert_eq!(result.unwrap_err(), TransactionError::InstructionError(0, InstructionError::InvalidSeeds));
+        let ix = instruction::migrate_account(&program_id, &v2_collection_key, &payer.pubkey(), Some(&authority));
+        process(&mut context, &[ix], &[&payer]).await.unwrap();
+        let collection = get_collection(&mut context, v2_collection_key).await;
+        assert_eq!(collection.version, CollectionAccount::VERSION);
+        assert_eq!((collection.authority, collection.original_authority), (payer.pubkey(), authority));
+        assert_eq!(collection.total_minted, 3);
+
+        // v1 大小但类型标识不符的账户不会按 v1 布局迁移
+        // An account of v1 size with the wrong discriminator is not migrated as the v1 layout
+        let mismatched_key = nft_address(&program_id, &collection_key, 5);
+        let mut mismatched = vec![0; NFT_ACCOUNT_V1_SIZE];
+        mismatched[..2].copy_from_slice(&[AccountKey::Collection as u8, 1]);
+        set_program_account(&mut context, &program_id, mismatched_key, mismatched).await;
+        let result = process(&mut context, &[migrate_ix(mismatched_key)], &[&payer]).await;
+        assert_eq!(result.unwrap_err(), custom_error(NFTError::UnsupportedAccountVersion));
+
+        // 迁移后的账户可以正常使用
+        // Migrated accounts work as usual
+        let new_owner = Keypair::new().pubkey();
+        let ixs = [
+            mint_nft_ix(&program_id, &collection_key, &authority, 8),
+            transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &new_owner),
+        ];
+        process(&mut context, &ixs, &[]).await.unwrap();
+        assert_eq!(get_collection(&mut context, collection_key).await.total_minted, 8);
+        assert_eq!(get_nft(&mut context, nft_key).await.owner, new_owner);
+    }
+
+    // 测试超过32字节的集合名称
+    // Test collection names longer than 32 bytes
+    #[tokio::test]
+    async fn test_long_collection_name() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let name = "A Collection Name Well Past The Seed Limit";
+        assert!(name.len() > 32);
+
+        let ix = initialize_collection_ix(&program_id, &authority, name);
+        process(&mut context, &[ix], &[]).await.unwrap();
+
+        // 客户端可以从 (权限, 名称) 找到集合
+        // Clients can find the collection from (authority, name)
+        let (collection_key, _) = find_collection_address(&program_id, &authority, name);
+        assert_eq!(get_collection(&mut context, collection_key).await.name, name);
+    }
+
+    // 测试存储和传入的bump: 错误的bump被拒绝, 计算单元不随bump变化
+    // Test stored and supplied bumps: wrong bumps are rejected and compute units do not vary with the bump
+    #[tokio::test]
+    async fn test_stored_bump_compute_units() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let (collection_key, nft_key) = setup_nft(&mut context, &program_id, "Bumps").await;
+        assert_eq!(get_collection(&mut context, collection_key).await.bump, find_collection_address(&program_id, &authority, "Bumps").1);
+        assert_eq!(get_nft(&mut context, nft_key).await.bump, find_nft_address(&program_id, &collection_key, 1).1);
+
+        // 错误的bump被拒绝
+        // A wrong bump is rejected
+        let mut ix = mint_nft_ix(&program_id, &collection_key, &authority, 2);
+        let mut data = NFTInstruction::try_from_slice(&ix.data).unwrap();
+        if let NFTInstruction::MintNFT { ref mut bump, .. } = data {
+            *bump = bump.wrapping_sub(1);
+        }
+        ix.data = data.try_to_vec().unwrap();
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+
+        // 没有挂单时, 错误的挂单bump同样被拒绝
+        // A wrong listing bump is rejected even when there is no listing
+        let mut ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &Pubkey::new_unique());
+        let mut data = NFTInstruction::try_from_slice(&ix.data).unwrap();
+        if let NFTInstruction::TransferNFT { ref mut listing_bump, .. } = data {
+            *listing_bump = listing_bump.wrapping_sub(1);
+        }
+        ix.data = data.try_to_vec().unwrap();
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+
+        // 模拟执行并读取消耗的计算单元: 在链上搜索bump时, 规范bump每比255小一, 就多一次 create_program_address
+        // (1500单元); 传入bump后, 不同bump的同一指令消耗相同。原生测试只计内置程序的固定开销, 用
+        // `cargo test-sbf` 运行SBF程序时差值反映程序的真实开销
+        // Simulate and read the consumed compute units: searching for a bump on chain costs one more
+        // create_program_address (1500 units) for every step the canonical bump is below 255, with supplied bumps the
+        // same instruction costs the same whatever the bump. Native tests only charge the builtin's fixed cost, under
+        // `cargo test-sbf` the difference reflects the real program
+        const CREATE_PROGRAM_ADDRESS_UNITS: u64 = 1500;
+        let nft_bump = |serial: u64| find_nft_address(&program_id, &collection_key, serial).1;
+        let listing_bump =
+            |serial: u64| find_listing_address(&program_id, &nft_address(&program_id, &collection_key, serial)).1;
+        let serial_where =
+            |bump: &dyn Fn(u64) -> u8, wanted: fn(u8) -> bool| (10..).find(|serial| wanted(bump(*serial))).unwrap();
+
+        let mint_ix = |serial: u64| mint_nft_ix(&program_id, &collection_key, &authority, serial);
+        let high_units = simulated_units(&mut context, &[mint_ix(serial_where(&nft_bump, |bump| bump == 255))]).await;
+        let low_units = simulated_units(&mut context, &[mint_ix(serial_where(&nft_bump, |bump| bump < 250))]).await;
+        assert!(high_units > 0);
+        assert!(high_units.abs_diff(low_units) < CREATE_PROGRAM_ADDRESS_UNITS, "{} / {}", high_units, low_units);
+
+        let high_serial = serial_where(&listing_bump, |bump| bump == 255);
+        let low_serial = serial_where(&listing_bump, |bump| bump < 250);
+        process(&mut context, &[mint_ix(high_serial), mint_ix(low_serial)], &[]).await.unwrap();
+        let recipient = Pubkey::new_unique();
+        let transfer_ix = |serial: u64| {
+            let nft_key = nft_address(&program_id, &collection_key, serial);
+            transfer_nft_ix(&program_id, &nft_key, &collection_key, &authority, &recipient)
+        };
+        let high_units = simulated_units(&mut context, &[transfer_ix(high_serial)]).await;
+        let low_units = simulated_units(&mut context, &[transfer_ix(low_serial)]).await;
+        assert!(high_units > 0);
+        assert!(high_units.abs_diff(low_units) < CREATE_PROGRAM_ADDRESS_UNITS, "{} / {}", high_units, low_units);
+    }
+
+    // 模拟交易并返回消耗的计算单元
+    // Simulate a transaction and return the compute units it consumed
+    async fn simulated_units(context: &mut ProgramTestContext, instructions: &[Instruction]) -> u64 {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+        assert_eq!(simulation.result, Some(Ok(())));
+        simulation.simulation_details.unwrap().units_consumed
+    }
+
+    // 测试由单独的付款人支付租金
+    // Test rent paid by a separate payer
+    #[tokio::test]
+    async fn test_separate_fee_payer() {
+        let (mut context, program_id) = setup().await;
+        let payer = context.payer.pubkey();
+        let authority = Keypair::new();
+        let collection_key = collection_address(&program_id, &authority.pubkey(), "Relayed");
+
+        // 权限账户没有SOL, 只共同签名
+        // The authority holds no SOL and only co-signs
+        let ixs = [
+            instruction::initialize_collection(
+                &program_id,
+                &payer,
+                &authority.pubkey(),
+                "Relayed",
+                "TEST",
+                "https://test.com",
+                5,
+                true,
+                TransferMode::Transferable,
+                vec![],
+                None,
+                SerialMode::CallerAssigned,
+            ),
+            instruction::mint_nft(
+                &program_id,
+                &payer,
+                &collection_key,
+                &authority.pubkey(),
+                1,
+                "https://test.com/1.json",
+                None,
+            ),
+        ];
+        let payer_before = balance(&mut context, payer).await;
+        process(&mut context, &ixs, &[&authority]).await.unwrap();
+
+        assert_eq!(balance(&mut context, authority.pubkey()).await, 0);
+        assert!(balance(&mut context, payer).await < payer_before);
+        let nft = get_nft(&mut context, nft_address(&program_id, &collection_key, 1)).await;
+        assert_eq!(nft.owner, authority.pubkey());
+    }
+
+    // 测试直接铸造给接收者
+    // Test minting directly to a recipient
+    #[tokio::test]
+    async fn test_mint_to_recipient() {
+        let (mut context, program_id) = setup().await;
+        let authority = context.payer.pubkey();
+        let collector = Keypair::new();
+        let friend = Keypair::new().pubkey();
+        let collection_key = collection_address(&program_id, &authority, "Drop");
+        let nft_key = nft_address(&program_id, &collection_key, 1);
+
+        let mut ix = mint_nft_ix(&program_id, &collection_key, &authority, 1);
+        let mut data = NFTInstruction::try_from_slice(&ix.data).unwrap();
+        if let NFTInstruction::MintNFT { ref mut recipient, .. } = data {
+            *recipient = Some(collector.pubkey());
+        }
+        ix.data = data.try_to_vec().unwrap();
+        let ixs = [initialize_collection_ix(&program_id, &authority, "Drop"), ix];
+        process(&mut context, &ixs, &[]).await.unwrap();
+
+        // 接收者直接拥有NFT, 铸造者单独记录
+        // The recipient owns the NFT directly, the minter is recorded separately
+        let nft = get_nft(&mut context, nft_key).await;
+        assert_eq!(nft.owner, collector.pubkey());
+        assert_eq!(nft.minter, Some(authority));
+
+        // 接收者可以立即转移, 铸造者记录保持不变
+        // The recipient can transfer right away, and the minter record stays
+        let ix = transfer_nft_ix(&program_id, &nft_key, &collection_key, &collector.pubkey(), &friend);
+        process(&mut context, &[ix], &[&collector]).await.unwrap();
+        let nft = get_nft(&mut context, nft_key).await;
+        assert_eq!(nft.owner, friend);
+        assert_eq!(nft.minter, Some(authority));
+    }
+
+    // 通过CPI调用本程序的示例游戏程序, 以PDA作为集合权限; 指令数据为 [操作, 本程序PDA的bump]
+    // Example game program that calls this program through CPI, with a PDA as the collection authority; instruction
+    // data is [action, bump of this program's PDA]
+    fn process_game_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        let [nft_program, collection, nft, game_authority, system_program, payer, player, listing] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let (_, bump) = Pubkey::find_program_address(&[b"game"], program_id);
+        let seeds: &[&[u8]] = &[b"game", &[bump]];
+        match data[0] {
+            0 => cpi::initialize_collection(
+                nft_program,
+                collection,
+                game_authority,
+                system_program,
+                payer,
+                "Game Items",
+                "GAME",
+                "https://game.com",
+                0,
+                true,
+                TransferMode::Transferable,
+                vec![],
+                None,
+                SerialMode::Sequential,
+                data[1],
+                &[seeds],
+            ),
+            1 => cpi::mint_nft(
+                nft_program,
+                collection,
+                nft,
+                game_authority,
+                system_program,
+                payer,
+                1,
+                "https://game.com/1.json",
+                None,
+                data[1],
+                &[seeds],
+            ),
+            2 => cpi::transfer_nft(
+                nft_program,
+                nft,
+                game_authority,
+                player,
+                collection,
+                listing,
+                game_authority,
+                None,
+                data[1],
+                &[seeds],
+            ),
+            _ => cpi::burn_nft(nft_program, nft, player, player, collection, listing, player, None, data[1], &[]),
+        }
+    }
+
+    // 测试其他程序通过CPI铸造、转移和销毁
+    // Test another program minting, transferring and burning through CPI
+    #[tokio::test]
+    async fn test_cpi_from_another_program() {
+        let program_id = Pubkey::new_unique();
+        let game_id = Pubkey::new_unique();
+        let mut program_test = ProgramTest::new("nft_contract", program_id, processor!(process_instruction));
+        program_test.add_program("game", game_id, processor!(process_game_instruction));
+        let mut context = program_test.start_with_context().await;
+
+        let payer = context.payer.pubkey();
+        let player = Keypair::new();
+        let game_authority = Pubkey::find_program_address(&[b"game"], &game_id).0;
+        let collection_key = collection_address(&program_id, &game_authority, "Game Items");
+        let nft_key = nft_address(&program_id, &collection_key, 1);
+        let collection_bump = find_collection_address(&program_id, &game_authority, "Game Items").1;
+        let nft_bump = find_nft_address(&program_id, &collection_key, 1).1;
+        let listing_bump = find_listing_address(&program_id, &nft_key).1;
+        let game_ix = |action: u8, player_is_signer: bool| {
+            let bump = [collection_bump, nft_bump, listing_bump, listing_bump][action as usize];
+            Instruction::new_with_bytes(
+                game_id,
+                &[action, bump],
+                vec![
+                    AccountMeta::new_readonly(program_id, false),
+                    AccountMeta::new(collection_key, false),
+                    AccountMeta::new(nft_key, false),
+                    AccountMeta::new(game_authority, false),
+                    AccountMeta::new_readonly(system_program::id(), false),
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(player.pubkey(), player_is_signer),
+                    AccountMeta::new(listing_address(&program_id, &nft_key), false),
+                ],
+            )
+        };
+
+        process(&mut context, &[game_ix(0, false), game_ix(1, false)], &[]).await.unwrap();
+        let collection = get_collection(&mut context, collection_key).await;
+        assert_eq!(collection.authority, game_authority);
+        let nft = get_nft(&mut context, nft_key).await;
+        assert_eq!(nft.owner, game_authority);
+
+        process(&mut context, &[game_ix(2, false)], &[]).await.unwrap();
+        assert_eq!(get_nft(&mut context, nft_key).await.owner, player.pubkey());
+
+        // 玩家签名后由游戏程序代为销毁
+        // The game program burns on the player's behalf once the player signs
+        process(&mut context, &[game_ix(3, true)], &[&player]).await.unwrap();
+        assert!(is_tombstone(&mut context, &program_id, nft_key).await);
+        assert_eq!(get_collection(&mut context, collection_key).await.total_burned, 1);
+    }
+
+    // 测试从交易日志解码事件, 只保留本程序输出的数据
+    // Test decoding events from transaction logs, keeping only data emitted by this program
     #[test]
-    fn test_update_collection_metadata() {
-        // 类似于上面的测试，但针对更新集合元数据指令
-        // Similar to the above test, but for the update collection metadata instruction
+    fn test_event_log_decoding() {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let program_id = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let transferred = event::NFTEvent::NFTTransferred {
+            nft: Pubkey::new_unique(),
+            from: Pubkey::new_unique(),
+            to: Pubkey::new_unique(),
+        };
+        let updated = event::NFTEvent::MetadataUpdated {
+            account: Pubkey::new_unique(),
+            old_uri: "https://test.com/1.json".to_string(),
+            new_uri: "ipfs://updated".to_string(),
+        };
+        let data_line = |event: &event::NFTEvent| format!("Program data: {}", STANDARD.encode(event.try_to_vec().unwrap()));
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: invoke misleading message".to_string(),
+            data_line(&transferred),
+            format!("Program {} invoke [2]", other_program),
+            data_line(&updated),
+            format!("Program {} success", other_program),
+            "Program data: bm90IGFuIGV2ZW50".to_string(),
+            data_line(&updated),
+            format!("Program {} consumed 5000 of 200000 compute units", program_id),
+            format!("Program {} success", program_id),
+            data_line(&transferred),
+        ];
+
+        // 其他程序的数据、无法解码的数据以及调用之外的数据都被忽略
+        // Data from other programs, undecodable data and data outside an invocation are all ignored
+        assert_eq!(event::parse_logs(&program_id, &logs), vec![transferred, updated]);
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,310 @@
+use std::fmt;
+
+use borsh::BorshDeserialize;
+use nft_contract::{
+    error::NFTError,
+    event::{parse_logs, NFTEvent},
+    instruction::NFTInstruction,
+    state::{
+        is_uninitialized, AccountKey, CollectionAccount, Creator, Listing, NFTAccount, OperatorApproval, ProgramAccount,
+    },
+};
+use num_traits::FromPrimitive;
+use serde_json::{json, Value};
+use solana_program::{
+    instruction::{CompiledInstruction, InstructionError},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[cfg(test)]
+mod test;
+
+/// 解码后的本程序指令, 无法解码的指令同样保留
+/// A decoded instruction of this program, instructions that fail to decode are kept too
+#[derive(Debug, Clone)]
+pub struct DecodedInstruction {
+    /// 所属顶层指令的位置
+    /// Index of the top-level instruction it belongs to
+    pub top_level_index: u8,
+
+    /// 是否为CPI产生的内部指令
+    /// Whether it is an inner instruction produced by CPI
+    pub is_inner: bool,
+
+    /// 解码后的指令, 无法解码时为解码错误
+    /// Decoded instruction, or the decode error when it cannot be decoded
+    pub instruction: Result<NFTInstruction, NFTError>,
+
+    /// 原始指令数据
+    /// Raw instruction data
+    pub data: Vec<u8>,
+
+    /// 按指令顺序排列的账户 (跳过超出账户列表的索引)
+    /// Accounts in instruction order (indexes past the account list are skipped)
+    pub accounts: Vec<Pubkey>,
+}
+
+/// 解码后的程序错误, 未知的错误码保留原值
+/// A decoded program error, unknown codes keep their raw value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodedError {
+    /// `ProgramError::Custom` 中的错误码
+    /// Error code from `ProgramError::Custom`
+    pub code: u32,
+
+    /// 对应的 `NFTError` 变体
+    /// Matching `NFTError` variant
+    pub error: Option<NFTError>,
+}
+
+impl DecodedError {
+    /// 从自定义错误码解码
+    /// Decode from a custom error code
+    pub fn from_code(code: u32) -> Self {
+        Self { code, error: NFTError::from_u32(code) }
+    }
+}
+
+impl fmt::Display for DecodedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.error {
+            Some(error) => write!(f, "NFTError::{:?} (Custom({})): {}", error, self.code, error),
+            None => write!(f, "未知错误 | Unknown error (Custom({}))", self.code),
+        }
+    }
+}
+
+/// 按程序的加载规则解码出的程序账户
+/// A program account decoded with the program's loading rules
+#[derive(Debug, Clone)]
+pub enum DecodedAccount {
+    /// 集合账户
+    /// Collection account
+    Collection(CollectionAccount),
+
+    /// NFT账户
+    /// NFT account
+    NFT(NFTAccount),
+
+    /// 操作员授权账户
+    /// Operator approval account
+    OperatorApproval(OperatorApproval),
+
+    /// 挂单账户
+    /// Listing account
+    Listing(Listing),
+}
+
+impl DecodedAccount {
+    /// 账户类型名, 与IDL中的账户名相同
+    /// Account type name, the same as the account name in the IDL
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DecodedAccount::Collection(_) => "CollectionAccount",
+            DecodedAccount::NFT(_) => "NFTAccount",
+            DecodedAccount::OperatorApproval(_) => "OperatorApproval",
+            DecodedAccount::Listing(_) => "Listing",
+        }
+    }
+
+    /// 转换为 `{"type": .., "data": ..}` JSON, 字段与 `fixtures/borsh_fixtures.json` 相同
+    /// (不含类型标识和版本, 64位整数为字符串)
+    /// Convert to `{"type": .., "data": ..}` JSON with the same fields as `fixtures/borsh_fixtures.json`
+    /// (without the discriminator and version, 64-bit integers as strings)
+    pub fn to_json(&self) -> Value {
+        let data = match self {
+            DecodedAccount::Collection(account) => json!({
+                "authority": account.authority.to_string(),
+                "name": account.name,
+                "symbol": account.symbol,
+                "uri": account.uri,
+                "royalty_percentage": account.royalty_percentage,
+                "is_mutable": account.is_mutable,
+                "total_minted": account.total_minted.to_string(),
+                "pending_authority": account.pending_authority.map(|key| key.to_string()),
+                "freeze_authority": account.freeze_authority.map(|key| key.to_string()),
+                "transfer_mode": format!("{:?}", account.transfer_mode),
+                "creators": account.creators.iter().map(creator_json).collect::<Vec<_>>(),
+                "max_supply": account.max_supply.map(|supply| supply.to_string()),
+                "serial_mode": format!("{:?}", account.serial_mode),
+                "total_burned": account.total_burned.to_string(),
+                "bump": account.bump,
+                "original_authority": account.original_authority.to_string(),
+            }),
+            DecodedAccount::NFT(account) => json!({
+                "owner": account.owner.to_string(),
+                "collection": account.collection.to_string(),
+                "metadata_uri": account.metadata_uri,
+                "is_minted": account.is_minted,
+                "serial_number": account.serial_number.to_string(),
+                "created_at": account.created_at.to_string(),
+                "updated_at": account.updated_at.to_string(),
+                "delegate": account.delegate.map(|key| key.to_string()),
+                "is_frozen": account.is_frozen,
+                "bump": account.bump,
+                "minter": account.minter.map(|key| key.to_string()),
+            }),
+            DecodedAccount::OperatorApproval(account) => json!({
+                "owner": account.owner.to_string(),
+                "collection": account.collection.to_string(),
+                "operator": account.operator.to_string(),
+                "expires_at": account.expires_at.map(|expires_at| expires_at.to_string()),
+                "bump": account.bump,
+            }),
+            DecodedAccount::Listing(account) => json!({
+                "nft": account.nft.to_string(),
+                "seller": account.seller.to_string(),
+                "price": account.price.to_string(),
+                "bump": account.bump,
+            }),
+        };
+        json!({ "type": self.type_name(), "data": data })
+    }
+}
+
+fn creator_json(creator: &Creator) -> Value {
+    json!({ "address": creator.address.to_string(), "verified": creator.verified, "share": creator.share })
+}
+
+/// 一笔交易中与本程序相关的全部记录
+/// All records of a transaction that concern this program
+#[derive(Debug, Clone, Default)]
+pub struct TransactionRecords {
+    /// 按执行顺序排列的本程序指令
+    /// This program's instructions in execution order
+    pub instructions: Vec<DecodedInstruction>,
+
+    /// 本程序输出的事件
+    /// Events emitted by this program
+    pub events: Vec<NFTEvent>,
+
+    /// 本程序返回的错误
+    /// Error returned by this program
+    pub error: Option<DecodedError>,
+}
+
+/// 将自定义错误码映射回 `NFTError`
+/// Map a custom error code back to `NFTError`
+pub fn decode_error_code(code: u32) -> Option<NFTError> {
+    NFTError::from_u32(code)
+}
+
+/// 将 `ProgramError::Custom` 映射回 `NFTError`
+/// Map `ProgramError::Custom` back to `NFTError`
+pub fn decode_program_error(error: &ProgramError) -> Option<NFTError> {
+    match error {
+        ProgramError::Custom(code) => decode_error_code(*code),
+        _ => None,
+    }
+}
+
+/// 将 `InstructionError::Custom` 映射回 `NFTError`
+/// Map `InstructionError::Custom` back to `NFTError`
+pub fn decode_instruction_error(error: &InstructionError) -> Option<NFTError> {
+    match error {
+        InstructionError::Custom(code) => decode_error_code(*code),
+        _ => None,
+    }
+}
+
+/// 从日志中找出本程序的自定义错误 ("Program <id> failed: custom program error: 0x..")
+/// Find this program's custom error in the logs ("Program <id> failed: custom program error: 0x..")
+pub fn decode_log_error<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Option<DecodedError> {
+    let prefix = format!("Program {} failed: custom program error: 0x", program_id);
+    logs.iter().find_map(|line| {
+        let code = line.as_ref().strip_prefix(&prefix)?;
+        u32::from_str_radix(code.trim(), 16).ok().map(DecodedError::from_code)
+    })
+}
+
+/// 按程序的加载规则 (类型标识, 版本和大小) 解码账户数据
+/// Decode account data with the program's loading rules (discriminator, version and size)
+pub fn decode_account(data: &[u8]) -> Result<DecodedAccount, ProgramError> {
+    if is_uninitialized(data) {
+        return Err(NFTError::NotInitialized.into());
+    }
+    match data.first().copied() {
+        Some(key) if key == AccountKey::Collection as u8 => {
+            CollectionAccount::load(data).map(DecodedAccount::Collection)
+        }
+        Some(key) if key == AccountKey::NFT as u8 => NFTAccount::load(data).map(DecodedAccount::NFT),
+        Some(key) if key == AccountKey::OperatorApproval as u8 => {
+            OperatorApproval::load(data).map(DecodedAccount::OperatorApproval)
+        }
+        Some(key) if key == AccountKey::Listing as u8 => Listing::load(data).map(DecodedAccount::Listing),
+        _ => Err(NFTError::AccountTypeMismatch.into()),
+    }
+}
+
+/// 解码账户数据为 `{"type": .., "data": ..}` JSON, 错误以可读文本返回 (自定义错误码解码为 `NFTError`)
+/// Decode account data into `{"type": .., "data": ..}` JSON, errors come back as readable text (custom codes decoded
+/// to `NFTError`)
+pub fn decode_account_json(data: &[u8]) -> Result<Value, String> {
+    decode_account(data).map(|account| account.to_json()).map_err(|error| match error {
+        ProgramError::Custom(code) => DecodedError::from_code(code).to_string(),
+        error => error.to_string(),
+    })
+}
+
+/// 解码指令数据
+/// Decode instruction data
+pub fn decode_instruction(data: &[u8]) -> Result<NFTInstruction, NFTError> {
+    NFTInstruction::try_from_slice(data).map_err(|_| NFTError::InvalidInstruction)
+}
+
+/// 解码编译后的指令, 不属于本程序时返回 `None`
+/// Decode a compiled instruction, returning `None` when it does not belong to this program
+pub fn decode_compiled_instruction(
+    program_id: &Pubkey,
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+) -> Option<Result<(NFTInstruction, Vec<Pubkey>), NFTError>> {
+    if account_keys.get(instruction.program_id_index as usize) != Some(program_id) {
+        return None;
+    }
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|index| account_keys.get(*index as usize).copied().ok_or(NFTError::InvalidInstruction))
+        .collect::<Result<Vec<_>, _>>();
+    Some(accounts.and_then(|accounts| Ok((decode_instruction(&instruction.data)?, accounts))))
+}
+
+/// 解码整笔交易: 顶层指令、按顶层指令位置分组的内部指令 (与RPC的 innerInstructions 相同) 以及日志
+/// Decode a whole transaction: top-level instructions, inner instructions grouped by top-level index (as in the RPC innerInstructions) and logs
+pub fn decode_transaction<S: AsRef<str>>(
+    program_id: &Pubkey,
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    inner_instructions: &[(u8, Vec<CompiledInstruction>)],
+    logs: &[S],
+) -> TransactionRecords {
+    let mut records = TransactionRecords::default();
+    for (index, instruction) in instructions.iter().enumerate() {
+        let index = index as u8;
+        let inner = inner_instructions
+            .iter()
+            .filter(|(inner_index, _)| *inner_index == index)
+            .flat_map(|(_, inner)| inner.iter().map(|instruction| (true, instruction)));
+        for (is_inner, compiled) in std::iter::once((false, instruction)).chain(inner) {
+            let Some(decoded) = decode_compiled_instruction(program_id, account_keys, compiled) else {
+                continue;
+            };
+            records.instructions.push(DecodedInstruction {
+                top_level_index: index,
+                is_inner,
+                instruction: decoded.map(|(instruction, _)| instruction),
+                data: compiled.data.clone(),
+                accounts: compiled
+                    .accounts
+                    .iter()
+                    .filter_map(|index| account_keys.get(*index as usize).copied())
+                    .collect(),
+            });
+        }
+    }
+    records.events = parse_logs(program_id, logs);
+    records.error = decode_log_error(program_id, logs);
+    records
+}
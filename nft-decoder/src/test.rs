@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use borsh::BorshSerialize;
+    use nft_contract::{
+        instruction,
+        state::{
+            find_collection_address, COLLECTION_ACCOUNT_SIZE, LISTING_SIZE, NFT_ACCOUNT_SIZE, OPERATOR_APPROVAL_SIZE,
+        },
+    };
+    use solana_program::{instruction::Instruction, message::Message};
+
+    // 测试错误码映射回枚举变体
+    // Test mapping error codes back to enum variants
+    #[test]
+    fn test_decode_error_codes() {
+        assert_eq!(decode_error_code(0), Some(NFTError::InvalidInstruction));
+        assert_eq!(decode_error_code(5), Some(NFTError::InsufficientAuthority));
+        assert_eq!(decode_error_code(6), Some(NFTError::AlreadyMinted));
+        assert_eq!(decode_program_error(&ProgramError::Custom(12)), Some(NFTError::NFTFrozen));
+        assert_eq!(decode_instruction_error(&InstructionError::Custom(24)), Some(NFTError::UriTooLong));
+        assert_eq!(decode_error_code(25), Some(NFTError::InvalidRoyaltyRecipient));
+        assert_eq!(decode_error_code(26), None);
+        assert_eq!(decode_program_error(&ProgramError::InvalidAccountData), None);
+
+        // 每个错误码都能映射回自身
+        // Every error code maps back to itself
+        for code in 0..26 {
+            assert_eq!(decode_error_code(code).map(|error| error as u32), Some(code));
+        }
+
+        assert_eq!(
+            DecodedError::from_code(6).to_string(),
+            "NFTError::AlreadyMinted (Custom(6)): NFT已铸造 | NFT already minted"
+        );
+        assert_eq!(DecodedError::from_code(99).to_string(), "未知错误 | Unknown error (Custom(99))");
+    }
+
+    // 测试解码整笔交易的指令、事件和错误
+    // Test decoding a whole transaction's instructions, events and errors
+    #[test]
+    fn test_decode_transaction() {
+        let program_id = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let collection = find_collection_address(&program_id, &authority, "Decoded").0;
+        let mint = instruction::mint_nft(&program_id, &authority, &collection, &authority, 1, "ipfs://1", None);
+        let burn =
+            instruction::burn_nft(&program_id, &Pubkey::new_unique(), &collection, &authority, &authority, &authority, None);
+        let game = Instruction::new_with_bytes(other_program, &[], vec![]);
+        let message = Message::new(&[mint.clone(), game], Some(&authority));
+        let mut account_keys = message.account_keys.clone();
+        account_keys.extend([burn.accounts[0].pubkey, burn.accounts[4].pubkey]);
+
+        // 由其他程序通过CPI发起的销毁
+        // A burn issued by another program through CPI
+        let inner_burn = CompiledInstruction::new_from_raw_parts(
+            account_keys.iter().position(|key| *key == program_id).unwrap() as u8,
+            burn.data.clone(),
+            burn.accounts
+                .iter()
+                .map(|meta| account_keys.iter().position(|key| *key == meta.pubkey).unwrap() as u8)
+                .collect(),
+        );
+
+        // 无法解码的内部指令
+        // An inner instruction that fails to decode
+        let inner_garbage = CompiledInstruction::new_from_raw_parts(inner_burn.program_id_index, vec![255, 1], vec![]);
+
+        let burned = NFTEvent::NFTBurned {
+            collection,
+            nft: burn.accounts[0].pubkey,
+            owner: authority,
+            serial_number: 1,
+        };
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            format!("Program data: {}", to_base64(&burned.try_to_vec().unwrap())),
+            format!("Program {} success", program_id),
+            format!("Program {} invoke [1]", program_id),
+            format!("Program {} failed: custom program error: 0x5", program_id),
+        ];
+        let records = decode_transaction(
+            &program_id,
+            &account_keys,
+            &message.instructions,
+            &[(1, vec![inner_burn, inner_garbage])],
+            &logs,
+        );
+
+        // 其他程序的指令被跳过, 铸造、内部销毁和无法解码的指令按执行顺序排列
+        // The other program's instruction is skipped, mint, inner burn and undecodable one follow execution order
+        assert_eq!(records.instructions.len(), 3);
+        let decoded_mint = &records.instructions[0];
+        assert!(matches!(
+            decoded_mint.instruction,
+            Ok(NFTInstruction::MintNFT { serial_number: 1, .. })
+        ));
+        assert_eq!((decoded_mint.top_level_index, decoded_mint.is_inner), (0, false));
+        assert_eq!(decoded_mint.accounts, mint.accounts.iter().map(|meta| meta.pubkey).collect::<Vec<_>>());
+        assert!(matches!(records.instructions[1].instruction, Ok(NFTInstruction::BurnNFT { .. })));
+        assert_eq!(records.instructions[1].data, burn.data);
+        assert_eq!((records.instructions[1].top_level_index, records.instructions[1].is_inner), (1, true));
+        let garbage = &records.instructions[2];
+        assert!(matches!(garbage.instruction, Err(NFTError::InvalidInstruction)));
+        assert_eq!(garbage.data, vec![255, 1]);
+        assert!(garbage.accounts.is_empty());
+
+        assert_eq!(records.events, vec![burned]);
+        assert_eq!(records.error.and_then(|error| error.error), Some(NFTError::InsufficientAuthority));
+
+        // 无法解析的指令数据返回错误
+        // Unparseable instruction data returns an error
+        assert_eq!(decode_instruction(&[255]).unwrap_err(), NFTError::InvalidInstruction);
+    }
+
+    // 测试账户按程序规则解码, 结果与夹具的JSON一致; 旧版本、未初始化和未知类型的账户返回解码后的错误
+    // Test that accounts decode with the program's rules into the fixtures' JSON; old versions, uninitialized and
+    // unknown account types return decoded errors
+    #[test]
+    fn test_decode_accounts() {
+        let fixtures: Value = serde_json::from_str(include_str!("../../fixtures/borsh_fixtures.json")).unwrap();
+        let mut collection_data = Vec::new();
+        for fixture in fixtures["accounts"].as_array().unwrap() {
+            let hex = fixture["hex"].as_str().unwrap();
+            let mut data: Vec<u8> =
+                (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect();
+
+            // 账户按当前版本的大小分配, 其余是预留空间
+            // Accounts are allocated at the current version's size, the rest is spare space
+            let size = match fixture["name"].as_str().unwrap() {
+                "CollectionAccount" => COLLECTION_ACCOUNT_SIZE,
+                "NFTAccount" => NFT_ACCOUNT_SIZE,
+                "OperatorApproval" => OPERATOR_APPROVAL_SIZE,
+                "Listing" => LISTING_SIZE,
+                name => panic!("未知账户 | unknown account {}", name),
+            };
+            data.resize(size, 0);
+            let decoded = decode_account(&data).unwrap();
+            assert_eq!(decoded.type_name(), fixture["name"]);
+            let expected = json!({ "type": fixture["name"], "data": fixture["value"] });
+            assert_eq!(decode_account_json(&data).unwrap(), expected);
+            if matches!(decoded, DecodedAccount::Collection(_)) {
+                collection_data = data;
+            }
+        }
+
+        assert_eq!(decode_account(&collection_data[..40]).unwrap_err(), ProgramError::InvalidAccountData);
+        collection_data[1] = 0;
+        assert!(decode_account_json(&collection_data).unwrap_err().starts_with("NFTError::UnsupportedAccountVersion"));
+        assert_eq!(decode_account(&[0; 8]).unwrap_err(), NFTError::NotInitialized.into());
+        assert!(decode_account_json(&[9, 1]).unwrap_err().starts_with("NFTError::AccountTypeMismatch"));
+    }
+
+    fn to_base64(data: &[u8]) -> String {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        STANDARD.encode(data)
+    }
+}
@@ -0,0 +1,599 @@
+use std::collections::HashMap;
+
+use nft_contract::instruction::INSTRUCTION_ACCOUNTS;
+use serde::{Deserialize, Serialize};
+use syn::{Attribute, Expr, Fields, File, ImplItem, Item, ItemEnum, ItemStruct, Lit, LitStr, Meta, Type};
+
+#[cfg(test)]
+mod test;
+
+// 程序源码在编译时读入, 源码改变时IDL随之重新生成; 指令账户的名称和标志来自程序的账户表
+// Program sources are read at compile time, so the IDL is regenerated whenever they change; instruction account names
+// and flags come from the program's account tables
+const CARGO_MANIFEST: &str = include_str!("../../nft-contract/Cargo.toml");
+const INSTRUCTION_SOURCE: &str = include_str!("../../nft-contract/src/instruction.rs");
+const STATE_SOURCE: &str = include_str!("../../nft-contract/src/state.rs");
+const ERROR_SOURCE: &str = include_str!("../../nft-contract/src/error.rs");
+const EVENT_SOURCE: &str = include_str!("../../nft-contract/src/event.rs");
+
+/// 提交到仓库的IDL文件路径 (相对于工作区根目录)
+/// Path of the IDL file committed to the repository (relative to the workspace root)
+pub const IDL_PATH: &str = "idl/nft_contract.json";
+
+/// Anchor 0.30 格式的IDL; 本程序使用Borsh枚举标签而非Anchor的哈希, 因此鉴别符都是显式给出的
+/// IDL in the Anchor 0.30 format; this program uses Borsh enum tags rather than Anchor hashes, so every discriminator is explicit
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Idl {
+    pub metadata: IdlMetadata,
+    pub instructions: Vec<IdlInstruction>,
+    pub accounts: Vec<IdlAccount>,
+    pub events: Vec<IdlEvent>,
+    pub errors: Vec<IdlError>,
+    pub types: Vec<IdlTypeDef>,
+}
+
+/// IDL元数据
+/// IDL metadata
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlMetadata {
+    pub name: String,
+    pub version: String,
+    pub spec: String,
+    pub description: String,
+}
+
+/// 指令定义
+/// Instruction definition
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlInstruction {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<IdlInstructionAccount>,
+    pub args: Vec<IdlField>,
+}
+
+/// 指令账户
+/// Instruction account
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlInstructionAccount {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub writable: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub signer: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+}
+
+/// 账户定义, 鉴别符为账户类型标识和版本
+/// Account definition, the discriminator is the account key followed by the version
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlAccount {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+/// 事件定义
+/// Event definition
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlEvent {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+}
+
+/// 错误定义
+/// Error definition
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlError {
+    pub code: u32,
+    pub name: String,
+    pub msg: String,
+}
+
+/// 字段
+/// Field
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+/// 类型定义
+/// Type definition
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlTypeDef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+/// 结构体或枚举
+/// Struct or enum
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefTy {
+    Struct { fields: Vec<IdlField> },
+    Enum { variants: Vec<IdlEnumVariant> },
+}
+
+/// 枚举变体
+/// Enum variant
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<IdlField>>,
+}
+
+/// 字段类型
+/// Field type
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum IdlType {
+    Primitive(String),
+    Option { option: Box<IdlType> },
+    Vec { vec: Box<IdlType> },
+    Array { array: (Box<IdlType>, usize) },
+    Defined { defined: IdlDefined },
+}
+
+/// 引用的自定义类型
+/// Referenced user-defined type
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IdlDefined {
+    pub name: String,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// 由程序源码生成IDL
+/// Generate the IDL from the program sources
+pub fn generate() -> Idl {
+    let instruction_file = parse(INSTRUCTION_SOURCE);
+    let state_file = parse(STATE_SOURCE);
+    let error_file = parse(ERROR_SOURCE);
+    let event_file = parse(EVENT_SOURCE);
+
+    let instructions: Vec<IdlInstruction> = find_enum(&instruction_file, "NFTInstruction")
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(index, variant)| instruction(index, &variant.attrs, &variant.ident.to_string(), &variant.fields))
+        .collect();
+
+    // 账户定义来自 `ProgramAccount` 实现中的 KEY 和 VERSION
+    // Account definitions come from KEY and VERSION in the `ProgramAccount` impls
+    let account_keys: Vec<String> = find_enum(&state_file, "AccountKey")
+        .variants
+        .iter()
+        .map(|variant| variant.ident.to_string())
+        .collect();
+    let mut accounts = Vec::new();
+    let mut account_types = Vec::new();
+    for item in &state_file.items {
+        let Item::Impl(item_impl) = item else { continue };
+        let is_program_account = item_impl
+            .trait_
+            .as_ref()
+            .is_some_and(|(_, path, _)| path.is_ident("ProgramAccount"));
+        if !is_program_account {
+            continue;
+        }
+        let name = type_name(&item_impl.self_ty);
+        let mut key = None;
+        let mut version = None;
+        for impl_item in &item_impl.items {
+            let ImplItem::Const(constant) = impl_item else { continue };
+            match (constant.ident.to_string().as_str(), &constant.expr) {
+                ("KEY", Expr::Path(path)) => {
+                    let variant = path.path.segments.last().unwrap().ident.to_string();
+                    key = account_keys.iter().position(|name| *name == variant);
+                }
+                ("VERSION", Expr::Lit(lit)) => {
+                    if let Lit::Int(int) = &lit.lit {
+                        version = int.base10_parse::<u8>().ok();
+                    }
+                }
+                _ => {}
+            }
+        }
+        let (Some(key), Some(version)) = (key, version) else {
+            panic!("{} 缺少 KEY 或 VERSION | {} is missing KEY or VERSION", name, name);
+        };
+        accounts.push(IdlAccount { name: name.clone(), discriminator: vec![key as u8, version] });
+        account_types.push(name);
+    }
+
+    let errors = find_enum(&error_file, "NFTError")
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(code, variant)| IdlError {
+            code: code as u32,
+            name: variant.ident.to_string(),
+            msg: error_message(&variant.attrs),
+        })
+        .collect();
+
+    // 事件的字段作为同名结构体放入类型列表
+    // Event fields go into the type list as a struct of the same name
+    let mut types = Vec::new();
+    let mut events = Vec::new();
+    for (index, variant) in find_enum(&event_file, "NFTEvent").variants.iter().enumerate() {
+        let name = variant.ident.to_string();
+        events.push(IdlEvent { name: name.clone(), discriminator: vec![index as u8] });
+        types.push(IdlTypeDef {
+            name,
+            docs: english_docs(&variant.attrs),
+            ty: IdlTypeDefTy::Struct { fields: fields(&variant.fields) },
+        });
+    }
+
+    // 账户结构体去掉由鉴别符表示的 key 和 version 字段
+    // Account structs drop the key and version fields that the discriminator represents
+    let mut account_type_defs = Vec::new();
+    for name in &account_types {
+        let item = find_struct(&state_file, name);
+        let fields = fields(&item.fields)
+            .into_iter()
+            .filter(|field| field.name != "key" && field.name != "version")
+            .collect();
+        account_type_defs.push(IdlTypeDef {
+            name: name.clone(),
+            docs: english_docs(&item.attrs),
+            ty: IdlTypeDefTy::Struct { fields },
+        });
+    }
+    account_type_defs.append(&mut types);
+    let mut types = account_type_defs;
+
+    // 按首次引用的顺序加入被引用的自定义类型
+    // Add referenced user-defined types in order of first reference
+    let mut referenced = Vec::new();
+    let arg_types = instructions_arg_types(&instructions);
+    collect_defined(arg_types.iter().copied().chain(types.iter().flat_map(type_def_field_types)), &mut referenced);
+    let definitions = type_definitions(&state_file);
+    let mut index = 0;
+    while index < referenced.len() {
+        let name = referenced[index].clone();
+        index += 1;
+        if types.iter().any(|ty| ty.name == name) {
+            continue;
+        }
+        let definition = definitions
+            .get(name.as_str())
+            .unwrap_or_else(|| panic!("未找到类型 {} | Type {} not found", name, name));
+        let type_def = type_def(definition);
+        collect_defined(type_def_field_types(&type_def), &mut referenced);
+        types.push(type_def);
+    }
+
+    Idl {
+        metadata: IdlMetadata {
+            name: "nft_contract".to_string(),
+            version: manifest_value("version"),
+            spec: "0.1.0".to_string(),
+            description: manifest_value("description"),
+        },
+        instructions,
+        accounts,
+        events,
+        errors,
+        types,
+    }
+}
+
+/// 生成格式化的IDL JSON (以换行结尾)
+/// Generate the formatted IDL JSON (ending with a newline)
+pub fn generate_json() -> String {
+    let mut json = serde_json::to_string_pretty(&generate()).expect("IDL可以序列化 | IDL is serializable");
+    json.push('\n');
+    json
+}
+
+fn parse(source: &str) -> File {
+    syn::parse_file(source).expect("程序源码可以解析 | program source parses")
+}
+
+fn find_enum<'a>(file: &'a File, name: &str) -> &'a ItemEnum {
+    file.items
+        .iter()
+        .find_map(|item| match item {
+            Item::Enum(item) if item.ident == name => Some(item),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("未找到枚举 {} | Enum {} not found", name, name))
+}
+
+fn find_struct<'a>(file: &'a File, name: &str) -> &'a ItemStruct {
+    file.items
+        .iter()
+        .find_map(|item| match item {
+            Item::Struct(item) if item.ident == name => Some(item),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("未找到结构体 {} | Struct {} not found", name, name))
+}
+
+/// 源码中的结构体和枚举定义
+/// Struct and enum definitions in a source file
+enum Definition<'a> {
+    Struct(&'a ItemStruct),
+    Enum(&'a ItemEnum),
+}
+
+fn type_definitions(file: &File) -> HashMap<String, Definition<'_>> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some((item.ident.to_string(), Definition::Struct(item))),
+            Item::Enum(item) => Some((item.ident.to_string(), Definition::Enum(item))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn type_def(definition: &Definition) -> IdlTypeDef {
+    match definition {
+        Definition::Struct(item) => IdlTypeDef {
+            name: item.ident.to_string(),
+            docs: english_docs(&item.attrs),
+            ty: IdlTypeDefTy::Struct { fields: fields(&item.fields) },
+        },
+        Definition::Enum(item) => IdlTypeDef {
+            name: item.ident.to_string(),
+            docs: english_docs(&item.attrs),
+            ty: IdlTypeDefTy::Enum {
+                variants: item
+                    .variants
+                    .iter()
+                    .map(|variant| IdlEnumVariant {
+                        name: variant.ident.to_string(),
+                        fields: match variant.fields {
+                            Fields::Unit => None,
+                            _ => Some(fields(&variant.fields)),
+                        },
+                    })
+                    .collect(),
+            },
+        },
+    }
+}
+
+fn type_def_field_types(type_def: &IdlTypeDef) -> Vec<&IdlType> {
+    match &type_def.ty {
+        IdlTypeDefTy::Struct { fields } => fields.iter().map(|field| &field.ty).collect(),
+        IdlTypeDefTy::Enum { variants } => variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().flatten().map(|field| &field.ty))
+            .collect(),
+    }
+}
+
+fn instructions_arg_types(instructions: &[IdlInstruction]) -> Vec<&IdlType> {
+    instructions.iter().flat_map(|ix| ix.args.iter().map(|arg| &arg.ty)).collect()
+}
+
+fn collect_defined<'a>(types: impl IntoIterator<Item = &'a IdlType>, names: &mut Vec<String>) {
+    for ty in types {
+        match ty {
+            IdlType::Primitive(_) => {}
+            IdlType::Option { option: inner } | IdlType::Vec { vec: inner } | IdlType::Array { array: (inner, _) } => {
+                collect_defined([inner.as_ref()], names)
+            }
+            IdlType::Defined { defined } => {
+                if !names.contains(&defined.name) {
+                    names.push(defined.name.clone());
+                }
+            }
+        }
+    }
+}
+
+/// 由 `NFTInstruction` 变体、账户表和账户文档生成指令定义
+/// Build an instruction definition from an `NFTInstruction` variant, its account table and its account docs
+fn instruction(index: usize, attrs: &[Attribute], name: &str, variant_fields: &Fields) -> IdlInstruction {
+    let table = INSTRUCTION_ACCOUNTS
+        .get(index)
+        .unwrap_or_else(|| panic!("{} 缺少账户表 | {} has no account table", name, name));
+    let lines = doc_lines(attrs);
+    let mut docs: Vec<String> = lines.get(1).cloned().into_iter().collect();
+    let mut account_docs = Vec::new();
+    for line in &lines {
+        if let Some(account_doc) = account_doc(line) {
+            account_docs.push(account_doc);
+        } else if line.starts_with("   ") && line.is_ascii() {
+            // 账户列表后的英文补充说明, 例如 BuyNFT 的版税接收者
+            // English notes after the account list, such as the royalty recipients of BuyNFT
+            docs.push(line.trim().to_string());
+        }
+    }
+    assert!(
+        account_docs.len() == table.len(),
+        "{} 的账户文档与账户表数量不一致 | {} documents a different number of accounts than its account table",
+        name,
+        name
+    );
+
+    // 文档中的标志必须与账户表一致
+    // Flags in the docs must agree with the account table
+    let accounts = table
+        .iter()
+        .zip(account_docs)
+        .map(|(account, (flags, english))| {
+            assert!(
+                flags.contains("writable") == account.writable
+                    && flags.contains("signer") == account.signer
+                    && english.contains("(optional") == account.optional,
+                "{}.{} 的文档与账户表不一致 | {}.{} docs disagree with the account table",
+                name,
+                account.name,
+                name,
+                account.name
+            );
+            IdlInstructionAccount {
+                name: account.name.to_string(),
+                docs: vec![english],
+                writable: account.writable,
+                signer: account.signer,
+                optional: account.optional,
+                address: (account.name == "system_program").then(|| "11111111111111111111111111111111".to_string()),
+            }
+        })
+        .collect();
+    IdlInstruction {
+        name: snake_case(name),
+        docs,
+        discriminator: vec![index as u8],
+        accounts,
+        args: fields(variant_fields),
+    }
+}
+
+/// 解析形如 "0. `[writable, signer]` 中文 | English" 的账户文档, 返回标志和英文说明
+/// Parse an account doc line shaped like "0. `[writable, signer]` 中文 | English" into its flags and English text
+fn account_doc(line: &str) -> Option<(String, String)> {
+    let (position, rest) = line.split_once(". `[")?;
+    if position.trim().parse::<usize>().is_err() {
+        return None;
+    }
+    let (flags, description) = rest.split_once("]`")?;
+    let english = description.rsplit_once(" | ").map_or(description, |(_, english)| english).trim();
+    Some((flags.to_string(), english.to_string()))
+}
+
+fn fields(fields: &Fields) -> Vec<IdlField> {
+    match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|field| IdlField {
+                name: field.ident.as_ref().unwrap().to_string(),
+                docs: english_docs(&field.attrs),
+                ty: idl_type(&field.ty),
+            })
+            .collect(),
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(_) => panic!("不支持元组字段 | Tuple fields are not supported"),
+    }
+}
+
+/// 将Rust类型映射为IDL类型
+/// Map a Rust type to an IDL type
+fn idl_type(ty: &Type) -> IdlType {
+    match ty {
+        Type::Array(array) => {
+            let Expr::Lit(lit) = &array.len else { panic!("数组长度必须是字面量 | Array length must be a literal") };
+            let Lit::Int(len) = &lit.lit else { panic!("数组长度必须是整数 | Array length must be an integer") };
+            IdlType::Array { array: (Box::new(idl_type(&array.elem)), len.base10_parse().unwrap()) }
+        }
+        Type::Path(path) => {
+            let segment = path.path.segments.last().unwrap();
+            let generic = || match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(syn::GenericArgument::Type(inner)) => Box::new(idl_type(inner)),
+                    _ => panic!("不支持的泛型参数 | Unsupported generic argument"),
+                },
+                _ => panic!("缺少泛型参数 | Missing generic argument"),
+            };
+            let name = segment.ident.to_string();
+            match name.as_str() {
+                "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" | "bool" => {
+                    IdlType::Primitive(name)
+                }
+                "String" => IdlType::Primitive("string".to_string()),
+                "Pubkey" => IdlType::Primitive("pubkey".to_string()),
+                "Option" => IdlType::Option { option: generic() },
+                "Vec" => IdlType::Vec { vec: generic() },
+                _ => IdlType::Defined { defined: IdlDefined { name } },
+            }
+        }
+        _ => panic!("不支持的类型 | Unsupported type"),
+    }
+}
+
+fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) => path.path.segments.last().unwrap().ident.to_string(),
+        _ => panic!("不支持的类型 | Unsupported type"),
+    }
+}
+
+/// 读取文档注释行 (去掉 `///` 后的第一个空格)
+/// Read doc comment lines (dropping the first space after `///`)
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Str(text) => Some(text.value().strip_prefix(' ').unwrap_or(&text.value()).to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// 双语文档的英文行 (第二行)
+/// The English line of a bilingual doc comment (the second line)
+fn english_docs(attrs: &[Attribute]) -> Vec<String> {
+    let lines = doc_lines(attrs);
+    lines.get(1).or(lines.first()).cloned().into_iter().collect()
+}
+
+/// `#[error("中文 | English")]` 中的消息
+/// The message in `#[error("中文 | English")]`
+fn error_message(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("error"))
+        .and_then(|attr| attr.parse_args::<LitStr>().ok())
+        .map(|message| message.value())
+        .unwrap_or_default()
+}
+
+/// 读取程序清单中的字段
+/// Read a field from the program manifest
+fn manifest_value(key: &str) -> String {
+    CARGO_MANIFEST
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            (name.trim() == key).then(|| value.trim().trim_matches('"').to_string())
+        })
+        .unwrap_or_default()
+}
+
+/// 将 PascalCase 转为 snake_case, 连续大写视为一个词 (MintNFT -> mint_nft)
+/// Convert PascalCase to snake_case, treating a run of capitals as one word (MintNFT -> mint_nft)
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let previous_lower = chars[index - 1].is_lowercase();
+            let next_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if previous_lower || (chars[index - 1].is_uppercase() && next_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
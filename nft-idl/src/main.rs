@@ -0,0 +1,9 @@
+use std::{fs, path::Path};
+
+// 重新生成提交到仓库的IDL文件
+// Regenerate the IDL file committed to the repository
+fn main() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(nft_idl::IDL_PATH);
+    fs::write(&path, nft_idl::generate_json()).expect("写入IDL文件失败 | failed to write the IDL file");
+    println!("IDL已写入 | IDL written to {}", nft_idl::IDL_PATH);
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::{fs, path::Path};
+
+    // 提交的IDL与程序源码不一致时失败
+    // Fails when the committed IDL no longer matches the program sources
+    #[test]
+    fn test_idl_is_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(IDL_PATH);
+        let committed = fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            committed == generate_json(),
+            "IDL已过期, 请运行 `cargo run -p nft-idl` | {} is stale, run `cargo run -p nft-idl`",
+            IDL_PATH
+        );
+    }
+
+    // 生成的IDL可以读回, 类型、可选标志和文档都保留, 重新序列化后不变
+    // The generated IDL reads back with its types, optional flags and docs kept, and serializes unchanged
+    #[test]
+    fn test_idl_round_trips() {
+        let json = generate_json();
+        let parsed: Idl = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string_pretty(&parsed).unwrap() + "\n", json);
+
+        let mint = parsed.instructions.iter().find(|ix| ix.name == "mint_nft").unwrap();
+        assert!(mint.accounts.iter().any(|account| account.name == "payer" && account.optional));
+        let recipient = mint.args.iter().find(|arg| arg.name == "recipient").unwrap();
+        assert_eq!(recipient.ty, IdlType::Option { option: Box::new(IdlType::Primitive("pubkey".to_string())) });
+        assert!(!recipient.docs.is_empty());
+
+        let collection = parsed.types.iter().find(|ty| ty.name == "CollectionAccount").unwrap();
+        let IdlTypeDefTy::Struct { fields } = &collection.ty else { panic!("应为结构体 | expected a struct") };
+        let transfer_mode = fields.iter().find(|field| field.name == "transfer_mode").unwrap();
+        assert_eq!(
+            transfer_mode.ty,
+            IdlType::Defined { defined: IdlDefined { name: "TransferMode".to_string() } }
+        );
+
+        // 缺省的可选字段按默认值读入
+        // Omitted optional fields read back as their defaults
+        let account: IdlInstructionAccount = serde_json::from_str(r#"{"name": "nft"}"#).unwrap();
+        assert!(!account.writable && !account.signer && !account.optional && account.docs.is_empty());
+    }
+
+    // 测试指令、账户和错误的生成结果
+    // Test the generated instructions, accounts and errors
+    #[test]
+    fn test_generated_definitions() {
+        let idl = generate();
+
+        let mint = idl.instructions.iter().find(|ix| ix.name == "mint_nft").unwrap();
+        assert_eq!(mint.discriminator, vec![1]);
+        let accounts: Vec<_> = mint
+            .accounts
+            .iter()
+            .map(|account| (account.name.as_str(), account.writable, account.signer, account.optional))
+            .collect();
+        assert_eq!(
+            accounts,
+            vec![
+                ("collection", true, false, false),
+                ("nft", true, false, false),
+                ("authority", true, true, false),
+                ("system_program", false, false, false),
+                ("payer", true, true, true),
+            ]
+        );
+        let recipient = mint.args.iter().find(|arg| arg.name == "recipient").unwrap();
+        assert_eq!(
+            recipient.ty,
+            IdlType::Option { option: Box::new(IdlType::Primitive("pubkey".to_string())) }
+        );
+
+        // 账户名称来自账户表, 而不是文档中的描述
+        // Account names come from the account table rather than the doc descriptions
+        let transfer = idl.instructions.iter().find(|ix| ix.name == "transfer_nft").unwrap();
+        let names: Vec<_> = transfer.accounts.iter().map(|account| account.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["nft", "signer", "new_owner", "collection", "listing", "listing_seller", "operator_approval"]
+        );
+        assert_eq!(transfer.accounts[4].docs, vec!["Listing PDA, closed when it exists"]);
+
+        let collection = idl.accounts.iter().find(|account| account.name == "CollectionAccount").unwrap();
+        assert_eq!(collection.discriminator, vec![1, 3]);
+        assert!(idl.types.iter().any(|ty| ty.name == "TransferMode"));
+        assert!(idl.types.iter().any(|ty| ty.name == "NFTMinted"));
+
+        let error = &idl.errors[6];
+        assert_eq!((error.code, error.name.as_str()), (6, "AlreadyMinted"));
+        assert_eq!(error.msg, "NFT已铸造 | NFT already minted");
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("InitializeCollection"), "initialize_collection");
+        assert_eq!(snake_case("MintNFT"), "mint_nft");
+        assert_eq!(snake_case("UpdateNFTMetadata"), "update_nft_metadata");
+    }
+}
@@ -0,0 +1,226 @@
+use borsh::BorshSerialize;
+use nft_contract::{
+    instruction::NFTInstruction,
+    state::{find_collection_address, find_nft_address, Creator, SerialMode, TransferMode},
+};
+use serde_json::Value;
+use solana_program::pubkey::Pubkey;
+use wasm_bindgen::prelude::*;
+
+#[cfg(test)]
+mod test;
+
+/// 推导出的PDA地址及其bump
+/// Derived PDA address and its bump
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedAddress {
+    /// Base58编码的地址
+    /// Base58-encoded address
+    pub address: String,
+
+    /// PDA种子bump
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// 推导集合账户地址
+/// Derive the collection account address
+#[wasm_bindgen(js_name = deriveCollectionAddress)]
+pub fn derive_collection_address(program_id: &str, authority: &str, name: &str) -> Result<DerivedAddress, String> {
+    let (address, bump) = find_collection_address(&parse_pubkey(program_id)?, &parse_pubkey(authority)?, name);
+    Ok(DerivedAddress { address: address.to_string(), bump })
+}
+
+/// 推导NFT账户地址
+/// Derive the NFT account address
+#[wasm_bindgen(js_name = deriveNftAddress)]
+pub fn derive_nft_address(program_id: &str, collection: &str, serial_number: u64) -> Result<DerivedAddress, String> {
+    let (address, bump) = find_nft_address(&parse_pubkey(program_id)?, &parse_pubkey(collection)?, serial_number);
+    Ok(DerivedAddress { address: address.to_string(), bump })
+}
+
+/// 按指令名 (如 "mint_nft") 和参数对象编码指令数据, 参数格式与 `fixtures/borsh_fixtures.json` 相同
+/// Encode instruction data from an instruction name (e.g. "mint_nft") and an args object, in the same format as
+/// `fixtures/borsh_fixtures.json`
+#[wasm_bindgen(js_name = encodeInstruction)]
+pub fn encode_instruction(name: &str, args: JsValue) -> Result<Vec<u8>, JsValue> {
+    let args_json = if args.is_undefined() || args.is_null() {
+        "{}".to_string()
+    } else {
+        String::from(js_sys::JSON::stringify(&args)?)
+    };
+    Ok(encode_instruction_json(name, &args_json)?)
+}
+
+/// 按程序的加载规则 (类型标识、版本和大小) 解码程序账户数据, 返回 `{ type, data }`
+/// Decode program account data with the program's loading rules (discriminator, version and size), returning
+/// `{ type, data }`
+#[wasm_bindgen(js_name = decodeAccount)]
+pub fn decode_account(data: &[u8]) -> Result<JsValue, JsValue> {
+    let decoded = nft_decoder::decode_account_json(data)?;
+    js_sys::JSON::parse(&decoded.to_string())
+}
+
+/// 由JSON参数构造程序的 `NFTInstruction` 并序列化, 与链上解析的字节完全一致
+/// Build the program's `NFTInstruction` from JSON args and serialize it, so the bytes are exactly what the program
+/// parses
+pub fn encode_instruction_json(name: &str, args_json: &str) -> Result<Vec<u8>, String> {
+    let args: Value =
+        serde_json::from_str(args_json).map_err(|error| format!("无效的JSON | invalid JSON: {}", error))?;
+    let args = &args;
+    let instruction = match name {
+        "initialize_collection" => NFTInstruction::InitializeCollection {
+            name: field(args, "name")?,
+            symbol: field(args, "symbol")?,
+            uri: field(args, "uri")?,
+            royalty_percentage: field(args, "royalty_percentage")?,
+            is_mutable: field(args, "is_mutable")?,
+            transfer_mode: field(args, "transfer_mode")?,
+            creators: field(args, "creators")?,
+            max_supply: field(args, "max_supply")?,
+            serial_mode: field(args, "serial_mode")?,
+            bump: field(args, "bump")?,
+        },
+        "mint_nft" => NFTInstruction::MintNFT {
+            metadata_uri: field(args, "metadata_uri")?,
+            serial_number: field(args, "serial_number")?,
+            bump: field(args, "bump")?,
+            recipient: field(args, "recipient")?,
+        },
+        "transfer_nft" => NFTInstruction::TransferNFT {
+            new_owner: field(args, "new_owner")?,
+            listing_bump: field(args, "listing_bump")?,
+        },
+        "burn_nft" => NFTInstruction::BurnNFT { listing_bump: field(args, "listing_bump")? },
+        "update_nft_metadata" => {
+            NFTInstruction::UpdateNFTMetadata { new_metadata_uri: field(args, "new_metadata_uri")? }
+        }
+        "update_collection_metadata" => NFTInstruction::UpdateCollectionMetadata { new_uri: field(args, "new_uri")? },
+        "propose_collection_authority" => {
+            NFTInstruction::ProposeCollectionAuthority { new_authority: field(args, "new_authority")? }
+        }
+        "accept_collection_authority" => NFTInstruction::AcceptCollectionAuthority,
+        "approve_delegate" => NFTInstruction::ApproveDelegate { delegate: field(args, "delegate")? },
+        "revoke_delegate" => NFTInstruction::RevokeDelegate,
+        "set_operator_approval" => NFTInstruction::SetOperatorApproval {
+            operator: field(args, "operator")?,
+            approved: field(args, "approved")?,
+            expires_at: field(args, "expires_at")?,
+            bump: field(args, "bump")?,
+        },
+        "set_freeze_authority" => {
+            NFTInstruction::SetFreezeAuthority { freeze_authority: field(args, "freeze_authority")? }
+        }
+        "freeze_nft" => NFTInstruction::FreezeNFT,
+        "thaw_nft" => NFTInstruction::ThawNFT,
+        "list_nft" => NFTInstruction::ListNFT { price: field(args, "price")?, bump: field(args, "bump")? },
+        "delist_nft" => NFTInstruction::DelistNFT,
+        "buy_nft" => NFTInstruction::BuyNFT { price: field(args, "price")? },
+        "sign_creator" => NFTInstruction::SignCreator,
+        "migrate_account" => NFTInstruction::MigrateAccount,
+        name => return Err(format!("未知指令 | unknown instruction {}", name)),
+    };
+    Ok(instruction.try_to_vec().expect("序列化到内存不会失败 | serializing into memory cannot fail"))
+}
+
+// 可从JSON参数读取的值, 格式与 `fixtures/borsh_fixtures.json` 相同
+// Values readable from JSON args, in the same format as `fixtures/borsh_fixtures.json`
+trait FromJson: Sized {
+    fn from_json(value: &Value) -> Option<Self>;
+}
+
+impl FromJson for u8 {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_u64()?.try_into().ok()
+    }
+}
+
+// 64位整数接受字符串, 也接受安全范围内的数字
+// 64-bit integers accept strings as well as numbers within the safe range
+impl FromJson for u64 {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_u64().or_else(|| value.as_str()?.parse().ok())
+    }
+}
+
+impl FromJson for i64 {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_i64().or_else(|| value.as_str()?.parse().ok())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_str().map(str::to_string)
+    }
+}
+
+impl FromJson for Pubkey {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_str()?.parse().ok()
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => Some(None),
+            value => T::from_json(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Option<Self> {
+        value.as_array()?.iter().map(T::from_json).collect()
+    }
+}
+
+impl FromJson for TransferMode {
+    fn from_json(value: &Value) -> Option<Self> {
+        match value.as_str()? {
+            "Transferable" => Some(TransferMode::Transferable),
+            "Soulbound" => Some(TransferMode::Soulbound),
+            "AuthorityRevocable" => Some(TransferMode::AuthorityRevocable),
+            _ => None,
+        }
+    }
+}
+
+impl FromJson for SerialMode {
+    fn from_json(value: &Value) -> Option<Self> {
+        match value.as_str()? {
+            "CallerAssigned" => Some(SerialMode::CallerAssigned),
+            "Sequential" => Some(SerialMode::Sequential),
+            _ => None,
+        }
+    }
+}
+
+impl FromJson for Creator {
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Creator {
+            address: FromJson::from_json(value.get("address")?)?,
+            verified: FromJson::from_json(value.get("verified")?)?,
+            share: FromJson::from_json(value.get("share")?)?,
+        })
+    }
+}
+
+// 读取必需的参数字段; 可选值也必须给出, 用 null 表示空
+// Read a required args field; optional values must be present too, with null for none
+fn field<T: FromJson>(args: &Value, name: &str) -> Result<T, String> {
+    let value = args.get(name).ok_or_else(|| format!("缺少字段 | missing field {}", name))?;
+    T::from_json(value).ok_or_else(|| format!("字段 {} 的值无效 | invalid value for field {}: {}", name, name, value))
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, String> {
+    value.parse().map_err(|_| format!("无效的公钥 | invalid public key: {}", value))
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use nft_contract::{instruction, state::find_nft_address};
+
+    // 测试PDA推导与程序的辅助函数一致
+    // Test that PDA derivation matches the program helpers
+    #[test]
+    fn test_derive_addresses() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let (collection, bump) = find_collection_address(&program_id, &authority, "Browser");
+        assert_eq!(
+            derive_collection_address(&program_id.to_string(), &authority.to_string(), "Browser").unwrap(),
+            DerivedAddress { address: collection.to_string(), bump }
+        );
+
+        let (nft, bump) = find_nft_address(&program_id, &collection, 7);
+        assert_eq!(
+            derive_nft_address(&program_id.to_string(), &collection.to_string(), 7).unwrap(),
+            DerivedAddress { address: nft.to_string(), bump }
+        );
+
+        assert!(derive_nft_address("not a key", &collection.to_string(), 7).unwrap_err().contains("invalid public key"));
+    }
+
+    // 测试由JSON编码的指令与指令构造函数的数据一致
+    // Test that instructions encoded from JSON match the instruction builders' data
+    #[test]
+    fn test_encode_instruction() {
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let collection = find_collection_address(&program_id, &payer, "Browser").0;
+        let expected = instruction::mint_nft(&program_id, &payer, &collection, &payer, 7, "ipfs://7", Some(recipient));
+        let bump = find_nft_address(&program_id, &collection, 7).1;
+
+        // 64位整数可以是数字或字符串
+        // 64-bit integers may be numbers or strings
+        for serial_number in ["7", "\"7\""] {
+            let args = format!(
+                r#"{{"metadata_uri": "ipfs://7", "serial_number": {}, "bump": {}, "recipient": "{}"}}"#,
+                serial_number, bump, recipient
+            );
+            assert_eq!(encode_instruction_json("mint_nft", &args).unwrap(), expected.data);
+        }
+        assert_eq!(encode_instruction_json("burn_nft", r#"{"listing_bump": 254}"#).unwrap(), vec![3, 254]);
+        assert_eq!(encode_instruction_json("accept_collection_authority", "{}").unwrap(), vec![7]);
+
+        assert!(encode_instruction_json("mint_everything", "{}").unwrap_err().contains("unknown instruction"));
+        assert!(encode_instruction_json("transfer_nft", "{}").unwrap_err().contains("missing field new_owner"));
+        assert!(encode_instruction_json("list_nft", r#"{"price": -1}"#)
+            .unwrap_err()
+            .contains("invalid value for field price"));
+    }
+
+    // 每个指令夹具的JSON值都编码为夹具的字节
+    // Every instruction fixture's JSON value encodes to the fixture bytes
+    #[test]
+    fn test_encode_instruction_fixtures() {
+        let fixtures: Value = serde_json::from_str(include_str!("../../fixtures/borsh_fixtures.json")).unwrap();
+        let instructions = fixtures["instructions"].as_array().unwrap();
+        for fixture in instructions {
+            let hex = fixture["hex"].as_str().unwrap();
+            let data: Vec<u8> =
+                (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect();
+            let name = fixture["name"].as_str().unwrap();
+            assert_eq!(encode_instruction_json(name, &fixture["value"].to_string()).unwrap(), data, "{}", name);
+        }
+
+        // 夹具覆盖程序的全部指令
+        // The fixtures cover every instruction of the program
+        let mut tags: Vec<&str> = instructions.iter().map(|fixture| &fixture["hex"].as_str().unwrap()[..2]).collect();
+        tags.dedup();
+        let last = NFTInstruction::MigrateAccount.try_to_vec().unwrap()[0];
+        assert_eq!(tags, (0..=last).map(|tag| format!("{:02x}", tag)).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,40 @@
+//! 在 Node.js 中运行: `wasm-pack test --node nft-wasm`, 或以 wasm-bindgen-test-runner 作为
+//! `CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER` 运行 `cargo test --target wasm32-unknown-unknown -p nft-wasm`
+//! Run in Node.js: `wasm-pack test --node nft-wasm`, or `cargo test --target wasm32-unknown-unknown -p nft-wasm` with
+//! wasm-bindgen-test-runner as `CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER`
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Reflect, JSON};
+use nft_contract::state::{Listing, ProgramAccount, LISTING_SIZE};
+use nft_wasm::*;
+use solana_program::pubkey::Pubkey;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn derives_addresses() {
+    let program_id = Pubkey::new_unique().to_string();
+    let collection = derive_collection_address(&program_id, &Pubkey::new_unique().to_string(), "Browser").unwrap();
+    let nft = derive_nft_address(&program_id, &collection.address, 1).unwrap();
+    assert_ne!(collection.address, nft.address);
+}
+
+#[wasm_bindgen_test]
+fn encodes_instruction_from_js_object() {
+    let args = JSON::parse(r#"{"metadata_uri": "ipfs://1", "serial_number": "1", "bump": 255, "recipient": null}"#)
+        .unwrap();
+    let data = encode_instruction("mint_nft", args).unwrap();
+    assert_eq!(data[0], 1);
+    assert_eq!(encode_instruction("accept_collection_authority", JsValue::UNDEFINED).unwrap(), vec![7]);
+    assert!(encode_instruction("transfer_nft", JsValue::UNDEFINED).is_err());
+}
+
+#[wasm_bindgen_test]
+fn decodes_account_to_js_object() {
+    let mut data = vec![0; LISTING_SIZE];
+    Listing::new(Pubkey::new_unique(), Pubkey::new_unique(), 42, 255).save(&mut data).unwrap();
+    let account = decode_account(&data).unwrap();
+    assert_eq!(Reflect::get(&account, &"type".into()).unwrap(), "Listing");
+    let fields = Reflect::get(&account, &"data".into()).unwrap();
+    assert_eq!(Reflect::get(&fields, &"price".into()).unwrap(), "42");
+}